@@ -53,12 +53,12 @@ mod usage {
     fn for_unrolled_simd(b: &mut Bencher) {
         let mut out = [0f32; 1024];
         b.iter(|| {
-            for (i, v) in [-123.456f32; 1024].simd_iter(f32s(0.0)).unroll(8).enumerate() {
+            for (i, v) in [-123.456f32; 1024].simd_iter(f32s(0.0)).unroll::<8>().enumerate() {
                 macro_rules! compute {
                     ($($idx:expr),*) => {
                         $(
                             let a = f32s(9.0) * v[$idx].abs().sqrt().rsqrt().ceil().sqrt() - f32s(4.0) - f32s(2.0);
-                            a.store(&mut out, (i + $idx) * a.width());
+                            a.store(&mut out, (i * 8 + $idx) * a.width());
                         )*
                     }
                 }
@@ -97,14 +97,14 @@ mod usage {
     fn high_latency_unrolled(b: &mut Bencher) {
         let mut out = [0f32; 1024];
         b.iter(|| {
-            for (i, v) in [-123.456f32; 1024].simd_iter(f32s(0.0)).unroll(8).enumerate() {
+            for (i, v) in [-123.456f32; 1024].simd_iter(f32s(0.0)).unroll::<8>().enumerate() {
                 macro_rules! compute {
                     ($($idx:expr),*) => {
                         $(
                             let (a, b) = (v[$idx] * f32s(1.20)).upcast();
                             let ans = (a.sqrt() * f64s(3.141592653589793)).saturating_downcast(
                                 b.sqrt() * f64s(3.141592653589793));
-                            ans.store(&mut out, (i + $idx) * ans.width());
+                            ans.store(&mut out, (i * 8 + $idx) * ans.width());
                         )*
                     }
                 }
@@ -330,6 +330,27 @@ mod usage {
         })
     }
 
+    #[bench]
+    fn base100_enc_interleaved(b: &mut Bencher) {
+        let mut out = [0u8; 4096];
+        b.iter(|| {
+            let mut i = 0;
+            [123u8; 1024].simd_iter(u8s(0)).simd_do_each(|v| {
+                let (a, b): (u16s, u16s) = v.upcast();
+                let third = ((a + u16s(55)) / u16s(64) + u16s(143)).saturating_downcast((b + u16s(55)) / u16s(64) + u16s(143));
+                let fourth = ((v + u8s(55)) & u8s(0x3f)) + u8s(128);
+
+                let (w, x, y, z) = u8s(0xf0).interleave_four(u8s(0x9f), third, fourth);
+                w.store(&mut out, i);
+                x.store(&mut out, i + v.width());
+                y.store(&mut out, i + v.width() * 2);
+                z.store(&mut out, i + v.width() * 3);
+                i += v.width() * 4;
+            });
+            out
+        })
+    }
+
     #[bench]
     fn base100_enc_scalar(b: &mut Bencher) {
         let mut out = [0u8; 4096];
@@ -365,4 +386,115 @@ mod usage {
                 .scalar_collect()
         })
     }
+
+    // Every element of a column is on its own cache line, and the matrix is
+    // much larger than the cache, so each load misses unless prefetched.
+    #[cfg(feature = "std")]
+    const STRIDE_COLUMNS: usize = 64;
+
+    #[cfg(feature = "std")]
+    fn stride_matrix() -> Vec<f32> {
+        (0..STRIDE_COLUMNS * 262144).map(|x| x as f32).collect()
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn stride_gather(b: &mut Bencher) {
+        let matrix = stride_matrix();
+        b.iter(|| {
+            (&matrix[..]).stride(STRIDE_COLUMNS, &[f32s(0.0); STRIDE_COLUMNS]).remove(0)
+                .simd_reduce(f32s(0.0), |acc, v| acc + v)
+                .sum()
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn stride_gather_prefetch(b: &mut Bencher) {
+        let matrix = stride_matrix();
+        b.iter(|| {
+            (&matrix[..]).stride(STRIDE_COLUMNS, &[f32s(0.0); STRIDE_COLUMNS]).remove(0)
+                .prefetch(8, Locality::None)
+                .simd_reduce(f32s(0.0), |acc, v| acc + v)
+                .sum()
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn sequential_scan(b: &mut Bencher) {
+        let matrix = stride_matrix();
+        b.iter(|| {
+            matrix.simd_iter(f32s(0.0))
+                .simd_reduce(f32s(0.0), |acc, v| acc + v)
+                .sum()
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn sequential_scan_prefetch(b: &mut Bencher) {
+        let matrix = stride_matrix();
+        b.iter(|| {
+            matrix.simd_iter(f32s(0.0))
+                .prefetch(16, Locality::None)
+                .simd_reduce(f32s(0.0), |acc, v| acc + v)
+                .sum()
+        })
+    }
+
+    #[cfg(feature = "std")]
+    fn sort_input(len: usize) -> Vec<u32> {
+        let mut state = 0x2545_F491u32;
+        (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        }).collect()
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn sort_u32_simd(b: &mut Bencher) {
+        let input = sort_input(4096);
+        b.iter(|| {
+            let mut data = input.clone();
+            faster::sort::sort_unstable(&mut data);
+            data
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn sort_u32_scalar(b: &mut Bencher) {
+        let input = sort_input(4096);
+        b.iter(|| {
+            let mut data = input.clone();
+            data.sort_unstable();
+            data
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn sort_f32_simd(b: &mut Bencher) {
+        let input = sort_input(4096).iter().map(|&x| x as f32 - 2e9).collect::<Vec<_>>();
+        b.iter(|| {
+            let mut data = input.clone();
+            faster::sort::sort_unstable(&mut data);
+            data
+        })
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn sort_f32_scalar(b: &mut Bencher) {
+        let input = sort_input(4096).iter().map(|&x| x as f32 - 2e9).collect::<Vec<_>>();
+        b.iter(|| {
+            let mut data = input.clone();
+            data.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+            data
+        })
+    }
 }
@@ -27,7 +27,7 @@ header = lambda imports: f"""
 
 #![allow(unused_imports)]
 
-use crate::arch::current::vecs::*;
+use super::vecs::*;
 use crate::std::mem::transmute;
 use crate::vecs::*;
 
@@ -0,0 +1,603 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Buffers of primitives aligned to a cache line.
+//!
+//! Loads and stores of vectors from a slice are unaligned, as a slice may
+//! begin anywhere. Unaligned accesses which straddle two cache lines are
+//! slower than those which don't, and the loads of a slice which doesn't
+//! begin on a vector boundary straddle them often. [`AlignedVec`] and
+//! [`AlignedBox`] always begin on a cache line, so every vector at an offset
+//! which is a multiple of its width is loaded and stored with aligned
+//! instructions.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//! use faster::aligned::AlignedVec;
+//!
+//! # fn main() {
+//! let data = AlignedVec::from_elem(3u32, 100);
+//! let sum = data.simd_iter(u32s(0))
+//!     .simd_reduce(u32s(0), |acc, v| acc + v)
+//!     .sum();
+//! assert_eq!(sum, 300);
+//! # }
+//! ```
+//!
+//! [`AlignedVec`]: struct.AlignedVec.html
+//! [`AlignedBox`]: struct.AlignedBox.html
+
+use crate::iters::{Locality, Prefetch, SIMDArray, SIMDArrayMut, SIMDIter, SIMDObject, SIMDSized};
+use crate::arch::current::prefetch::prefetch;
+use crate::vecs::{Packable, Packed};
+use crate::core::alloc::{alloc, dealloc, handle_alloc_error, realloc, Layout};
+use crate::core::fmt;
+use crate::core::iter::FromIterator;
+use crate::core::mem::{align_of, size_of};
+use crate::core::ops::{Deref, DerefMut};
+use crate::core::ptr::{self, NonNull};
+use crate::core::slice;
+
+/// The alignment of every buffer, which is the size of a cache line and of
+/// the widest vector.
+pub const ALIGNMENT: usize = 64;
+
+#[inline(always)]
+fn layout<T>(capacity: usize) -> Layout {
+    Layout::from_size_align(size_of::<T>().checked_mul(capacity).expect("capacity overflow"), ALIGNMENT)
+        .expect("capacity overflow")
+}
+
+#[inline(always)]
+fn dangling<T>() -> NonNull<T> {
+    // Aligned, so empty buffers are aligned too
+    unsafe { NonNull::new_unchecked(ALIGNMENT as *mut T) }
+}
+
+/// Return whether the `offset`th element of an aligned buffer is aligned to
+/// the vector `V`.
+#[inline(always)]
+fn is_aligned<V : Packed>(offset: usize) -> bool {
+    (offset * size_of::<V::Scalar>()) % align_of::<V>() == 0
+}
+
+/// A contiguous growable array of primitives, like `Vec<T>`, whose first
+/// element is aligned to [`ALIGNMENT`] bytes.
+///
+/// ```
+/// extern crate faster;
+/// use faster::aligned::{AlignedVec, ALIGNMENT};
+///
+/// # fn main() {
+/// let mut data = AlignedVec::new();
+/// data.extend_from_slice(&[1.0f32, 2.0]);
+/// data.push(3.0);
+/// assert_eq!(&data[..], &[1.0, 2.0, 3.0]);
+/// assert_eq!(data.as_ptr() as usize % ALIGNMENT, 0);
+/// # }
+/// ```
+///
+/// [`ALIGNMENT`]: constant.ALIGNMENT.html
+pub struct AlignedVec<T : Packable> {
+    ptr: NonNull<T>,
+    len: usize,
+    capacity: usize,
+}
+
+unsafe impl<T : Packable + Send> Send for AlignedVec<T> {}
+unsafe impl<T : Packable + Sync> Sync for AlignedVec<T> {}
+
+impl<T : Packable> AlignedVec<T> {
+    /// Return an empty vector, which doesn't allocate until it's pushed to.
+    #[inline]
+    pub fn new() -> Self {
+        AlignedVec { ptr: dangling(), len: 0, capacity: 0 }
+    }
+
+    /// Return an empty vector with room for at least `capacity` elements.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut ret = Self::new();
+        ret.reserve(capacity);
+        ret
+    }
+
+    /// Return a vector of `len` copies of `value`.
+    #[inline]
+    pub fn from_elem(value: T, len: usize) -> Self {
+        let mut ret = Self::with_capacity(len);
+        for i in 0..len {
+            unsafe { ptr::write(ret.ptr.as_ptr().add(i), value); }
+        }
+        ret.len = len;
+        ret
+    }
+
+    /// Return the number of elements this vector can hold without
+    /// reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Make room for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+        if needed <= self.capacity {
+            return;
+        }
+        let capacity = needed.max(self.capacity * 2).max(ALIGNMENT / size_of::<T>());
+        let new = layout::<T>(capacity);
+        let ptr = unsafe {
+            if self.capacity == 0 {
+                alloc(new)
+            } else {
+                realloc(self.ptr.as_ptr() as *mut u8, layout::<T>(self.capacity), new.size())
+            }
+        };
+        self.ptr = NonNull::new(ptr as *mut T).unwrap_or_else(|| handle_alloc_error(new));
+        self.capacity = capacity;
+    }
+
+    /// Append `value` to the end of this vector.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity {
+            self.reserve(1);
+        }
+        unsafe { ptr::write(self.ptr.as_ptr().add(self.len), value); }
+        self.len += 1;
+    }
+
+    /// Remove the last element of this vector and return it, or `None` if it
+    /// is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(unsafe { ptr::read(self.ptr.as_ptr().add(self.len)) })
+        }
+    }
+
+    /// Append every element of `data` to the end of this vector.
+    #[inline]
+    pub fn extend_from_slice(&mut self, data: &[T]) {
+        self.reserve(data.len());
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), self.ptr.as_ptr().add(self.len), data.len());
+        }
+        self.len += data.len();
+    }
+
+    /// Shorten this vector to `len` elements, if it is longer.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    /// Remove every element from this vector, keeping its allocation.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Convert this vector into an aligned boxed slice, dropping any excess
+    /// capacity.
+    pub fn into_boxed_slice(self) -> AlignedBox<[T]> {
+        let mut data = self;
+        if data.len < data.capacity {
+            if data.len == 0 {
+                return AlignedBox::from(&[][..]);
+            }
+            let new = layout::<T>(data.len);
+            let ptr = unsafe {
+                realloc(data.ptr.as_ptr() as *mut u8, layout::<T>(data.capacity), new.size())
+            };
+            data.ptr = NonNull::new(ptr as *mut T).unwrap_or_else(|| handle_alloc_error(new));
+            data.capacity = data.len;
+        }
+        let ret = AlignedBox { ptr: data.ptr, len: data.len };
+        data.capacity = 0;
+        ret
+    }
+
+    /// Return an iterator over this vector which packs its elements into its
+    /// native vectors, and loads them with aligned loads.
+    #[inline(always)]
+    pub fn simd_iter(&self, default: T::Vector) -> SIMDIter<&Self> {
+        SIMDIter { data: self, position: 0, default, prefetch: Prefetch::default() }
+    }
+
+    /// Return a mutable iterator over this vector which packs its elements
+    /// into its native vectors, and loads and stores them with aligned loads
+    /// and stores.
+    #[inline(always)]
+    pub fn simd_iter_mut(&mut self, default: T::Vector) -> SIMDIter<&mut Self> {
+        SIMDIter { data: self, position: 0, default, prefetch: Prefetch::default() }
+    }
+}
+
+impl<T : Packable> Drop for AlignedVec<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.capacity != 0 {
+            unsafe { dealloc(self.ptr.as_ptr() as *mut u8, layout::<T>(self.capacity)); }
+        }
+    }
+}
+
+impl<T : Packable> Default for AlignedVec<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T : Packable> Deref for AlignedVec<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T : Packable> DerefMut for AlignedVec<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T : Packable> Clone for AlignedVec<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        AlignedVec::from(&self[..])
+    }
+}
+
+impl<T : Packable> fmt::Debug for AlignedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self[..], f)
+    }
+}
+
+impl<T : Packable + PartialEq> PartialEq for AlignedVec<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<'a, T : Packable> From<&'a [T]> for AlignedVec<T> {
+    #[inline]
+    fn from(data: &'a [T]) -> Self {
+        let mut ret = Self::with_capacity(data.len());
+        ret.extend_from_slice(data);
+        ret
+    }
+}
+
+impl<T : Packable> FromIterator<T> for AlignedVec<T> {
+    #[inline]
+    fn from_iter<I : IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut ret = Self::with_capacity(iter.size_hint().0);
+        for x in iter {
+            ret.push(x);
+        }
+        ret
+    }
+}
+
+impl<T : Packable> Extend<T> for AlignedVec<T> {
+    #[inline]
+    fn extend<I : IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// A boxed slice of primitives whose first element is aligned to
+/// [`ALIGNMENT`] bytes.
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+/// use faster::aligned::AlignedBox;
+///
+/// # fn main() {
+/// let mut data = AlignedBox::from(&[1i16, 2, 3, 4, 5][..]);
+/// data.simd_iter_mut(i16s(0)).simd_for_each(|v| *v = *v * i16s(2));
+/// assert_eq!(&data[..], &[2, 4, 6, 8, 10]);
+/// # }
+/// ```
+///
+/// [`ALIGNMENT`]: constant.ALIGNMENT.html
+pub struct AlignedBox<T : ?Sized + AlignedSlice> {
+    ptr: NonNull<T::Element>,
+    len: usize,
+}
+
+/// A slice which may be held in an `AlignedBox`.
+pub trait AlignedSlice {
+    #[doc(hidden)]
+    type Element : Packable;
+}
+
+impl<T : Packable> AlignedSlice for [T] {
+    type Element = T;
+}
+
+unsafe impl<T : Packable + Send> Send for AlignedBox<[T]> {}
+unsafe impl<T : Packable + Sync> Sync for AlignedBox<[T]> {}
+
+impl<T : Packable> AlignedBox<[T]> {
+    /// Return a boxed slice of `len` copies of `value`.
+    #[inline]
+    pub fn from_elem(value: T, len: usize) -> Self {
+        AlignedVec::from_elem(value, len).into_boxed_slice()
+    }
+
+    /// Return an iterator over this slice which packs its elements into its
+    /// native vectors, and loads them with aligned loads.
+    #[inline(always)]
+    pub fn simd_iter(&self, default: T::Vector) -> SIMDIter<&Self> {
+        SIMDIter { data: self, position: 0, default, prefetch: Prefetch::default() }
+    }
+
+    /// Return a mutable iterator over this slice which packs its elements
+    /// into its native vectors, and loads and stores them with aligned loads
+    /// and stores.
+    #[inline(always)]
+    pub fn simd_iter_mut(&mut self, default: T::Vector) -> SIMDIter<&mut Self> {
+        SIMDIter { data: self, position: 0, default, prefetch: Prefetch::default() }
+    }
+}
+
+impl<T : ?Sized + AlignedSlice> Drop for AlignedBox<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe { dealloc(self.ptr.as_ptr() as *mut u8, layout::<T::Element>(self.len)); }
+        }
+    }
+}
+
+impl<T : Packable> Deref for AlignedBox<[T]> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T : Packable> DerefMut for AlignedBox<[T]> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T : Packable> Clone for AlignedBox<[T]> {
+    #[inline]
+    fn clone(&self) -> Self {
+        AlignedBox::from(&self[..])
+    }
+}
+
+impl<T : Packable> fmt::Debug for AlignedBox<[T]> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self[..], f)
+    }
+}
+
+impl<T : Packable + PartialEq> PartialEq for AlignedBox<[T]> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<'a, T : Packable> From<&'a [T]> for AlignedBox<[T]> {
+    #[inline]
+    fn from(data: &'a [T]) -> Self {
+        if data.is_empty() {
+            return AlignedBox { ptr: dangling(), len: 0 };
+        }
+        AlignedVec::from(data).into_boxed_slice()
+    }
+}
+
+impl<T : Packable> From<AlignedVec<T>> for AlignedBox<[T]> {
+    #[inline]
+    fn from(data: AlignedVec<T>) -> Self {
+        data.into_boxed_slice()
+    }
+}
+
+macro_rules! impl_aligned_array {
+    ($name:ty, ($($genera:tt),*)) => {
+        impl< $($genera),* > SIMDObject for $name where S : Packable<Vector = V>, V : Packed<Scalar = S> {
+            type Vector = V;
+            type Scalar = S;
+        }
+
+        impl< $($genera),* > SIMDArray for $name where S : Packable<Vector = V>, V : Packed<Scalar = S> {
+            #[inline(always)]
+            fn load(&self, offset: usize) -> Self::Vector {
+                if is_aligned::<V>(offset) {
+                    V::load_aligned(self, offset)
+                } else {
+                    V::load(self, offset)
+                }
+            }
+
+            #[inline(always)]
+            unsafe fn load_unchecked(&self, offset: usize) -> Self::Vector {
+                debug_assert!(self[offset..].len() >= Self::Vector::WIDTH);
+                if is_aligned::<V>(offset) {
+                    V::load_aligned_unchecked(self, offset)
+                } else {
+                    V::load_unchecked(self, offset)
+                }
+            }
+
+            #[inline(always)]
+            fn load_scalar(&self, offset: usize) -> Self::Scalar {
+                self[offset]
+            }
+
+            #[inline(always)]
+            unsafe fn load_scalar_unchecked(&self, offset: usize) -> Self::Scalar {
+                debug_assert!(offset < self.len());
+                *self.get_unchecked(offset)
+            }
+
+            #[inline(always)]
+            fn prefetch_scalar(&self, offset: usize, locality: Locality) {
+                prefetch(self.as_ptr().wrapping_add(offset), locality)
+            }
+        }
+
+        impl< $($genera),* > SIMDSized for $name where S : Packable<Vector = V>, V : Packed<Scalar = S> {
+            #[inline(always)]
+            fn scalar_len(&self) -> usize {
+                self.len()
+            }
+        }
+    }
+}
+
+macro_rules! impl_aligned_array_mut {
+    ($name:ty, ($($genera:tt),*)) => {
+        impl< $($genera),* > SIMDArrayMut for $name where S : Packable<Vector = V>, V : Packed<Scalar = S> {
+            #[inline(always)]
+            fn store(&mut self, value: Self::Vector, offset: usize) {
+                if is_aligned::<V>(offset) {
+                    value.store_aligned(self, offset)
+                } else {
+                    value.store(self, offset)
+                }
+            }
+
+            #[inline(always)]
+            unsafe fn store_unchecked(&mut self, value: Self::Vector, offset: usize) {
+                debug_assert!(self[offset..].len() >= Self::Vector::WIDTH);
+                if is_aligned::<V>(offset) {
+                    value.store_aligned_unchecked(self, offset)
+                } else {
+                    value.store_unchecked(self, offset)
+                }
+            }
+
+            #[inline(always)]
+            fn store_scalar(&mut self, value: Self::Scalar, offset: usize) {
+                self[offset] = value;
+            }
+
+            #[inline(always)]
+            unsafe fn store_scalar_unchecked(&mut self, value: Self::Scalar, offset: usize) {
+                debug_assert!(offset < self.len());
+                *self.get_unchecked_mut(offset) = value;
+            }
+        }
+    }
+}
+
+impl_aligned_array!(AlignedVec<S>, (S, V));
+impl_aligned_array!(&'a AlignedVec<S>, ('a, S, V));
+impl_aligned_array!(&'a mut AlignedVec<S>, ('a, S, V));
+impl_aligned_array!(AlignedBox<[S]>, (S, V));
+impl_aligned_array!(&'a AlignedBox<[S]>, ('a, S, V));
+impl_aligned_array!(&'a mut AlignedBox<[S]>, ('a, S, V));
+impl_aligned_array_mut!(AlignedVec<S>, (S, V));
+impl_aligned_array_mut!(&'a mut AlignedVec<S>, ('a, S, V));
+impl_aligned_array_mut!(AlignedBox<[S]>, (S, V));
+impl_aligned_array_mut!(&'a mut AlignedBox<[S]>, ('a, S, V));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn aligned_allocations() {
+        let mut data = AlignedVec::new();
+        for i in 0..3000 {
+            data.push(i as u8);
+            assert_eq!(data.as_ptr() as usize % ALIGNMENT, 0);
+        }
+        assert_eq!(data.len(), 3000);
+        assert!(data.iter().enumerate().all(|(i, &x)| x == i as u8));
+        assert_eq!(data.pop(), Some((2999 % 256) as u8));
+
+        let boxed = data.clone().into_boxed_slice();
+        assert_eq!(boxed.as_ptr() as usize % ALIGNMENT, 0);
+        assert_eq!(&boxed[..], &data[..]);
+
+        let empty = AlignedVec::<f64>::new().into_boxed_slice();
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.as_ptr() as usize % ALIGNMENT, 0);
+    }
+
+    #[test]
+    fn pop_to_empty() {
+        let mut data = AlignedVec::from_elem(0u64, 5);
+        for (i, x) in data.iter_mut().enumerate() {
+            *x = i as u64;
+        }
+        for i in (0..5).rev() {
+            assert_eq!(data.pop(), Some(i as u64));
+            assert_eq!(data.len(), i);
+        }
+        assert_eq!(data.pop(), None);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn aligned_load_store() {
+        let mut data = AlignedVec::from_elem(0u32, u32s::WIDTH * 3);
+        for (i, x) in data.iter_mut().enumerate() {
+            *x = i as u32;
+        }
+        let v = u32s::load_aligned(&data, u32s::WIDTH);
+        assert_eq!(v, u32s::load(&data, u32s::WIDTH));
+        (v + u32s(1)).store_aligned(&mut data, 0);
+        assert_eq!(data[0], u32s::WIDTH as u32 + 1);
+        assert_eq!(SIMDArray::load(&data, 1), u32s::load(&data, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(all(debug_assertions, any(target_arch = "x86", target_arch = "x86_64"), not(feature = "force-polyfill")))]
+    fn misaligned_load() {
+        let data = AlignedVec::from_elem(0u32, u32s::WIDTH * 2);
+        u32s::load_aligned(&data, 1);
+    }
+
+    #[test]
+    fn aligned_iterators() {
+        for &len in [0, 1, 15, 16, 17, 100, 1000].iter() {
+            let expected = (0..len).map(|x| x as f32).collect::<Vec<f32>>();
+            let data = expected.iter().cloned().collect::<AlignedVec<f32>>();
+            let doubled = data.simd_iter(f32s(0.0))
+                .simd_map(|v| v * f32s(2.0))
+                .scalar_collect();
+            assert_eq!(doubled, expected.iter().map(|x| x * 2.0).collect::<Vec<f32>>());
+
+            let mut boxed = AlignedBox::from(data);
+            boxed.simd_iter_mut(f32s(0.0)).simd_for_each(|v| *v = *v + f32s(1.0));
+            assert_eq!(&boxed[..], &expected.iter().map(|x| x + 1.0).collect::<Vec<f32>>()[..]);
+        }
+    }
+}
@@ -1,11 +1,20 @@
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+// The native backend is compiled unless the `force-polyfill` feature asks for
+// the portable one in its place. The `polyfill` feature compiles the portable
+// backend beside the native one, as `arch::polyfill`, so the two can be
+// compared lane-for-lane.
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "force-polyfill")))]
 pub mod x86;
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(any(not(any(target_arch = "x86", target_arch = "x86_64")),
+          feature = "force-polyfill", feature = "polyfill"))]
 pub mod unknown;
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "force-polyfill")))]
 pub use self::x86 as current;
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(any(not(any(target_arch = "x86", target_arch = "x86_64")), feature = "force-polyfill"))]
 pub use self::unknown as current;
+
+#[cfg(feature = "polyfill")]
+pub use self::unknown as polyfill;
@@ -6,7 +6,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::intrin::abs::Abs;
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::core::mem::transmute;
 
 impl Abs for f32x4 {
@@ -0,0 +1,21 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::vecs::*;
+use crate::intrin::addsub::*;
+
+impl_packed_addsub!(f32x4, "__undefined", __undefined, (0, 1), (2, 3));
+impl_packed_addsub!(f64x2, "__undefined", __undefined, (0, 1));
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+
+    test_packed_addsub!((f32x4, f64x2), (addsub_f32x4, addsub_f64x2));
+}
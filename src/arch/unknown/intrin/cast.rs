@@ -0,0 +1,27 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::vecs::*;
+use crate::intrin::cast::*;
+
+impl_packed_cast!(Asi32s, as_i32s, f32x4, i32x4, i32, "__undefined", __undefined);
+impl_packed_cast!(Asi32s, as_i32s, u32x4, i32x4, i32, reinterpret);
+
+impl_packed_cast!(Asf32s, as_f32s, i32x4, f32x4, f32, "__undefined", __undefined);
+impl_packed_cast!(Asf32s, as_f32s, u32x4, f32x4, f32, "__undefined", __undefined);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+
+    test_packed_cast!((f32x4, u32x4), (f32, u32), (as_i32s, as_i32s), (i32x4, i32x4), (i32, i32),
+                      (as_i32s_f32x4, as_i32s_u32x4));
+    test_packed_cast!((i32x4, u32x4), (i32, u32), (as_f32s, as_f32s), (f32x4, f32x4), (f32, f32),
+                      (as_f32s_i32x4, as_f32s_u32x4));
+}
@@ -5,7 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::intrin::destride::*;
 
 impl Destride for u8x16 {
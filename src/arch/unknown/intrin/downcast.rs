@@ -5,8 +5,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::intrin::downcast::*;
+use crate::half::*;
 
 impl Downcast<i16x8> for i32x4 {
     #[inline(always)]
@@ -36,9 +37,9 @@ impl Downcast<u32x4> for u64x2 {
     #[inline(always)]
     fn saturating_downcast(self, other: Self) -> u32x4 {
         u32x4::new(self.extract(0).min(0xFFFFFFFF) as u32,
-                   self.extract(1).min(0x7FFFFFFF) as u32,
-                   other.extract(0).min(0x7FFFFFFF) as u32,
-                   other.extract(1).min(0x7FFFFFFF) as u32)
+                   self.extract(1).min(0xFFFFFFFF) as u32,
+                   other.extract(0).min(0xFFFFFFFF) as u32,
+                   other.extract(1).min(0xFFFFFFFF) as u32)
     }
 }
 
@@ -248,13 +249,13 @@ impl Downcast<u32x8> for u64x4 {
     #[inline(always)]
     fn saturating_downcast(self, other: Self) -> u32x8 {
         u32x8::new(self.extract(0).min(0xFFFFFFFF) as u32,
-                   self.extract(1).min(0x7FFFFFFF) as u32,
+                   self.extract(1).min(0xFFFFFFFF) as u32,
                    self.extract(2).min(0xFFFFFFFF) as u32,
-                   self.extract(3).min(0x7FFFFFFF) as u32,
-                   other.extract(0).min(0x7FFFFFFF) as u32,
-                   other.extract(1).min(0x7FFFFFFF) as u32,
-                   other.extract(2).min(0x7FFFFFFF) as u32,
-                   other.extract(3).min(0x7FFFFFFF) as u32)
+                   self.extract(3).min(0xFFFFFFFF) as u32,
+                   other.extract(0).min(0xFFFFFFFF) as u32,
+                   other.extract(1).min(0xFFFFFFFF) as u32,
+                   other.extract(2).min(0xFFFFFFFF) as u32,
+                   other.extract(3).min(0xFFFFFFFF) as u32)
     }
 }
 
@@ -271,3 +272,31 @@ impl Downcast<f32x8> for f64x4 {
                    other.extract(3) as f32)
     }
 }
+
+impl Downcast<PackedF16<u16x8>> for f32x4 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> PackedF16<u16x8> {
+        PackedF16(downcast_half_polyfill(self, other, f32_to_f16))
+    }
+}
+
+impl Downcast<PackedBF16<u16x8>> for f32x4 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> PackedBF16<u16x8> {
+        PackedBF16(downcast_half_polyfill(self, other, f32_to_bf16))
+    }
+}
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+
+    #[test]
+    fn downcast_saturates_unsigned() {
+        let out: u32x4 = u64x2::splat(!0).saturating_downcast(u64x2::splat(0x1_0000_0000));
+        assert!((0..u32x4::WIDTH).all(|i| out.extract(i) == 0xFFFF_FFFF));
+        let out: u32x8 = u64x4::splat(!0).saturating_downcast(u64x4::splat(0x1_0000_0000));
+        assert!((0..u32x8::WIDTH).all(|i| out.extract(i) == 0xFFFF_FFFF));
+    }
+}
@@ -5,7 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::vecs::*;
 use crate::intrin::endian::*;
 
@@ -38,7 +38,7 @@ mod tests {
     #![allow(unused_imports)]
 
     use crate::prelude::*;
-    use crate::arch::current::vecs::*;
+    use crate::arch::unknown::vecs::*;
 
     test_packed_swap_bytes!((u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, u64x2, i64x2),
                              (swap_bytes_u8x16, swap_bytes_i8x16, swap_bytes_u16x8, swap_bytes_i16x8, swap_bytes_u32x4, swap_bytes_i32x4, swap_bytes_u64x2, swap_bytes_i64x2));
@@ -6,7 +6,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::intrin::eq::*;
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::vecs::*;
 
 rust_fallback_eq! {
@@ -72,7 +72,7 @@ rust_fallback_eq! {
 mod tests {
     #![allow(unused_imports)]
     use crate::prelude::*;
-    use crate::arch::current::vecs::*;
+    use crate::arch::unknown::vecs::*;
 
     // test_packed_eq!(u8x64, u8, u8x64, u8, test_eq_u8x64);
     // test_packed_eq!(u8x32, u8, u8x32, u8, test_eq_u8x32);
@@ -7,16 +7,16 @@
 
 use crate::intrin::hadd::*;
 use crate::core::ops::Add;
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::vecs::*;
 
-impl HAdd for u64x2 { hop!(hadd, Add::add, 0, 1); }
-impl HAdd for u32x4 { hop!(hadd, Add::add, 0, 1, 2, 3); }
-impl HAdd for u16x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HAdd for u8x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HAdd for i64x2 { hop!(hadd, Add::add, 0, 1); }
-impl HAdd for i32x4 { hop!(hadd, Add::add, 0, 1, 2, 3); }
-impl HAdd for i16x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HAdd for i8x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for u64x2 { hop!(hadd, u64::wrapping_add, 0, 1); }
+impl HAdd for u32x4 { hop!(hadd, u32::wrapping_add, 0, 1, 2, 3); }
+impl HAdd for u16x8 { hop!(hadd, u16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for u8x16 { hop!(hadd, u8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for i64x2 { hop!(hadd, i64::wrapping_add, 0, 1); }
+impl HAdd for i32x4 { hop!(hadd, i32::wrapping_add, 0, 1, 2, 3); }
+impl HAdd for i16x8 { hop!(hadd, i16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for i8x16 { hop!(hadd, i8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
 impl HAdd for f64x2 { hop!(hadd, Add::add, 0, 1); }
 impl HAdd for f32x4 { hop!(hadd, Add::add, 0, 1, 2, 3); }
@@ -6,6 +6,8 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 mod abs;
+mod addsub;
+mod cast;
 mod cmp;
 mod destride;
 mod downcast;
@@ -13,8 +15,12 @@ mod endian;
 mod eq;
 mod hadd;
 mod hsub;
+mod lookup;
 mod merge;
+mod movemask;
+mod mul_add;
 mod recip;
+mod restride;
 mod round;
 mod rsqrt;
 mod saturating_add;
@@ -22,12 +28,18 @@ mod saturating_hadd;
 mod saturating_sub;
 mod saturating_hsub;
 mod sum;
+mod sort;
 mod sqrt;
+mod stream;
+mod swizzle;
 mod transmute;
+mod transpose;
 mod upcast;
 
 pub mod prelude {
     pub use super::abs::*;
+    pub use super::addsub::*;
+    pub use super::cast::*;
     pub use super::cmp::*;
     pub use super::destride::*;
     pub use super::downcast::*;
@@ -35,8 +47,12 @@ pub mod prelude {
     pub use super::eq::*;
     pub use super::hadd::*;
     pub use super::hsub::*;
+    pub use super::lookup::*;
     pub use super::merge::*;
+    pub use super::movemask::*;
+    pub use super::mul_add::*;
     pub use super::recip::*;
+    pub use super::restride::*;
     pub use super::round::*;
     pub use super::rsqrt::*;
     pub use super::saturating_add::*;
@@ -45,6 +61,10 @@ pub mod prelude {
     pub use super::saturating_sub::*;
     pub use super::sum::*;
     pub use super::sqrt::*;
+    pub use super::sort::*;
+    pub use super::stream::*;
+    pub use super::swizzle::*;
     pub use super::transmute::*;
+    pub use super::transpose::*;
     pub use super::upcast::*;
 }
@@ -0,0 +1,21 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::vecs::*;
+use crate::intrin::movemask::*;
+
+impl_packed_movemask!(u8x16, u32, "__undefined", __undefined);
+impl_packed_movemask!(i8x16, u32, "__undefined", __undefined);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+
+    test_packed_movemask!((u8x16, i8x16), (u8, i8), (movemask_u8x16, movemask_i8x16));
+}
@@ -0,0 +1,27 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::intrin::restride::*;
+
+macro_rules! impl_restride {
+    ($($vec:ty),*) => {
+        $(
+            impl Restride for $vec {
+                #[inline(always)]
+                fn interleave_two(self, other: Self) -> (Self, Self) {
+                    fallback!();
+                    let mut out = [self; 2];
+                    interleave_polyfill(&[self, other], &mut out);
+                    (out[0], out[1])
+                }
+            }
+        )*
+    }
+}
+
+impl_restride!(u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2);
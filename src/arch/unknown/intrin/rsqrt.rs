@@ -6,7 +6,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::intrin::rsqrt::*;
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::vecs::*;
 
 // TODO: Guards and non-simd
@@ -29,6 +29,7 @@ rust_fallback_impl! {
     }
 }
 
+#[cfg(any(not(any(target_arch = "x86", target_arch = "x86_64")), feature = "force-polyfill"))]
 impl Rsqrt for f32 {
     #[inline(always)]
     fn rsqrt(&self) -> Self {
@@ -36,6 +37,7 @@ impl Rsqrt for f32 {
     }
 }
 
+#[cfg(any(not(any(target_arch = "x86", target_arch = "x86_64")), feature = "force-polyfill"))]
 impl Rsqrt for f64 {
     #[inline(always)]
     fn rsqrt(&self) -> Self {
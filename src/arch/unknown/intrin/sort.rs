@@ -0,0 +1,94 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::vecs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::sort::*;
+
+/// Compare each element of `v` with the element whose index differs from its
+/// own by `j`, and keep the smaller of the two in the first half of each
+/// block of `k` elements. Keep the larger in the second half.
+#[inline(always)]
+fn bitonic_stage_polyfill<V : Packed + Cmp>(v: V, j: usize, k: usize) -> V {
+    let mut partner = v;
+    for i in 0..V::WIDTH {
+        partner = partner.replace(i, v.extract(i ^ j));
+    }
+    let (lo, hi) = (Cmp::min(&v, partner), Cmp::max(&v, partner));
+    let mut ret = v;
+    for i in 0..V::WIDTH {
+        let ascending = i & k == 0;
+        ret = ret.replace(i, if (i & j == 0) == ascending { lo.extract(i) } else { hi.extract(i) });
+    }
+    ret
+}
+
+#[inline(always)]
+fn sort_lanes_polyfill<V : Packed + Cmp>(v: V) -> V {
+    let mut v = v;
+    let mut k = 2;
+    while k <= V::WIDTH {
+        let mut j = k / 2;
+        while j > 0 {
+            v = bitonic_stage_polyfill(v, j, k);
+            j /= 2;
+        }
+        k *= 2;
+    }
+    v
+}
+
+#[inline(always)]
+fn merge_sorted_polyfill<V : Packed + Cmp>(a: V, b: V) -> (V, V) {
+    // Reversing `b` makes both halves of the merged sequence bitonic, so they
+    // only need cleaning after the first compare-and-swap.
+    let mut rev = b;
+    for i in 0..V::WIDTH {
+        rev = rev.replace(i, b.extract(V::WIDTH - 1 - i));
+    }
+    let (mut lo, mut hi) = (Cmp::min(&a, rev), Cmp::max(&a, rev));
+    let mut j = V::WIDTH / 2;
+    while j > 0 {
+        lo = bitonic_stage_polyfill(lo, j, V::WIDTH * 2);
+        hi = bitonic_stage_polyfill(hi, j, V::WIDTH * 2);
+        j /= 2;
+    }
+    (lo, hi)
+}
+
+macro_rules! impl_sort_lanes_polyfill {
+    ($($vec:tt),*) => {
+        $(
+            impl SortLanes for $vec {
+                #[inline(always)]
+                fn sort_lanes(&self) -> Self {
+                    fallback!();
+                    sort_lanes_polyfill(*self)
+                }
+
+                #[inline(always)]
+                fn merge_sorted(&self, other: Self) -> (Self, Self) {
+                    fallback!();
+                    merge_sorted_polyfill(*self, other)
+                }
+            }
+        )*
+    }
+}
+
+impl_sort_lanes_polyfill!(u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+
+    test_packed_sort_lanes!((u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2),
+                            (u8, i8, u16, i16, u32, i32, f32, u64, i64, f64),
+                            (sort_lanes_u8x16, sort_lanes_i8x16, sort_lanes_u16x8, sort_lanes_i16x8, sort_lanes_u32x4, sort_lanes_i32x4, sort_lanes_f32x4, sort_lanes_u64x2, sort_lanes_i64x2, sort_lanes_f64x2));
+}
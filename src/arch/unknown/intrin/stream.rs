@@ -0,0 +1,22 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::vecs::*;
+use crate::intrin::stream::*;
+
+impl_packed_stream!(plain, u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+
+    test_packed_stream!((u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2),
+                        (u8, i8, u16, i16, u32, i32, f32, u64, i64, f64),
+                        (stream_u8x16, stream_i8x16, stream_u16x8, stream_i16x8, stream_u32x4, stream_i32x4, stream_f32x4, stream_u64x2, stream_i64x2, stream_f64x2));
+}
@@ -5,7 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::vecs::*;
 use crate::intrin::sum::{Sum,UpcastSum};
 
@@ -16,7 +16,7 @@ mod tests {
     #![allow(unused_imports)]
 
     use crate::prelude::*;
-    use crate::arch::current::vecs::*;
+    use crate::arch::unknown::vecs::*;
 
     test_packed_sum_int!(u8x16, u8, test_packed_sum_u8x16);
     test_packed_sum_int!(i8x16, i8, test_packed_sum_i8x16);
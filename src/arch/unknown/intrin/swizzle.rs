@@ -0,0 +1,65 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::vecs::*;
+use crate::intrin::transmute::*;
+use crate::core::mem::transmute;
+
+impl_packed_swizzle!(u8x16, u8x16, "__undefined", __undefined,
+                     (1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14),
+                     (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15));
+impl_packed_swizzle!(i8x16, u8x16, "__undefined", __undefined,
+                     (1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14),
+                     (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15));
+impl_packed_swizzle!(u16x8, u8x16, "__undefined", __undefined,
+                     (2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13),
+                     (0, 1, 2, 3, 4, 5, 6, 7));
+impl_packed_swizzle!(i16x8, u8x16, "__undefined", __undefined,
+                     (2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13),
+                     (0, 1, 2, 3, 4, 5, 6, 7));
+impl_packed_swizzle!(u32x4, u8x16, "__undefined", __undefined,
+                     (4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11),
+                     (0, 1, 2, 3));
+impl_packed_swizzle!(i32x4, u8x16, "__undefined", __undefined,
+                     (4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11),
+                     (0, 1, 2, 3));
+impl_packed_swizzle!(f32x4, u8x16, "__undefined", __undefined,
+                     (4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11),
+                     (0, 1, 2, 3));
+impl_packed_swizzle!(u64x2, u8x16, "__undefined", __undefined,
+                     (8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7),
+                     (0, 1));
+impl_packed_swizzle!(i64x2, u8x16, "__undefined", __undefined,
+                     (8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7),
+                     (0, 1));
+impl_packed_swizzle!(f64x2, u8x16, "__undefined", __undefined,
+                     (8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7),
+                     (0, 1));
+
+mod tests {
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+
+    macro_rules! test_packed_swizzle {
+        (($($vec:tt),*), ($($fn:tt),*)) => {
+            $(
+                #[test]
+                fn $fn() {
+                    let a = $vec::interleave(0u8 as <$vec as Packed>::Scalar,
+                                             1u8 as <$vec as Packed>::Scalar);
+                    let b = $vec::interleave(1u8 as <$vec as Packed>::Scalar,
+                                             0u8 as <$vec as Packed>::Scalar);
+                    assert_eq!(a.flip(), b);
+                }
+            )*
+        }
+    }
+
+    test_packed_swizzle!((u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2),
+                         (flip_u8x16, flip_i8x16, flip_u16x8, flip_i16x8, flip_u32x4, flip_i32x4, flip_f32x4, flip_u64x2, flip_i64x2, flip_f64x2));
+}
@@ -6,7 +6,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::intrin::transmute::*;
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::core::mem::transmute;
 
 impl_packed_transmute!(u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4,
@@ -0,0 +1,28 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::unknown::vecs::*;
+use crate::vecs::*;
+use crate::intrin::transpose::*;
+
+impl_transpose_polyfill!(Transpose4x4, transpose4x4, 4, f32x4, u32x4, i32x4);
+impl_transpose_polyfill!(Transpose8x8, transpose8x8, 8, u16x8, i16x8);
+impl_transpose_polyfill!(Transpose16x16, transpose16x16, 16, u8x16, i8x16);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::unknown::vecs::*;
+    use crate::vecs::*;
+
+    test_packed_transpose!(transpose4x4, 4, (f32x4, u32x4, i32x4),
+                           (transpose4x4_f32x4, transpose4x4_u32x4, transpose4x4_i32x4));
+    test_packed_transpose!(transpose8x8, 8, (u16x8, i16x8),
+                           (transpose8x8_u16x8, transpose8x8_i16x8));
+    test_packed_transpose!(transpose16x16, 16, (u8x16, i8x16),
+                           (transpose16x16_u8x16, transpose16x16_i8x16));
+}
@@ -5,8 +5,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::arch::current::vecs::*;
+use crate::arch::unknown::vecs::*;
 use crate::intrin::upcast::*;
+use crate::half::*;
 
 impl Upcast<u16x8> for u8x16 {
     #[inline(always)]
@@ -384,3 +385,17 @@ impl Upcast<u64x8> for u32x16 {
                     self.extract(15) as u64))
     }
 }
+
+impl Upcast<f32x4> for PackedF16<u16x8> {
+    #[inline(always)]
+    fn upcast(self) -> (f32x4, f32x4) {
+        upcast_half_polyfill(self.0, f16_to_f32)
+    }
+}
+
+impl Upcast<f32x4> for PackedBF16<u16x8> {
+    #[inline(always)]
+    fn upcast(self) -> (f32x4, f32x4) {
+        upcast_half_polyfill(self.0, bf16_to_f32)
+    }
+}
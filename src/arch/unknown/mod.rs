@@ -1,3 +1,5 @@
 pub mod intrin;
+pub mod prefetch;
 pub mod vecs;
 pub mod vec_patterns;
+pub mod widths;
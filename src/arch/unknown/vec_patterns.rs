@@ -11,7 +11,7 @@
 
 #![allow(unused_imports)]
 
-use crate::arch::current::vecs::*;
+use super::vecs::*;
 use crate::core::mem::transmute;
 use crate::vecs::*;
 
@@ -227,17 +227,26 @@ macro_rules! impl_cast {
     }
 }
 
+// These vectors only implement `Packable` when this is the current backend, so
+// it can be compiled beside a native backend with the `polyfill` feature.
+macro_rules! impl_packed_polyfill {
+    ($($args:tt)*) => {
+        impl_packed!($($args)*, any(not(any(target_arch = "x86", target_arch = "x86_64")),
+                                    feature = "force-polyfill"));
+    }
+}
+
 // "undefined" is just a string that should not match any target-feature.
-impl_packed!(u8, u8s, u8x16, 1, 16, [], ["undefined"]);
-impl_packed!(i8, i8s, i8x16, 1, 16, [], ["undefined"]);
-impl_packed!(u16, u16s, u16x8, 2, 8, [], ["undefined"]);
-impl_packed!(i16, i16s, i16x8, 2, 8, [], ["undefined"]);
-impl_packed!(u32, u32s, u32x4, 4, 4, [], ["undefined"]);
-impl_packed!(i32, i32s, i32x4, 4, 4, [], ["undefined"]);
-impl_packed!(f32, f32s, f32x4, 4, 4, [], ["undefined"]);
-impl_packed!(u64, u64s, u64x2, 8, 2, [], ["undefined"]);
-impl_packed!(i64, i64s, i64x2, 8, 2, [], ["undefined"]);
-impl_packed!(f64, f64s, f64x2, 8, 2, [], ["undefined"]);
+impl_packed_polyfill!(u8, u8s, u8x16, 1, 16, [], ["undefined"]);
+impl_packed_polyfill!(i8, i8s, i8x16, 1, 16, [], ["undefined"]);
+impl_packed_polyfill!(u16, u16s, u16x8, 2, 8, [], ["undefined"]);
+impl_packed_polyfill!(i16, i16s, i16x8, 2, 8, [], ["undefined"]);
+impl_packed_polyfill!(u32, u32s, u32x4, 4, 4, [], ["undefined"]);
+impl_packed_polyfill!(i32, i32s, i32x4, 4, 4, [], ["undefined"]);
+impl_packed_polyfill!(f32, f32s, f32x4, 4, 4, [], ["undefined"]);
+impl_packed_polyfill!(u64, u64s, u64x2, 8, 2, [], ["undefined"]);
+impl_packed_polyfill!(i64, i64s, i64x2, 8, 2, [], ["undefined"]);
+impl_packed_polyfill!(f64, f64s, f64x2, 8, 2, [], ["undefined"]);
 
 impl_packed_type!(f64, f64s, f64x2, 2, [x0, x1]);
 impl_packed_type!(f64, f64s, f64x4, 4, [x0, x1, x2, x3]);
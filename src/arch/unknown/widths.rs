@@ -0,0 +1,14 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Wider vectors aren't implemented as `Packed` on unknown architectures, so
+// only the 128-bit family is available here.
+
+impl_width!(w128, "Vectors which are always 128 bits wide, regardless of the native vector width of the target.",
+            u8, u8s, u8x16, i8, i8s, i8x16, u16, u16s, u16x8, i16, i16s, i16x8,
+            u32, u32s, u32x4, i32, i32s, i32x4, f32, f32s, f32x4,
+            u64, u64s, u64x2, i64, i64s, i64x2, f64, f64s, f64x2);
@@ -324,3 +324,183 @@ impl Abs for i64x8 {
     }
 }
 
+impl Abs for i8x64 {
+    type Out = u8x64;
+
+    #[inline(always)]
+    fn abs(&self) -> Self::Out {
+        fallback!();
+        Self::Out::new(unsafe { transmute::<i8, u8>(self.extract(0).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(1).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(2).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(3).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(4).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(5).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(6).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(7).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(8).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(9).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(10).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(11).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(12).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(13).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(14).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(15).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(16).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(17).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(18).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(19).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(20).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(21).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(22).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(23).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(24).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(25).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(26).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(27).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(28).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(29).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(30).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(31).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(32).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(33).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(34).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(35).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(36).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(37).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(38).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(39).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(40).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(41).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(42).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(43).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(44).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(45).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(46).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(47).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(48).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(49).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(50).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(51).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(52).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(53).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(54).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(55).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(56).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(57).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(58).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(59).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(60).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(61).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(62).overflowing_abs().0) },
+                       unsafe { transmute::<i8, u8>(self.extract(63).overflowing_abs().0) })
+    }
+}
+
+impl Abs for i16x32 {
+    type Out = u16x32;
+
+    #[inline(always)]
+    fn abs(&self) -> Self::Out {
+        fallback!();
+        Self::Out::new(unsafe { transmute::<i16, u16>(self.extract(0).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(1).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(2).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(3).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(4).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(5).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(6).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(7).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(8).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(9).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(10).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(11).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(12).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(13).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(14).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(15).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(16).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(17).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(18).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(19).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(20).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(21).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(22).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(23).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(24).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(25).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(26).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(27).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(28).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(29).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(30).overflowing_abs().0) },
+                       unsafe { transmute::<i16, u16>(self.extract(31).overflowing_abs().0) })
+    }
+}
+
+impl Abs for i32x16 {
+    type Out = u32x16;
+
+    #[inline(always)]
+    fn abs(&self) -> Self::Out {
+        fallback!();
+        Self::Out::new(unsafe { transmute::<i32, u32>(self.extract(0).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(1).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(2).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(3).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(4).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(5).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(6).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(7).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(8).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(9).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(10).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(11).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(12).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(13).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(14).overflowing_abs().0) },
+                       unsafe { transmute::<i32, u32>(self.extract(15).overflowing_abs().0) })
+    }
+}
+
+impl Abs for f32x16 {
+    type Out = f32x16;
+
+    #[inline(always)]
+    fn abs(&self) -> Self::Out {
+        fallback!();
+        Self::Out::new(self.extract(0).abs(),
+                       self.extract(1).abs(),
+                       self.extract(2).abs(),
+                       self.extract(3).abs(),
+                       self.extract(4).abs(),
+                       self.extract(5).abs(),
+                       self.extract(6).abs(),
+                       self.extract(7).abs(),
+                       self.extract(8).abs(),
+                       self.extract(9).abs(),
+                       self.extract(10).abs(),
+                       self.extract(11).abs(),
+                       self.extract(12).abs(),
+                       self.extract(13).abs(),
+                       self.extract(14).abs(),
+                       self.extract(15).abs())
+    }
+}
+
+impl Abs for f64x8 {
+    type Out = f64x8;
+
+    #[inline(always)]
+    fn abs(&self) -> Self::Out {
+        fallback!();
+        Self::Out::new(self.extract(0).abs(),
+                       self.extract(1).abs(),
+                       self.extract(2).abs(),
+                       self.extract(3).abs(),
+                       self.extract(4).abs(),
+                       self.extract(5).abs(),
+                       self.extract(6).abs(),
+                       self.extract(7).abs())
+    }
+}
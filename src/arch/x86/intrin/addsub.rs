@@ -6,31 +6,23 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::intrin::addsub::*;
 
-// impl AddSub for f32x4 {
-//     #[inline(always)]
-//     fn addsub(&self, other: Self) -> Self {
-//         unsafe { _mm_addsub_ps(*self, other) }
-//     }
-// }
+impl_packed_addsub!(f32x4, "sse3", _mm_addsub_ps, (0, 1), (2, 3));
+impl_packed_addsub!(f64x2, "sse3", _mm_addsub_pd, (0, 1));
+impl_packed_addsub!(f32x8, "avx", _mm256_addsub_ps, (0, 1), (2, 3), (4, 5), (6, 7));
+impl_packed_addsub!(f64x4, "avx", _mm256_addsub_pd, (0, 1), (2, 3));
+impl_packed_addsub!(f32x16, "avx512-butnotyet", _mm512_addsub_ps, (0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11), (12, 13), (14, 15));
+impl_packed_addsub!(f64x8, "avx512-butnotyet", _mm512_addsub_pd, (0, 1), (2, 3), (4, 5), (6, 7));
 
-// impl AddSub for f64x2 {
-//     #[inline(always)]
-//     fn addsub(&self, other: Self) -> Self {
-//         unsafe { _mm_addsub_pd(*self, other) }
-//     }
-// }
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
 
-// impl AddSub for f32x8 {
-//     #[inline(always)]
-//     fn addsub(&self, other: Self) -> Self {
-//         unsafe { _mm256_addsub_ps(*self, other) }
-//     }
-// }
-
-// impl AddSub for f64x4 {
-//     #[inline(always)]
-//     fn addsub(&self, other: Self) -> Self {
-//         unsafe { _mm256_addsub_pd(*self, other) }
-//     }
-// }
+    test_packed_addsub!((f32x4, f64x2, f32x8, f64x4, f32x16, f64x8),
+                        (addsub_f32x4, addsub_f64x2, addsub_f32x8, addsub_f64x4, addsub_f32x16, addsub_f64x8));
+}
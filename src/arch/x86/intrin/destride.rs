@@ -154,3 +154,21 @@ impl_destride!(u32x8, 0, 2, 4, 6);
 impl_destride!(u32x4, 0, 2);
 impl_destride!(i32x8, 0, 2, 4, 6);
 impl_destride!(i32x4, 0, 2);
+
+impl_destride!(f32x8, 0, 2, 4, 6);
+impl_destride!(f32x4, 0, 2);
+
+impl_destride!(u64x4, 0, 2);
+impl_destride!(i64x4, 0, 2);
+impl_destride!(f64x4, 0, 2);
+
+impl_destride!(u8x64, 0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30, 32, 34, 36, 38, 40, 42, 44, 46, 48, 50, 52, 54, 56, 58, 60, 62);
+impl_destride!(i8x64, 0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30, 32, 34, 36, 38, 40, 42, 44, 46, 48, 50, 52, 54, 56, 58, 60, 62);
+impl_destride!(u16x32, 0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30);
+impl_destride!(i16x32, 0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30);
+impl_destride!(u32x16, 0, 2, 4, 6, 8, 10, 12, 14);
+impl_destride!(i32x16, 0, 2, 4, 6, 8, 10, 12, 14);
+impl_destride!(f32x16, 0, 2, 4, 6, 8, 10, 12, 14);
+impl_destride!(u64x8, 0, 2, 4, 6);
+impl_destride!(i64x8, 0, 2, 4, 6);
+impl_destride!(f64x8, 0, 2, 4, 6);
@@ -9,7 +9,9 @@ use crate::arch::current::vecs::*;
 use crate::vecs::*;
 use crate::vektor::x86_64::*;
 use crate::vektor::x86::*;
+use crate::intrin::cmp::*;
 use crate::intrin::downcast::*;
+use crate::half::*;
 use crate::intrin::transmute::*;
 use crate::core::mem::transmute;
 
@@ -52,9 +54,9 @@ impl Downcast<u32x4> for u64x2 {
     fn saturating_downcast(self, other: Self) -> u32x4 {
         fallback!();
         u32x4::new(self.extract(0).min(0xFFFFFFFF) as u32,
-                   self.extract(1).min(0x7FFFFFFF) as u32,
-                   other.extract(0).min(0x7FFFFFFF) as u32,
-                   other.extract(1).min(0x7FFFFFFF) as u32)
+                   self.extract(1).min(0xFFFFFFFF) as u32,
+                   other.extract(0).min(0xFFFFFFFF) as u32,
+                   other.extract(1).min(0xFFFFFFFF) as u32)
     }
 }
 
@@ -105,7 +107,9 @@ impl Downcast<u16x8> for u32x4 {
     #[cfg(target_feature = "sse4.1")]
     fn saturating_downcast(self, other: Self) -> u16x8 {
         optimized!();
-        unsafe { transmute(_mm_packus_epi32(transmute(self), transmute(other))) }
+        // packusdw reads its operands as signed, so clamp them first
+        let (lo, hi) = (Cmp::min(&self, u32x4::splat(0xFFFF)), Cmp::min(&other, u32x4::splat(0xFFFF)));
+        unsafe { transmute(_mm_packus_epi32(transmute(lo), transmute(hi))) }
     }
 
     #[inline(always)]
@@ -128,7 +132,9 @@ impl Downcast<u8x16> for u16x8 {
     #[cfg(target_feature = "sse2")]
     fn saturating_downcast(self, other: Self) -> u8x16 {
         optimized!();
-        unsafe { _mm_packus_epi16(self.be_i16s(), other.be_i16s()).be_u8s() }
+        // packuswb reads its operands as signed, so clamp them first
+        let (lo, hi) = (Cmp::min(&self, u16x8::splat(0xFF)), Cmp::min(&other, u16x8::splat(0xFF)));
+        unsafe { _mm_packus_epi16(lo.be_i16s(), hi.be_i16s()).be_u8s() }
     }
 
     #[inline(always)]
@@ -159,7 +165,8 @@ impl Downcast<i16x16> for i32x8 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> i16x16 {
         optimized!();
-        unsafe { _mm256_packs_epi32(self, other) }
+        // The packs work within 128-bit lanes, so reorder their quarters
+        unsafe { _mm256_permute4x64_epi64(_mm256_packs_epi32(self, other).be_i64s(), 0xD8).be_i16s() }
     }
 
     #[inline(always)]
@@ -190,7 +197,8 @@ impl Downcast<i8x32> for i16x16 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> i8x32 {
         optimized!();
-        unsafe { _mm256_packs_epi16(self.be_i16s(), other) }
+        // The packs work within 128-bit lanes, so reorder their quarters
+        unsafe { _mm256_permute4x64_epi64(_mm256_packs_epi16(self.be_i16s(), other).be_i64s(), 0xD8).be_i8s() }
     }
 
     #[inline(always)]
@@ -237,7 +245,10 @@ impl Downcast<u16x16> for u32x8 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> u16x16 {
         optimized!();
-        unsafe { transmute(_mm256_packus_epi32(transmute(self), transmute(other))) }
+        // The packs read their operands as signed and work within 128-bit
+        // lanes, so clamp the operands and reorder the quarters
+        let (lo, hi) = (Cmp::min(&self, u32x8::splat(0xFFFF)), Cmp::min(&other, u32x8::splat(0xFFFF)));
+        unsafe { transmute(_mm256_permute4x64_epi64(transmute(_mm256_packus_epi32(transmute(lo), transmute(hi))), 0xD8)) }
     }
 
     #[inline(always)]
@@ -268,7 +279,10 @@ impl Downcast<u8x32> for u16x16 {
     #[cfg(target_feature = "avx2")]
     fn saturating_downcast(self, other: Self) -> u8x32 {
         optimized!();
-        unsafe { _mm256_packus_epi16(self.be_i16s(), other.be_i16s()).be_u8s() }
+        // The packs read their operands as signed and work within 128-bit
+        // lanes, so clamp the operands and reorder the quarters
+        let (lo, hi) = (Cmp::min(&self, u16x16::splat(0xFF)), Cmp::min(&other, u16x16::splat(0xFF)));
+        unsafe { _mm256_permute4x64_epi64(_mm256_packus_epi16(lo.be_i16s(), hi.be_i16s()).be_i64s(), 0xD8).be_u8s() }
     }
 
     #[inline(always)]
@@ -330,13 +344,13 @@ impl Downcast<u32x8> for u64x4 {
     fn saturating_downcast(self, other: Self) -> u32x8 {
         fallback!();
         u32x8::new(self.extract(0).min(0xFFFFFFFF) as u32,
-                   self.extract(1).min(0x7FFFFFFF) as u32,
+                   self.extract(1).min(0xFFFFFFFF) as u32,
                    self.extract(2).min(0xFFFFFFFF) as u32,
-                   self.extract(3).min(0x7FFFFFFF) as u32,
-                   other.extract(0).min(0x7FFFFFFF) as u32,
-                   other.extract(1).min(0x7FFFFFFF) as u32,
-                   other.extract(2).min(0x7FFFFFFF) as u32,
-                   other.extract(3).min(0x7FFFFFFF) as u32)
+                   self.extract(3).min(0xFFFFFFFF) as u32,
+                   other.extract(0).min(0xFFFFFFFF) as u32,
+                   other.extract(1).min(0xFFFFFFFF) as u32,
+                   other.extract(2).min(0xFFFFFFFF) as u32,
+                   other.extract(3).min(0xFFFFFFFF) as u32)
     }
 }
 
@@ -354,3 +368,404 @@ impl Downcast<f32x8> for f64x4 {
                    other.extract(3) as f32)
     }
 }
+
+impl Downcast<i16x32> for i32x16 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> i16x32 {
+        fallback!();
+        i16x32::new(self.extract(0).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(1).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(2).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(3).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(4).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(5).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(6).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(7).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(8).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(9).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(10).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(11).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(12).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(13).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(14).min(0x00007FFF).max(-0x00008000) as i16,
+                    self.extract(15).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(0).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(1).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(2).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(3).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(4).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(5).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(6).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(7).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(8).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(9).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(10).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(11).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(12).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(13).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(14).min(0x00007FFF).max(-0x00008000) as i16,
+                    other.extract(15).min(0x00007FFF).max(-0x00008000) as i16)
+    }
+}
+
+impl Downcast<i32x16> for i64x8 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> i32x16 {
+        fallback!();
+        i32x16::new(self.extract(0).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    self.extract(1).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    self.extract(2).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    self.extract(3).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    self.extract(4).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    self.extract(5).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    self.extract(6).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    self.extract(7).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(0).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(1).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(2).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(3).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(4).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(5).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(6).min(0x7FFFFFFF).max(-0x80000000) as i32,
+                    other.extract(7).min(0x7FFFFFFF).max(-0x80000000) as i32)
+    }
+}
+
+impl Downcast<u32x16> for u64x8 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> u32x16 {
+        fallback!();
+        u32x16::new(self.extract(0).min(0xFFFFFFFF) as u32,
+                    self.extract(1).min(0xFFFFFFFF) as u32,
+                    self.extract(2).min(0xFFFFFFFF) as u32,
+                    self.extract(3).min(0xFFFFFFFF) as u32,
+                    self.extract(4).min(0xFFFFFFFF) as u32,
+                    self.extract(5).min(0xFFFFFFFF) as u32,
+                    self.extract(6).min(0xFFFFFFFF) as u32,
+                    self.extract(7).min(0xFFFFFFFF) as u32,
+                    other.extract(0).min(0xFFFFFFFF) as u32,
+                    other.extract(1).min(0xFFFFFFFF) as u32,
+                    other.extract(2).min(0xFFFFFFFF) as u32,
+                    other.extract(3).min(0xFFFFFFFF) as u32,
+                    other.extract(4).min(0xFFFFFFFF) as u32,
+                    other.extract(5).min(0xFFFFFFFF) as u32,
+                    other.extract(6).min(0xFFFFFFFF) as u32,
+                    other.extract(7).min(0xFFFFFFFF) as u32)
+    }
+}
+
+impl Downcast<f32x16> for f64x8 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> f32x16 {
+        fallback!();
+        f32x16::new(self.extract(0) as f32,
+                    self.extract(1) as f32,
+                    self.extract(2) as f32,
+                    self.extract(3) as f32,
+                    self.extract(4) as f32,
+                    self.extract(5) as f32,
+                    self.extract(6) as f32,
+                    self.extract(7) as f32,
+                    other.extract(0) as f32,
+                    other.extract(1) as f32,
+                    other.extract(2) as f32,
+                    other.extract(3) as f32,
+                    other.extract(4) as f32,
+                    other.extract(5) as f32,
+                    other.extract(6) as f32,
+                    other.extract(7) as f32)
+    }
+}
+
+impl Downcast<i8x64> for i16x32 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> i8x64 {
+        fallback!();
+        i8x64::new(self.extract(0).min(0x007F).max(-0x0080) as i8,
+                   self.extract(1).min(0x007F).max(-0x0080) as i8,
+                   self.extract(2).min(0x007F).max(-0x0080) as i8,
+                   self.extract(3).min(0x007F).max(-0x0080) as i8,
+                   self.extract(4).min(0x007F).max(-0x0080) as i8,
+                   self.extract(5).min(0x007F).max(-0x0080) as i8,
+                   self.extract(6).min(0x007F).max(-0x0080) as i8,
+                   self.extract(7).min(0x007F).max(-0x0080) as i8,
+                   self.extract(8).min(0x007F).max(-0x0080) as i8,
+                   self.extract(9).min(0x007F).max(-0x0080) as i8,
+                   self.extract(10).min(0x007F).max(-0x0080) as i8,
+                   self.extract(11).min(0x007F).max(-0x0080) as i8,
+                   self.extract(12).min(0x007F).max(-0x0080) as i8,
+                   self.extract(13).min(0x007F).max(-0x0080) as i8,
+                   self.extract(14).min(0x007F).max(-0x0080) as i8,
+                   self.extract(15).min(0x007F).max(-0x0080) as i8,
+                   self.extract(16).min(0x007F).max(-0x0080) as i8,
+                   self.extract(17).min(0x007F).max(-0x0080) as i8,
+                   self.extract(18).min(0x007F).max(-0x0080) as i8,
+                   self.extract(19).min(0x007F).max(-0x0080) as i8,
+                   self.extract(20).min(0x007F).max(-0x0080) as i8,
+                   self.extract(21).min(0x007F).max(-0x0080) as i8,
+                   self.extract(22).min(0x007F).max(-0x0080) as i8,
+                   self.extract(23).min(0x007F).max(-0x0080) as i8,
+                   self.extract(24).min(0x007F).max(-0x0080) as i8,
+                   self.extract(25).min(0x007F).max(-0x0080) as i8,
+                   self.extract(26).min(0x007F).max(-0x0080) as i8,
+                   self.extract(27).min(0x007F).max(-0x0080) as i8,
+                   self.extract(28).min(0x007F).max(-0x0080) as i8,
+                   self.extract(29).min(0x007F).max(-0x0080) as i8,
+                   self.extract(30).min(0x007F).max(-0x0080) as i8,
+                   self.extract(31).min(0x007F).max(-0x0080) as i8,
+                   other.extract(0).min(0x007F).max(-0x0080) as i8,
+                   other.extract(1).min(0x007F).max(-0x0080) as i8,
+                   other.extract(2).min(0x007F).max(-0x0080) as i8,
+                   other.extract(3).min(0x007F).max(-0x0080) as i8,
+                   other.extract(4).min(0x007F).max(-0x0080) as i8,
+                   other.extract(5).min(0x007F).max(-0x0080) as i8,
+                   other.extract(6).min(0x007F).max(-0x0080) as i8,
+                   other.extract(7).min(0x007F).max(-0x0080) as i8,
+                   other.extract(8).min(0x007F).max(-0x0080) as i8,
+                   other.extract(9).min(0x007F).max(-0x0080) as i8,
+                   other.extract(10).min(0x007F).max(-0x0080) as i8,
+                   other.extract(11).min(0x007F).max(-0x0080) as i8,
+                   other.extract(12).min(0x007F).max(-0x0080) as i8,
+                   other.extract(13).min(0x007F).max(-0x0080) as i8,
+                   other.extract(14).min(0x007F).max(-0x0080) as i8,
+                   other.extract(15).min(0x007F).max(-0x0080) as i8,
+                   other.extract(16).min(0x007F).max(-0x0080) as i8,
+                   other.extract(17).min(0x007F).max(-0x0080) as i8,
+                   other.extract(18).min(0x007F).max(-0x0080) as i8,
+                   other.extract(19).min(0x007F).max(-0x0080) as i8,
+                   other.extract(20).min(0x007F).max(-0x0080) as i8,
+                   other.extract(21).min(0x007F).max(-0x0080) as i8,
+                   other.extract(22).min(0x007F).max(-0x0080) as i8,
+                   other.extract(23).min(0x007F).max(-0x0080) as i8,
+                   other.extract(24).min(0x007F).max(-0x0080) as i8,
+                   other.extract(25).min(0x007F).max(-0x0080) as i8,
+                   other.extract(26).min(0x007F).max(-0x0080) as i8,
+                   other.extract(27).min(0x007F).max(-0x0080) as i8,
+                   other.extract(28).min(0x007F).max(-0x0080) as i8,
+                   other.extract(29).min(0x007F).max(-0x0080) as i8,
+                   other.extract(30).min(0x007F).max(-0x0080) as i8,
+                   other.extract(31).min(0x007F).max(-0x0080) as i8)
+    }
+}
+
+impl Downcast<u16x32> for u32x16 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> u16x32 {
+        fallback!();
+        u16x32::new(self.extract(0).min(0x0000FFFF) as u16,
+                    self.extract(1).min(0x0000FFFF) as u16,
+                    self.extract(2).min(0x0000FFFF) as u16,
+                    self.extract(3).min(0x0000FFFF) as u16,
+                    self.extract(4).min(0x0000FFFF) as u16,
+                    self.extract(5).min(0x0000FFFF) as u16,
+                    self.extract(6).min(0x0000FFFF) as u16,
+                    self.extract(7).min(0x0000FFFF) as u16,
+                    self.extract(8).min(0x0000FFFF) as u16,
+                    self.extract(9).min(0x0000FFFF) as u16,
+                    self.extract(10).min(0x0000FFFF) as u16,
+                    self.extract(11).min(0x0000FFFF) as u16,
+                    self.extract(12).min(0x0000FFFF) as u16,
+                    self.extract(13).min(0x0000FFFF) as u16,
+                    self.extract(14).min(0x0000FFFF) as u16,
+                    self.extract(15).min(0x0000FFFF) as u16,
+                    other.extract(0).min(0x0000FFFF) as u16,
+                    other.extract(1).min(0x0000FFFF) as u16,
+                    other.extract(2).min(0x0000FFFF) as u16,
+                    other.extract(3).min(0x0000FFFF) as u16,
+                    other.extract(4).min(0x0000FFFF) as u16,
+                    other.extract(5).min(0x0000FFFF) as u16,
+                    other.extract(6).min(0x0000FFFF) as u16,
+                    other.extract(7).min(0x0000FFFF) as u16,
+                    other.extract(8).min(0x0000FFFF) as u16,
+                    other.extract(9).min(0x0000FFFF) as u16,
+                    other.extract(10).min(0x0000FFFF) as u16,
+                    other.extract(11).min(0x0000FFFF) as u16,
+                    other.extract(12).min(0x0000FFFF) as u16,
+                    other.extract(13).min(0x0000FFFF) as u16,
+                    other.extract(14).min(0x0000FFFF) as u16,
+                    other.extract(15).min(0x0000FFFF) as u16)
+    }
+}
+
+impl Downcast<u8x64> for u16x32 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> u8x64 {
+        fallback!();
+        u8x64::new(self.extract(0).min(0x00FF) as u8,
+                   self.extract(1).min(0x00FF) as u8,
+                   self.extract(2).min(0x00FF) as u8,
+                   self.extract(3).min(0x00FF) as u8,
+                   self.extract(4).min(0x00FF) as u8,
+                   self.extract(5).min(0x00FF) as u8,
+                   self.extract(6).min(0x00FF) as u8,
+                   self.extract(7).min(0x00FF) as u8,
+                   self.extract(8).min(0x00FF) as u8,
+                   self.extract(9).min(0x00FF) as u8,
+                   self.extract(10).min(0x00FF) as u8,
+                   self.extract(11).min(0x00FF) as u8,
+                   self.extract(12).min(0x00FF) as u8,
+                   self.extract(13).min(0x00FF) as u8,
+                   self.extract(14).min(0x00FF) as u8,
+                   self.extract(15).min(0x00FF) as u8,
+                   self.extract(16).min(0x00FF) as u8,
+                   self.extract(17).min(0x00FF) as u8,
+                   self.extract(18).min(0x00FF) as u8,
+                   self.extract(19).min(0x00FF) as u8,
+                   self.extract(20).min(0x00FF) as u8,
+                   self.extract(21).min(0x00FF) as u8,
+                   self.extract(22).min(0x00FF) as u8,
+                   self.extract(23).min(0x00FF) as u8,
+                   self.extract(24).min(0x00FF) as u8,
+                   self.extract(25).min(0x00FF) as u8,
+                   self.extract(26).min(0x00FF) as u8,
+                   self.extract(27).min(0x00FF) as u8,
+                   self.extract(28).min(0x00FF) as u8,
+                   self.extract(29).min(0x00FF) as u8,
+                   self.extract(30).min(0x00FF) as u8,
+                   self.extract(31).min(0x00FF) as u8,
+                   other.extract(0).min(0x00FF) as u8,
+                   other.extract(1).min(0x00FF) as u8,
+                   other.extract(2).min(0x00FF) as u8,
+                   other.extract(3).min(0x00FF) as u8,
+                   other.extract(4).min(0x00FF) as u8,
+                   other.extract(5).min(0x00FF) as u8,
+                   other.extract(6).min(0x00FF) as u8,
+                   other.extract(7).min(0x00FF) as u8,
+                   other.extract(8).min(0x00FF) as u8,
+                   other.extract(9).min(0x00FF) as u8,
+                   other.extract(10).min(0x00FF) as u8,
+                   other.extract(11).min(0x00FF) as u8,
+                   other.extract(12).min(0x00FF) as u8,
+                   other.extract(13).min(0x00FF) as u8,
+                   other.extract(14).min(0x00FF) as u8,
+                   other.extract(15).min(0x00FF) as u8,
+                   other.extract(16).min(0x00FF) as u8,
+                   other.extract(17).min(0x00FF) as u8,
+                   other.extract(18).min(0x00FF) as u8,
+                   other.extract(19).min(0x00FF) as u8,
+                   other.extract(20).min(0x00FF) as u8,
+                   other.extract(21).min(0x00FF) as u8,
+                   other.extract(22).min(0x00FF) as u8,
+                   other.extract(23).min(0x00FF) as u8,
+                   other.extract(24).min(0x00FF) as u8,
+                   other.extract(25).min(0x00FF) as u8,
+                   other.extract(26).min(0x00FF) as u8,
+                   other.extract(27).min(0x00FF) as u8,
+                   other.extract(28).min(0x00FF) as u8,
+                   other.extract(29).min(0x00FF) as u8,
+                   other.extract(30).min(0x00FF) as u8,
+                   other.extract(31).min(0x00FF) as u8)
+    }
+}
+
+impl Downcast<PackedF16<u16x8>> for f32x4 {
+    #[inline(always)]
+    #[cfg(target_feature = "f16c")]
+    fn saturating_downcast(self, other: Self) -> PackedF16<u16x8> {
+        // vcvtps2ph only fills the low 64 bits; an immediate of 0 rounds to
+        // nearest even
+        optimized!();
+        unsafe {
+            let lo = _mm_cvtps_ph(transmute(self), 0x00);
+            let hi = _mm_cvtps_ph(transmute(other), 0x00);
+            PackedF16(transmute(_mm_unpacklo_epi64(transmute(lo), transmute(hi))))
+        }
+    }
+
+    #[inline(always)]
+    #[cfg(not(target_feature = "f16c"))]
+    fn saturating_downcast(self, other: Self) -> PackedF16<u16x8> {
+        fallback!();
+        PackedF16(downcast_half_polyfill(self, other, f32_to_f16))
+    }
+}
+
+impl Downcast<PackedF16<u16x16>> for f32x8 {
+    #[inline(always)]
+    #[cfg(target_feature = "f16c")]
+    fn saturating_downcast(self, other: Self) -> PackedF16<u16x16> {
+        optimized!();
+        unsafe {
+            let lo = _mm256_cvtps_ph(transmute(self), 0x00);
+            let hi = _mm256_cvtps_ph(transmute(other), 0x00);
+            PackedF16(transmute(_mm256_insertf128_si256(_mm256_castsi128_si256(transmute(lo)), transmute(hi), 1)))
+        }
+    }
+
+    #[inline(always)]
+    #[cfg(not(target_feature = "f16c"))]
+    fn saturating_downcast(self, other: Self) -> PackedF16<u16x16> {
+        fallback!();
+        PackedF16(downcast_half_polyfill(self, other, f32_to_f16))
+    }
+}
+
+impl Downcast<PackedF16<u16x32>> for f32x16 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> PackedF16<u16x32> {
+        fallback!();
+        PackedF16(downcast_half_polyfill(self, other, f32_to_f16))
+    }
+}
+
+impl Downcast<PackedBF16<u16x8>> for f32x4 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> PackedBF16<u16x8> {
+        fallback!();
+        PackedBF16(downcast_half_polyfill(self, other, f32_to_bf16))
+    }
+}
+
+impl Downcast<PackedBF16<u16x16>> for f32x8 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> PackedBF16<u16x16> {
+        fallback!();
+        PackedBF16(downcast_half_polyfill(self, other, f32_to_bf16))
+    }
+}
+
+impl Downcast<PackedBF16<u16x32>> for f32x16 {
+    #[inline(always)]
+    fn saturating_downcast(self, other: Self) -> PackedBF16<u16x32> {
+        fallback!();
+        PackedBF16(downcast_half_polyfill(self, other, f32_to_bf16))
+    }
+}
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    #[test]
+    fn downcast_keeps_halves_in_order() {
+        let out: i16x8 = i32x4::splat(1).saturating_downcast(i32x4::splat(2));
+        assert_eq!(out, i16x8::halfs(1, 2));
+        let out: i8x16 = i16x8::splat(1).saturating_downcast(i16x8::splat(2));
+        assert_eq!(out, i8x16::halfs(1, 2));
+        let out: i16x16 = i32x8::splat(1).saturating_downcast(i32x8::splat(2));
+        assert_eq!(out, i16x16::halfs(1, 2));
+        let out: i8x32 = i16x16::splat(1).saturating_downcast(i16x16::splat(2));
+        assert_eq!(out, i8x32::halfs(1, 2));
+        let out: u16x16 = u32x8::splat(1).saturating_downcast(u32x8::splat(2));
+        assert_eq!(out, u16x16::halfs(1, 2));
+        let out: u8x32 = u16x16::splat(1).saturating_downcast(u16x16::splat(2));
+        assert_eq!(out, u8x32::halfs(1, 2));
+    }
+
+    #[test]
+    fn downcast_saturates_unsigned() {
+        let out: u8x16 = u16x8::splat(0xFFFF).saturating_downcast(u16x8::splat(0x8000));
+        assert_eq!(out, u8x16::splat(0xFF));
+        let out: u16x8 = u32x4::splat(0xFFFF_FFFF).saturating_downcast(u32x4::splat(0x8000_0000));
+        assert_eq!(out, u16x8::splat(0xFFFF));
+        let out: u32x4 = u64x2::splat(!0).saturating_downcast(u64x2::splat(0x1_0000_0000));
+        assert_eq!(out, u32x4::splat(0xFFFF_FFFF));
+        let out: u8x32 = u16x16::splat(0xFFFF).saturating_downcast(u16x16::splat(0x8000));
+        assert_eq!(out, u8x32::splat(0xFF));
+        let out: u16x16 = u32x8::splat(0xFFFF_FFFF).saturating_downcast(u32x8::splat(0x8000_0000));
+        assert_eq!(out, u16x16::splat(0xFFFF));
+        let out: u32x8 = u64x4::splat(!0).saturating_downcast(u64x4::splat(0x1_0000_0000));
+        assert_eq!(out, u32x8::splat(0xFFFF_FFFF));
+    }
+}
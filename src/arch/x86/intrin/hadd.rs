@@ -92,34 +92,34 @@ impl HAdd for i32x8 {
     }
 }
 
-impl HAdd for u64x2 { hop!(hadd, Add::add, 0, 1); }
-impl HAdd for u64x4 { hop!(hadd, Add::add, 0, 1, 2, 3); }
-impl HAdd for u64x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HAdd for u32x4 { hop!(hadd, Add::add, 0, 1, 2, 3); }
-impl HAdd for u32x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HAdd for u32x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HAdd for u16x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HAdd for u16x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HAdd for u16x32 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HAdd for u8x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HAdd for u8x32 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HAdd for u8x64 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
-impl HAdd for i64x2 { hop!(hadd, Add::add, 0, 1); }
-impl HAdd for i64x4 { hop!(hadd, Add::add, 0, 1, 2, 3); }
-impl HAdd for i64x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for u64x2 { hop!(hadd, u64::wrapping_add, 0, 1); }
+impl HAdd for u64x4 { hop!(hadd, u64::wrapping_add, 0, 1, 2, 3); }
+impl HAdd for u64x8 { hop!(hadd, u64::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for u32x4 { hop!(hadd, u32::wrapping_add, 0, 1, 2, 3); }
+impl HAdd for u32x8 { hop!(hadd, u32::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for u32x16 { hop!(hadd, u32::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for u16x8 { hop!(hadd, u16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for u16x16 { hop!(hadd, u16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for u16x32 { hop!(hadd, u16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HAdd for u8x16 { hop!(hadd, u8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for u8x32 { hop!(hadd, u8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HAdd for u8x64 { hop!(hadd, u8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
+impl HAdd for i64x2 { hop!(hadd, i64::wrapping_add, 0, 1); }
+impl HAdd for i64x4 { hop!(hadd, i64::wrapping_add, 0, 1, 2, 3); }
+impl HAdd for i64x8 { hop!(hadd, i64::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
 #[cfg(not(target_feature = "ssse3"))]
-impl HAdd for i32x4 { hop!(hadd, Add::add, 0, 1, 2, 3); }
+impl HAdd for i32x4 { hop!(hadd, i32::wrapping_add, 0, 1, 2, 3); }
 #[cfg(not(target_feature = "avx2"))]
-impl HAdd for i32x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HAdd for i32x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for i32x8 { hop!(hadd, i32::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for i32x16 { hop!(hadd, i32::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
 #[cfg(not(target_feature = "ssse3"))]
-impl HAdd for i16x8 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HAdd for i16x8 { hop!(hadd, i16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7); }
 #[cfg(not(target_feature = "avx2"))]
-impl HAdd for i16x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HAdd for i16x32 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HAdd for i8x16 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HAdd for i8x32 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HAdd for i8x64 { hop!(hadd, Add::add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
+impl HAdd for i16x16 { hop!(hadd, i16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for i16x32 { hop!(hadd, i16::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HAdd for i8x16 { hop!(hadd, i8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HAdd for i8x32 { hop!(hadd, i8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HAdd for i8x64 { hop!(hadd, i8::wrapping_add, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
 #[cfg(not(target_feature = "sse3"))]
 impl HAdd for f64x2 { hop!(hadd, Add::add, 0, 1); }
 #[cfg(not(target_feature = "avx"))]
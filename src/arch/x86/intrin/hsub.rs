@@ -91,34 +91,34 @@ impl HSub for i32x8 {
     }
 }
 
-impl HSub for u64x2 { hop!(hsub, Sub::sub, 0, 1); }
-impl HSub for u64x4 { hop!(hsub, Sub::sub, 0, 1, 2, 3); }
-impl HSub for u64x8 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HSub for u32x4 { hop!(hsub, Sub::sub, 0, 1, 2, 3); }
-impl HSub for u32x8 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HSub for u32x16 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HSub for u16x8 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HSub for u16x16 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HSub for u16x32 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HSub for u8x16 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HSub for u8x32 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HSub for u8x64 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
-impl HSub for i64x2 { hop!(hsub, Sub::sub, 0, 1); }
-impl HSub for i64x4 { hop!(hsub, Sub::sub, 0, 1, 2, 3); }
-impl HSub for i64x8 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HSub for u64x2 { hop!(hsub, u64::wrapping_sub, 0, 1); }
+impl HSub for u64x4 { hop!(hsub, u64::wrapping_sub, 0, 1, 2, 3); }
+impl HSub for u64x8 { hop!(hsub, u64::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HSub for u32x4 { hop!(hsub, u32::wrapping_sub, 0, 1, 2, 3); }
+impl HSub for u32x8 { hop!(hsub, u32::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HSub for u32x16 { hop!(hsub, u32::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HSub for u16x8 { hop!(hsub, u16::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HSub for u16x16 { hop!(hsub, u16::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HSub for u16x32 { hop!(hsub, u16::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HSub for u8x16 { hop!(hsub, u8::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HSub for u8x32 { hop!(hsub, u8::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HSub for u8x64 { hop!(hsub, u8::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
+impl HSub for i64x2 { hop!(hsub, i64::wrapping_sub, 0, 1); }
+impl HSub for i64x4 { hop!(hsub, i64::wrapping_sub, 0, 1, 2, 3); }
+impl HSub for i64x8 { hop!(hsub, i64::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7); }
 #[cfg(not(target_feature = "ssse3"))]
-impl HSub for i32x4 { hop!(hsub, Sub::sub, 0, 1, 2, 3); }
+impl HSub for i32x4 { hop!(hsub, i32::wrapping_sub, 0, 1, 2, 3); }
 #[cfg(not(target_feature = "avx2"))]
-impl HSub for i32x8 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7); }
-impl HSub for i32x16 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HSub for i32x8 { hop!(hsub, i32::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HSub for i32x16 { hop!(hsub, i32::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
 #[cfg(not(target_feature = "ssse3"))]
-impl HSub for i16x8 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7); }
+impl HSub for i16x8 { hop!(hsub, i16::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7); }
 #[cfg(not(target_feature = "avx2"))]
-impl HSub for i16x16 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HSub for i16x32 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HSub for i8x16 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
-impl HSub for i8x32 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
-impl HSub for i8x64 { hop!(hsub, Sub::sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
+impl HSub for i16x16 { hop!(hsub, i16::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HSub for i16x32 { hop!(hsub, i16::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HSub for i8x16 { hop!(hsub, i8::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15); }
+impl HSub for i8x32 { hop!(hsub, i8::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31); }
+impl HSub for i8x64 { hop!(hsub, i8::wrapping_sub, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63); }
 #[cfg(not(target_feature = "sse3"))]
 impl HSub for f64x2 { hop!(hsub, Sub::sub, 0, 1); }
 #[cfg(not(target_feature = "avx"))]
@@ -0,0 +1,29 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::intrin::lookup::*;
+
+impl_packed_lookup!(u8x16, u8, "ssse3", _mm_shuffle_epi8);
+impl_packed_lookup!(i8x16, i8, "ssse3", _mm_shuffle_epi8);
+impl_packed_lookup!(u8x32, u8, "avx2", _mm256_shuffle_epi8);
+impl_packed_lookup!(i8x32, i8, "avx2", _mm256_shuffle_epi8);
+impl_packed_lookup!(u8x64, u8, "avx512-butnotyet", _mm512_shuffle_epi8);
+impl_packed_lookup!(i8x64, i8, "avx512-butnotyet", _mm512_shuffle_epi8);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    test_packed_lookup!((u8x16, i8x16, u8x32, i8x32, u8x64, i8x64),
+                        (u8, i8, u8, i8, u8, i8),
+                        (lookup_u8x16, lookup_i8x16, lookup_u8x32, lookup_i8x32, lookup_u8x64, lookup_i8x64));
+}
@@ -9,6 +9,7 @@
 
 mod abs;
 mod addsub;
+mod cast;
 mod cmp;
 mod destride;
 mod downcast;
@@ -16,9 +17,13 @@ mod endian;
 mod eq;
 mod hadd;
 mod hsub;
+mod lookup;
 mod merge;
+mod movemask;
+mod mul_add;
 mod popcnt;
 mod recip;
+mod restride;
 mod round;
 mod sum;
 mod rsqrt;
@@ -26,8 +31,12 @@ mod saturating_add;
 mod saturating_hadd;
 mod saturating_sub;
 mod saturating_hsub;
+mod sort;
 mod sqrt;
+mod stream;
+mod swizzle;
 mod transmute;
+mod transpose;
 mod upcast;
 
 // We use an internal prelude not to clutter the namespace when we import
@@ -35,6 +44,7 @@ mod upcast;
 pub mod prelude {
     pub use super::abs::*;
     pub use super::addsub::*;
+    pub use super::cast::*;
     pub use super::cmp::*;
     pub use super::destride::*;
     pub use super::downcast::*;
@@ -42,9 +52,13 @@ pub mod prelude {
     pub use super::eq::*;
     pub use super::hadd::*;
     pub use super::hsub::*;
+    pub use super::lookup::*;
     pub use super::merge::*;
+    pub use super::movemask::*;
+    pub use super::mul_add::*;
     pub use super::popcnt::*;
     pub use super::recip::*;
+    pub use super::restride::*;
     pub use super::round::*;
     pub use super::rsqrt::*;
     pub use super::sum::*;
@@ -52,6 +66,10 @@ pub mod prelude {
     pub use super::saturating_hadd::*;
     pub use super::saturating_hsub::*;
     pub use super::saturating_sub::*;
+    pub use super::sort::*;
+    pub use super::stream::*;
+    pub use super::swizzle::*;
     pub use super::transmute::*;
+    pub use super::transpose::*;
     pub use super::upcast::*;
 }
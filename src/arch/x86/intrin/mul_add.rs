@@ -0,0 +1,28 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::intrin::mul_add::*;
+
+impl_packed_mul_add!(f32x4, "fma", _mm_fmadd_ps);
+impl_packed_mul_add!(f64x2, "fma", _mm_fmadd_pd);
+impl_packed_mul_add!(f32x8, "fma", _mm256_fmadd_ps);
+impl_packed_mul_add!(f64x4, "fma", _mm256_fmadd_pd);
+impl_packed_mul_add!(f32x16, "avx512-butnotyet", _mm512_fmadd_ps);
+impl_packed_mul_add!(f64x8, "avx512-butnotyet", _mm512_fmadd_pd);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    test_packed_mul_add!((f32x4, f64x2, f32x8, f64x4, f32x16, f64x8),
+                         (mul_add_f32x4, mul_add_f64x2, mul_add_f32x8, mul_add_f64x4, mul_add_f32x16, mul_add_f64x8));
+}
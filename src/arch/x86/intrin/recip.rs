@@ -23,3 +23,9 @@ rust_fallback_impl! {
     }
 }
 
+rust_fallback_impl! {
+    impl Recip for f32x16 where "__undefined" {
+        recip => __undefined(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    }
+}
+
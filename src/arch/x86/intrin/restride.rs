@@ -0,0 +1,116 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::intrin::restride::*;
+use crate::core::mem::transmute;
+
+macro_rules! impl_restride_128 {
+    ($($vec:ty, $feat:expr, $lo:ident, $hi:ident);*;) => {
+        $(
+            impl Restride for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn interleave_two(self, other: Self) -> (Self, Self) {
+                    optimized!();
+                    unsafe {
+                        (transmute($lo(transmute(self), transmute(other))),
+                         transmute($hi(transmute(self), transmute(other))))
+                    }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn interleave_two(self, other: Self) -> (Self, Self) {
+                    fallback!();
+                    let mut out = [self; 2];
+                    interleave_polyfill(&[self, other], &mut out);
+                    (out[0], out[1])
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! impl_restride_256 {
+    ($($vec:ty, $feat:expr, $lo:ident, $hi:ident, $perm:ident);*;) => {
+        $(
+            impl Restride for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn interleave_two(self, other: Self) -> (Self, Self) {
+                    optimized!();
+                    unsafe {
+                        // The unpacks interleave within each 128-bit lane, so
+                        // we swap the high lane of `lo` with the low lane of
+                        // `hi` afterward.
+                        let lo = $lo(transmute(self), transmute(other));
+                        let hi = $hi(transmute(self), transmute(other));
+                        (transmute($perm(transmute(lo), transmute(hi), 0x20)),
+                         transmute($perm(transmute(lo), transmute(hi), 0x31)))
+                    }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn interleave_two(self, other: Self) -> (Self, Self) {
+                    fallback!();
+                    let mut out = [self; 2];
+                    interleave_polyfill(&[self, other], &mut out);
+                    (out[0], out[1])
+                }
+            }
+        )*
+    }
+}
+
+impl_restride_128! {
+    u8x16, "sse2", _mm_unpacklo_epi8, _mm_unpackhi_epi8;
+    i8x16, "sse2", _mm_unpacklo_epi8, _mm_unpackhi_epi8;
+    u16x8, "sse2", _mm_unpacklo_epi16, _mm_unpackhi_epi16;
+    i16x8, "sse2", _mm_unpacklo_epi16, _mm_unpackhi_epi16;
+    u32x4, "sse2", _mm_unpacklo_epi32, _mm_unpackhi_epi32;
+    i32x4, "sse2", _mm_unpacklo_epi32, _mm_unpackhi_epi32;
+    f32x4, "sse", _mm_unpacklo_ps, _mm_unpackhi_ps;
+    u64x2, "sse2", _mm_unpacklo_epi64, _mm_unpackhi_epi64;
+    i64x2, "sse2", _mm_unpacklo_epi64, _mm_unpackhi_epi64;
+    f64x2, "sse2", _mm_unpacklo_pd, _mm_unpackhi_pd;
+}
+
+impl_restride_256! {
+    u8x32, "avx2", _mm256_unpacklo_epi8, _mm256_unpackhi_epi8, _mm256_permute2x128_si256;
+    i8x32, "avx2", _mm256_unpacklo_epi8, _mm256_unpackhi_epi8, _mm256_permute2x128_si256;
+    u16x16, "avx2", _mm256_unpacklo_epi16, _mm256_unpackhi_epi16, _mm256_permute2x128_si256;
+    i16x16, "avx2", _mm256_unpacklo_epi16, _mm256_unpackhi_epi16, _mm256_permute2x128_si256;
+    u32x8, "avx2", _mm256_unpacklo_epi32, _mm256_unpackhi_epi32, _mm256_permute2x128_si256;
+    i32x8, "avx2", _mm256_unpacklo_epi32, _mm256_unpackhi_epi32, _mm256_permute2x128_si256;
+    f32x8, "avx", _mm256_unpacklo_ps, _mm256_unpackhi_ps, _mm256_permute2f128_ps;
+    u64x4, "avx2", _mm256_unpacklo_epi64, _mm256_unpackhi_epi64, _mm256_permute2x128_si256;
+    i64x4, "avx2", _mm256_unpacklo_epi64, _mm256_unpackhi_epi64, _mm256_permute2x128_si256;
+    f64x4, "avx", _mm256_unpacklo_pd, _mm256_unpackhi_pd, _mm256_permute2f128_pd;
+}
+
+macro_rules! impl_restride_polyfill {
+    ($($vec:ty),*) => {
+        $(
+            impl Restride for $vec {
+                #[inline(always)]
+                fn interleave_two(self, other: Self) -> (Self, Self) {
+                    fallback!();
+                    let mut out = [self; 2];
+                    interleave_polyfill(&[self, other], &mut out);
+                    (out[0], out[1])
+                }
+            }
+        )*
+    }
+}
+
+impl_restride_polyfill!(u8x64, i8x64, u16x32, i16x32, u32x16, i32x16, f32x16, u64x8, i64x8, f64x8);
@@ -25,6 +25,12 @@ rust_fallback_impl! {
     }
 }
 
+rust_fallback_impl! {
+    impl Rsqrt for f32x16 where "__undefined" {
+        rsqrt => __undefined(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    }
+}
+
 impl Rsqrt for f32 {
     #[inline(always)]
     fn rsqrt(&self) -> Self {
@@ -0,0 +1,305 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::intrin::cmp::*;
+use crate::intrin::sort::*;
+use packed_simd::shuffle;
+
+// Each stage pairs every element with the element at the first index, and
+// keeps the smaller of the two where the second index is below the width of
+// the vector, or the larger where it isn't. The tables are the usual bitonic
+// network, unrolled for each width.
+macro_rules! bitonic_stage {
+    ($v:ident, [$($p:expr),*], [$($s:expr),*]) => {{
+        let partner = shuffle!($v, [$($p),*]);
+        shuffle!(Cmp::min(&$v, partner), Cmp::max(&$v, partner), [$($s),*])
+    }}
+}
+
+macro_rules! bitonic_sort_2 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [1, 0],
+                               [0, 3]);
+        v
+    }}
+}
+
+macro_rules! bitonic_clean_2 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [1, 0],
+                               [0, 3]);
+        v
+    }}
+}
+
+macro_rules! bitonic_sort_4 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [1, 0, 3, 2],
+                               [0, 5, 6, 3]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1],
+                               [0, 1, 6, 7]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2],
+                               [0, 5, 2, 7]);
+        v
+    }}
+}
+
+macro_rules! bitonic_clean_4 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [2, 3, 0, 1],
+                               [0, 1, 6, 7]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2],
+                               [0, 5, 2, 7]);
+        v
+    }}
+}
+
+macro_rules! bitonic_sort_8 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6],
+                               [0, 9, 10, 3, 4, 13, 14, 7]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5],
+                               [0, 1, 10, 11, 12, 13, 6, 7]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6],
+                               [0, 9, 2, 11, 12, 5, 14, 7]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3],
+                               [0, 1, 2, 3, 12, 13, 14, 15]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5],
+                               [0, 1, 10, 11, 4, 5, 14, 15]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6],
+                               [0, 9, 2, 11, 4, 13, 6, 15]);
+        v
+    }}
+}
+
+macro_rules! bitonic_clean_8 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3],
+                               [0, 1, 2, 3, 12, 13, 14, 15]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5],
+                               [0, 1, 10, 11, 4, 5, 14, 15]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6],
+                               [0, 9, 2, 11, 4, 13, 6, 15]);
+        v
+    }}
+}
+
+macro_rules! bitonic_sort_16 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+                               [0, 17, 18, 3, 4, 21, 22, 7, 8, 25, 26, 11, 12, 29, 30, 15]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13],
+                               [0, 1, 18, 19, 20, 21, 6, 7, 8, 9, 26, 27, 28, 29, 14, 15]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+                               [0, 17, 2, 19, 20, 5, 22, 7, 8, 25, 10, 27, 28, 13, 30, 15]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11],
+                               [0, 1, 2, 3, 20, 21, 22, 23, 24, 25, 26, 27, 12, 13, 14, 15]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13],
+                               [0, 1, 18, 19, 4, 5, 22, 23, 24, 25, 10, 11, 28, 29, 14, 15]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+                               [0, 17, 2, 19, 4, 21, 6, 23, 24, 9, 26, 11, 28, 13, 30, 15]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11],
+                               [0, 1, 2, 3, 20, 21, 22, 23, 8, 9, 10, 11, 28, 29, 30, 31]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13],
+                               [0, 1, 18, 19, 4, 5, 22, 23, 8, 9, 26, 27, 12, 13, 30, 31]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+                               [0, 17, 2, 19, 4, 21, 6, 23, 8, 25, 10, 27, 12, 29, 14, 31]);
+        v
+    }}
+}
+
+macro_rules! bitonic_clean_16 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11],
+                               [0, 1, 2, 3, 20, 21, 22, 23, 8, 9, 10, 11, 28, 29, 30, 31]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13],
+                               [0, 1, 18, 19, 4, 5, 22, 23, 8, 9, 26, 27, 12, 13, 30, 31]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+                               [0, 17, 2, 19, 4, 21, 6, 23, 8, 25, 10, 27, 12, 29, 14, 31]);
+        v
+    }}
+}
+
+macro_rules! bitonic_sort_32 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30],
+                               [0, 33, 34, 3, 4, 37, 38, 7, 8, 41, 42, 11, 12, 45, 46, 15, 16, 49, 50, 19, 20, 53, 54, 23, 24, 57, 58, 27, 28, 61, 62, 31]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29],
+                               [0, 1, 34, 35, 36, 37, 6, 7, 8, 9, 42, 43, 44, 45, 14, 15, 16, 17, 50, 51, 52, 53, 22, 23, 24, 25, 58, 59, 60, 61, 30, 31]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30],
+                               [0, 33, 2, 35, 36, 5, 38, 7, 8, 41, 10, 43, 44, 13, 46, 15, 16, 49, 18, 51, 52, 21, 54, 23, 24, 57, 26, 59, 60, 29, 62, 31]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27],
+                               [0, 1, 2, 3, 36, 37, 38, 39, 40, 41, 42, 43, 12, 13, 14, 15, 16, 17, 18, 19, 52, 53, 54, 55, 56, 57, 58, 59, 28, 29, 30, 31]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29],
+                               [0, 1, 34, 35, 4, 5, 38, 39, 40, 41, 10, 11, 44, 45, 14, 15, 16, 17, 50, 51, 20, 21, 54, 55, 56, 57, 26, 27, 60, 61, 30, 31]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30],
+                               [0, 33, 2, 35, 4, 37, 6, 39, 40, 9, 42, 11, 44, 13, 46, 15, 16, 49, 18, 51, 20, 53, 22, 55, 56, 25, 58, 27, 60, 29, 62, 31]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31, 16, 17, 18, 19, 20, 21, 22, 23],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 24, 25, 26, 27, 28, 29, 30, 31]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27],
+                               [0, 1, 2, 3, 36, 37, 38, 39, 8, 9, 10, 11, 44, 45, 46, 47, 48, 49, 50, 51, 20, 21, 22, 23, 56, 57, 58, 59, 28, 29, 30, 31]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29],
+                               [0, 1, 34, 35, 4, 5, 38, 39, 8, 9, 42, 43, 12, 13, 46, 47, 48, 49, 18, 19, 52, 53, 22, 23, 56, 57, 26, 27, 60, 61, 30, 31]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30],
+                               [0, 33, 2, 35, 4, 37, 6, 39, 8, 41, 10, 43, 12, 45, 14, 47, 48, 17, 50, 19, 52, 21, 54, 23, 56, 25, 58, 27, 60, 29, 62, 31]);
+        let v = bitonic_stage!(v, [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31, 16, 17, 18, 19, 20, 21, 22, 23],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 40, 41, 42, 43, 44, 45, 46, 47, 16, 17, 18, 19, 20, 21, 22, 23, 56, 57, 58, 59, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27],
+                               [0, 1, 2, 3, 36, 37, 38, 39, 8, 9, 10, 11, 44, 45, 46, 47, 16, 17, 18, 19, 52, 53, 54, 55, 24, 25, 26, 27, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29],
+                               [0, 1, 34, 35, 4, 5, 38, 39, 8, 9, 42, 43, 12, 13, 46, 47, 16, 17, 50, 51, 20, 21, 54, 55, 24, 25, 58, 59, 28, 29, 62, 63]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30],
+                               [0, 33, 2, 35, 4, 37, 6, 39, 8, 41, 10, 43, 12, 45, 14, 47, 16, 49, 18, 51, 20, 53, 22, 55, 24, 57, 26, 59, 28, 61, 30, 63]);
+        v
+    }}
+}
+
+macro_rules! bitonic_clean_32 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31, 16, 17, 18, 19, 20, 21, 22, 23],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 40, 41, 42, 43, 44, 45, 46, 47, 16, 17, 18, 19, 20, 21, 22, 23, 56, 57, 58, 59, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27],
+                               [0, 1, 2, 3, 36, 37, 38, 39, 8, 9, 10, 11, 44, 45, 46, 47, 16, 17, 18, 19, 52, 53, 54, 55, 24, 25, 26, 27, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29],
+                               [0, 1, 34, 35, 4, 5, 38, 39, 8, 9, 42, 43, 12, 13, 46, 47, 16, 17, 50, 51, 20, 21, 54, 55, 24, 25, 58, 59, 28, 29, 62, 63]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30],
+                               [0, 33, 2, 35, 4, 37, 6, 39, 8, 41, 10, 43, 12, 45, 14, 47, 16, 49, 18, 51, 20, 53, 22, 55, 24, 57, 26, 59, 28, 61, 30, 63]);
+        v
+    }}
+}
+
+macro_rules! bitonic_sort_64 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30, 33, 32, 35, 34, 37, 36, 39, 38, 41, 40, 43, 42, 45, 44, 47, 46, 49, 48, 51, 50, 53, 52, 55, 54, 57, 56, 59, 58, 61, 60, 63, 62],
+                               [0, 65, 66, 3, 4, 69, 70, 7, 8, 73, 74, 11, 12, 77, 78, 15, 16, 81, 82, 19, 20, 85, 86, 23, 24, 89, 90, 27, 28, 93, 94, 31, 32, 97, 98, 35, 36, 101, 102, 39, 40, 105, 106, 43, 44, 109, 110, 47, 48, 113, 114, 51, 52, 117, 118, 55, 56, 121, 122, 59, 60, 125, 126, 63]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29, 34, 35, 32, 33, 38, 39, 36, 37, 42, 43, 40, 41, 46, 47, 44, 45, 50, 51, 48, 49, 54, 55, 52, 53, 58, 59, 56, 57, 62, 63, 60, 61],
+                               [0, 1, 66, 67, 68, 69, 6, 7, 8, 9, 74, 75, 76, 77, 14, 15, 16, 17, 82, 83, 84, 85, 22, 23, 24, 25, 90, 91, 92, 93, 30, 31, 32, 33, 98, 99, 100, 101, 38, 39, 40, 41, 106, 107, 108, 109, 46, 47, 48, 49, 114, 115, 116, 117, 54, 55, 56, 57, 122, 123, 124, 125, 62, 63]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30, 33, 32, 35, 34, 37, 36, 39, 38, 41, 40, 43, 42, 45, 44, 47, 46, 49, 48, 51, 50, 53, 52, 55, 54, 57, 56, 59, 58, 61, 60, 63, 62],
+                               [0, 65, 2, 67, 68, 5, 70, 7, 8, 73, 10, 75, 76, 13, 78, 15, 16, 81, 18, 83, 84, 21, 86, 23, 24, 89, 26, 91, 92, 29, 94, 31, 32, 97, 34, 99, 100, 37, 102, 39, 40, 105, 42, 107, 108, 45, 110, 47, 48, 113, 50, 115, 116, 53, 118, 55, 56, 121, 58, 123, 124, 61, 126, 63]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27, 36, 37, 38, 39, 32, 33, 34, 35, 44, 45, 46, 47, 40, 41, 42, 43, 52, 53, 54, 55, 48, 49, 50, 51, 60, 61, 62, 63, 56, 57, 58, 59],
+                               [0, 1, 2, 3, 68, 69, 70, 71, 72, 73, 74, 75, 12, 13, 14, 15, 16, 17, 18, 19, 84, 85, 86, 87, 88, 89, 90, 91, 28, 29, 30, 31, 32, 33, 34, 35, 100, 101, 102, 103, 104, 105, 106, 107, 44, 45, 46, 47, 48, 49, 50, 51, 116, 117, 118, 119, 120, 121, 122, 123, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29, 34, 35, 32, 33, 38, 39, 36, 37, 42, 43, 40, 41, 46, 47, 44, 45, 50, 51, 48, 49, 54, 55, 52, 53, 58, 59, 56, 57, 62, 63, 60, 61],
+                               [0, 1, 66, 67, 4, 5, 70, 71, 72, 73, 10, 11, 76, 77, 14, 15, 16, 17, 82, 83, 20, 21, 86, 87, 88, 89, 26, 27, 92, 93, 30, 31, 32, 33, 98, 99, 36, 37, 102, 103, 104, 105, 42, 43, 108, 109, 46, 47, 48, 49, 114, 115, 52, 53, 118, 119, 120, 121, 58, 59, 124, 125, 62, 63]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30, 33, 32, 35, 34, 37, 36, 39, 38, 41, 40, 43, 42, 45, 44, 47, 46, 49, 48, 51, 50, 53, 52, 55, 54, 57, 56, 59, 58, 61, 60, 63, 62],
+                               [0, 65, 2, 67, 4, 69, 6, 71, 72, 9, 74, 11, 76, 13, 78, 15, 16, 81, 18, 83, 20, 85, 22, 87, 88, 25, 90, 27, 92, 29, 94, 31, 32, 97, 34, 99, 36, 101, 38, 103, 104, 41, 106, 43, 108, 45, 110, 47, 48, 113, 50, 115, 52, 117, 54, 119, 120, 57, 122, 59, 124, 61, 126, 63]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31, 16, 17, 18, 19, 20, 21, 22, 23, 40, 41, 42, 43, 44, 45, 46, 47, 32, 33, 34, 35, 36, 37, 38, 39, 56, 57, 58, 59, 60, 61, 62, 63, 48, 49, 50, 51, 52, 53, 54, 55],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 56, 57, 58, 59, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27, 36, 37, 38, 39, 32, 33, 34, 35, 44, 45, 46, 47, 40, 41, 42, 43, 52, 53, 54, 55, 48, 49, 50, 51, 60, 61, 62, 63, 56, 57, 58, 59],
+                               [0, 1, 2, 3, 68, 69, 70, 71, 8, 9, 10, 11, 76, 77, 78, 79, 80, 81, 82, 83, 20, 21, 22, 23, 88, 89, 90, 91, 28, 29, 30, 31, 32, 33, 34, 35, 100, 101, 102, 103, 40, 41, 42, 43, 108, 109, 110, 111, 112, 113, 114, 115, 52, 53, 54, 55, 120, 121, 122, 123, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29, 34, 35, 32, 33, 38, 39, 36, 37, 42, 43, 40, 41, 46, 47, 44, 45, 50, 51, 48, 49, 54, 55, 52, 53, 58, 59, 56, 57, 62, 63, 60, 61],
+                               [0, 1, 66, 67, 4, 5, 70, 71, 8, 9, 74, 75, 12, 13, 78, 79, 80, 81, 18, 19, 84, 85, 22, 23, 88, 89, 26, 27, 92, 93, 30, 31, 32, 33, 98, 99, 36, 37, 102, 103, 40, 41, 106, 107, 44, 45, 110, 111, 112, 113, 50, 51, 116, 117, 54, 55, 120, 121, 58, 59, 124, 125, 62, 63]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30, 33, 32, 35, 34, 37, 36, 39, 38, 41, 40, 43, 42, 45, 44, 47, 46, 49, 48, 51, 50, 53, 52, 55, 54, 57, 56, 59, 58, 61, 60, 63, 62],
+                               [0, 65, 2, 67, 4, 69, 6, 71, 8, 73, 10, 75, 12, 77, 14, 79, 80, 17, 82, 19, 84, 21, 86, 23, 88, 25, 90, 27, 92, 29, 94, 31, 32, 97, 34, 99, 36, 101, 38, 103, 40, 105, 42, 107, 44, 109, 46, 111, 112, 49, 114, 51, 116, 53, 118, 55, 120, 57, 122, 59, 124, 61, 126, 63]);
+        let v = bitonic_stage!(v, [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31, 16, 17, 18, 19, 20, 21, 22, 23, 40, 41, 42, 43, 44, 45, 46, 47, 32, 33, 34, 35, 36, 37, 38, 39, 56, 57, 58, 59, 60, 61, 62, 63, 48, 49, 50, 51, 52, 53, 54, 55],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 72, 73, 74, 75, 76, 77, 78, 79, 16, 17, 18, 19, 20, 21, 22, 23, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 40, 41, 42, 43, 44, 45, 46, 47, 112, 113, 114, 115, 116, 117, 118, 119, 56, 57, 58, 59, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27, 36, 37, 38, 39, 32, 33, 34, 35, 44, 45, 46, 47, 40, 41, 42, 43, 52, 53, 54, 55, 48, 49, 50, 51, 60, 61, 62, 63, 56, 57, 58, 59],
+                               [0, 1, 2, 3, 68, 69, 70, 71, 8, 9, 10, 11, 76, 77, 78, 79, 16, 17, 18, 19, 84, 85, 86, 87, 24, 25, 26, 27, 92, 93, 94, 95, 96, 97, 98, 99, 36, 37, 38, 39, 104, 105, 106, 107, 44, 45, 46, 47, 112, 113, 114, 115, 52, 53, 54, 55, 120, 121, 122, 123, 60, 61, 62, 63]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29, 34, 35, 32, 33, 38, 39, 36, 37, 42, 43, 40, 41, 46, 47, 44, 45, 50, 51, 48, 49, 54, 55, 52, 53, 58, 59, 56, 57, 62, 63, 60, 61],
+                               [0, 1, 66, 67, 4, 5, 70, 71, 8, 9, 74, 75, 12, 13, 78, 79, 16, 17, 82, 83, 20, 21, 86, 87, 24, 25, 90, 91, 28, 29, 94, 95, 96, 97, 34, 35, 100, 101, 38, 39, 104, 105, 42, 43, 108, 109, 46, 47, 112, 113, 50, 51, 116, 117, 54, 55, 120, 121, 58, 59, 124, 125, 62, 63]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30, 33, 32, 35, 34, 37, 36, 39, 38, 41, 40, 43, 42, 45, 44, 47, 46, 49, 48, 51, 50, 53, 52, 55, 54, 57, 56, 59, 58, 61, 60, 63, 62],
+                               [0, 65, 2, 67, 4, 69, 6, 71, 8, 73, 10, 75, 12, 77, 14, 79, 16, 81, 18, 83, 20, 85, 22, 87, 24, 89, 26, 91, 28, 93, 30, 95, 96, 33, 98, 35, 100, 37, 102, 39, 104, 41, 106, 43, 108, 45, 110, 47, 112, 49, 114, 51, 116, 53, 118, 55, 120, 57, 122, 59, 124, 61, 126, 63]);
+        let v = bitonic_stage!(v, [32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31, 16, 17, 18, 19, 20, 21, 22, 23, 40, 41, 42, 43, 44, 45, 46, 47, 32, 33, 34, 35, 36, 37, 38, 39, 56, 57, 58, 59, 60, 61, 62, 63, 48, 49, 50, 51, 52, 53, 54, 55],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 72, 73, 74, 75, 76, 77, 78, 79, 16, 17, 18, 19, 20, 21, 22, 23, 88, 89, 90, 91, 92, 93, 94, 95, 32, 33, 34, 35, 36, 37, 38, 39, 104, 105, 106, 107, 108, 109, 110, 111, 48, 49, 50, 51, 52, 53, 54, 55, 120, 121, 122, 123, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27, 36, 37, 38, 39, 32, 33, 34, 35, 44, 45, 46, 47, 40, 41, 42, 43, 52, 53, 54, 55, 48, 49, 50, 51, 60, 61, 62, 63, 56, 57, 58, 59],
+                               [0, 1, 2, 3, 68, 69, 70, 71, 8, 9, 10, 11, 76, 77, 78, 79, 16, 17, 18, 19, 84, 85, 86, 87, 24, 25, 26, 27, 92, 93, 94, 95, 32, 33, 34, 35, 100, 101, 102, 103, 40, 41, 42, 43, 108, 109, 110, 111, 48, 49, 50, 51, 116, 117, 118, 119, 56, 57, 58, 59, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29, 34, 35, 32, 33, 38, 39, 36, 37, 42, 43, 40, 41, 46, 47, 44, 45, 50, 51, 48, 49, 54, 55, 52, 53, 58, 59, 56, 57, 62, 63, 60, 61],
+                               [0, 1, 66, 67, 4, 5, 70, 71, 8, 9, 74, 75, 12, 13, 78, 79, 16, 17, 82, 83, 20, 21, 86, 87, 24, 25, 90, 91, 28, 29, 94, 95, 32, 33, 98, 99, 36, 37, 102, 103, 40, 41, 106, 107, 44, 45, 110, 111, 48, 49, 114, 115, 52, 53, 118, 119, 56, 57, 122, 123, 60, 61, 126, 127]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30, 33, 32, 35, 34, 37, 36, 39, 38, 41, 40, 43, 42, 45, 44, 47, 46, 49, 48, 51, 50, 53, 52, 55, 54, 57, 56, 59, 58, 61, 60, 63, 62],
+                               [0, 65, 2, 67, 4, 69, 6, 71, 8, 73, 10, 75, 12, 77, 14, 79, 16, 81, 18, 83, 20, 85, 22, 87, 24, 89, 26, 91, 28, 93, 30, 95, 32, 97, 34, 99, 36, 101, 38, 103, 40, 105, 42, 107, 44, 109, 46, 111, 48, 113, 50, 115, 52, 117, 54, 119, 56, 121, 58, 123, 60, 125, 62, 127]);
+        v
+    }}
+}
+
+macro_rules! bitonic_clean_64 {
+    ($v:expr) => {{
+        let v = $v;
+        let v = bitonic_stage!(v, [32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7, 24, 25, 26, 27, 28, 29, 30, 31, 16, 17, 18, 19, 20, 21, 22, 23, 40, 41, 42, 43, 44, 45, 46, 47, 32, 33, 34, 35, 36, 37, 38, 39, 56, 57, 58, 59, 60, 61, 62, 63, 48, 49, 50, 51, 52, 53, 54, 55],
+                               [0, 1, 2, 3, 4, 5, 6, 7, 72, 73, 74, 75, 76, 77, 78, 79, 16, 17, 18, 19, 20, 21, 22, 23, 88, 89, 90, 91, 92, 93, 94, 95, 32, 33, 34, 35, 36, 37, 38, 39, 104, 105, 106, 107, 108, 109, 110, 111, 48, 49, 50, 51, 52, 53, 54, 55, 120, 121, 122, 123, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [4, 5, 6, 7, 0, 1, 2, 3, 12, 13, 14, 15, 8, 9, 10, 11, 20, 21, 22, 23, 16, 17, 18, 19, 28, 29, 30, 31, 24, 25, 26, 27, 36, 37, 38, 39, 32, 33, 34, 35, 44, 45, 46, 47, 40, 41, 42, 43, 52, 53, 54, 55, 48, 49, 50, 51, 60, 61, 62, 63, 56, 57, 58, 59],
+                               [0, 1, 2, 3, 68, 69, 70, 71, 8, 9, 10, 11, 76, 77, 78, 79, 16, 17, 18, 19, 84, 85, 86, 87, 24, 25, 26, 27, 92, 93, 94, 95, 32, 33, 34, 35, 100, 101, 102, 103, 40, 41, 42, 43, 108, 109, 110, 111, 48, 49, 50, 51, 116, 117, 118, 119, 56, 57, 58, 59, 124, 125, 126, 127]);
+        let v = bitonic_stage!(v, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13, 18, 19, 16, 17, 22, 23, 20, 21, 26, 27, 24, 25, 30, 31, 28, 29, 34, 35, 32, 33, 38, 39, 36, 37, 42, 43, 40, 41, 46, 47, 44, 45, 50, 51, 48, 49, 54, 55, 52, 53, 58, 59, 56, 57, 62, 63, 60, 61],
+                               [0, 1, 66, 67, 4, 5, 70, 71, 8, 9, 74, 75, 12, 13, 78, 79, 16, 17, 82, 83, 20, 21, 86, 87, 24, 25, 90, 91, 28, 29, 94, 95, 32, 33, 98, 99, 36, 37, 102, 103, 40, 41, 106, 107, 44, 45, 110, 111, 48, 49, 114, 115, 52, 53, 118, 119, 56, 57, 122, 123, 60, 61, 126, 127]);
+        let v = bitonic_stage!(v, [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18, 21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30, 33, 32, 35, 34, 37, 36, 39, 38, 41, 40, 43, 42, 45, 44, 47, 46, 49, 48, 51, 50, 53, 52, 55, 54, 57, 56, 59, 58, 61, 60, 63, 62],
+                               [0, 65, 2, 67, 4, 69, 6, 71, 8, 73, 10, 75, 12, 77, 14, 79, 16, 81, 18, 83, 20, 85, 22, 87, 24, 89, 26, 91, 28, 93, 30, 95, 32, 97, 34, 99, 36, 101, 38, 103, 40, 105, 42, 107, 44, 109, 46, 111, 48, 113, 50, 115, 52, 117, 54, 119, 56, 121, 58, 123, 60, 125, 62, 127]);
+        v
+    }}
+}
+
+macro_rules! impl_sort_lanes {
+    ($sort:ident, $clean:ident, [$($rev:expr),*], $($vec:tt),*) => {
+        $(
+            impl SortLanes for $vec {
+                #[inline(always)]
+                fn sort_lanes(&self) -> Self {
+                    optimized!();
+                    $sort!(*self)
+                }
+
+                #[inline(always)]
+                fn merge_sorted(&self, other: Self) -> (Self, Self) {
+                    optimized!();
+                    let rev = shuffle!(other, [$($rev),*]);
+                    ($clean!(Cmp::min(self, rev)), $clean!(Cmp::max(self, rev)))
+                }
+            }
+        )*
+    }
+}
+
+impl_sort_lanes!(bitonic_sort_2, bitonic_clean_2, [1, 0],
+                 u64x2, i64x2, f64x2);
+impl_sort_lanes!(bitonic_sort_4, bitonic_clean_4, [3, 2, 1, 0],
+                 u32x4, i32x4, f32x4, u64x4, i64x4, f64x4);
+impl_sort_lanes!(bitonic_sort_8, bitonic_clean_8, [7, 6, 5, 4, 3, 2, 1, 0],
+                 u16x8, i16x8, u32x8, i32x8, f32x8, u64x8, i64x8, f64x8);
+impl_sort_lanes!(bitonic_sort_16, bitonic_clean_16, [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+                 u8x16, i8x16, u16x16, i16x16, u32x16, i32x16, f32x16);
+impl_sort_lanes!(bitonic_sort_32, bitonic_clean_32, [31, 30, 29, 28, 27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+                 u8x32, i8x32, u16x32, i16x32);
+impl_sort_lanes!(bitonic_sort_64, bitonic_clean_64, [63, 62, 61, 60, 59, 58, 57, 56, 55, 54, 53, 52, 51, 50, 49, 48, 47, 46, 45, 44, 43, 42, 41, 40, 39, 38, 37, 36, 35, 34, 33, 32, 31, 30, 29, 28, 27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+                 u8x64, i8x64);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    test_packed_sort_lanes!((u64x2, i64x2, f64x2, u32x4, i32x4, f32x4, u64x4, i64x4, f64x4, u16x8, i16x8, u32x8, i32x8, f32x8, u64x8, i64x8, f64x8, u8x16, i8x16, u16x16, i16x16, u32x16, i32x16, f32x16, u8x32, i8x32, u16x32, i16x32, u8x64, i8x64),
+                            (u64, i64, f64, u32, i32, f32, u64, i64, f64, u16, i16, u32, i32, f32, u64, i64, f64, u8, i8, u16, i16, u32, i32, f32, u8, i8, u16, i16, u8, i8),
+                            (sort_lanes_u64x2, sort_lanes_i64x2, sort_lanes_f64x2, sort_lanes_u32x4, sort_lanes_i32x4, sort_lanes_f32x4, sort_lanes_u64x4, sort_lanes_i64x4, sort_lanes_f64x4, sort_lanes_u16x8, sort_lanes_i16x8, sort_lanes_u32x8, sort_lanes_i32x8, sort_lanes_f32x8, sort_lanes_u64x8, sort_lanes_i64x8, sort_lanes_f64x8, sort_lanes_u8x16, sort_lanes_i8x16, sort_lanes_u16x16, sort_lanes_i16x16, sort_lanes_u32x16, sort_lanes_i32x16, sort_lanes_f32x16, sort_lanes_u8x32, sort_lanes_i8x32, sort_lanes_u16x32, sort_lanes_i16x32, sort_lanes_u8x64, sort_lanes_i8x64));
+}
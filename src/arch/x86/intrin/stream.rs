@@ -0,0 +1,35 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::intrin::stream::*;
+
+impl_packed_stream!("sse2", _mm_stream_si128, _mm_sfence,
+                    u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2);
+impl_packed_stream!("avx", _mm256_stream_si256, _mm_sfence,
+                    u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, f32x8, u64x4, i64x4, f64x4);
+impl_packed_stream!("avx512-butnotyet", _mm512_stream_si512, _mm_sfence,
+                    u8x64, i8x64, u16x32, i16x32, u32x16, i32x16, f32x16, u64x8, i64x8, f64x8);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+
+    test_packed_stream!((u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2),
+                        (u8, i8, u16, i16, u32, i32, f32, u64, i64, f64),
+                        (stream_u8x16, stream_i8x16, stream_u16x8, stream_i16x8, stream_u32x4, stream_i32x4, stream_f32x4, stream_u64x2, stream_i64x2, stream_f64x2));
+    test_packed_stream!((u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, f32x8, u64x4, i64x4, f64x4),
+                        (u8, i8, u16, i16, u32, i32, f32, u64, i64, f64),
+                        (stream_u8x32, stream_i8x32, stream_u16x16, stream_i16x16, stream_u32x8, stream_i32x8, stream_f32x8, stream_u64x4, stream_i64x4, stream_f64x4));
+    test_packed_stream!((u8x64, i8x64, u16x32, i16x32, u32x16, i32x16, f32x16, u64x8, i64x8, f64x8),
+                        (u8, i8, u16, i16, u32, i32, f32, u64, i64, f64),
+                        (stream_u8x64, stream_i8x64, stream_u16x32, stream_i16x32, stream_u32x16, stream_i32x16, stream_f32x16, stream_u64x8, stream_i64x8, stream_f64x8));
+}
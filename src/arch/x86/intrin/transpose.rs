@@ -0,0 +1,155 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::arch::current::vecs::*;
+use crate::vecs::*;
+use crate::vektor::x86_64::*;
+use crate::vektor::x86::*;
+use crate::intrin::destride::*;
+use crate::intrin::transpose::*;
+use crate::core::mem::transmute;
+
+// Interleaving rows `i` and `i + n / 2` into rows `2i` and `2i + 1` for
+// log2(n) rounds transposes an n-by-n matrix. The unpack instructions only
+// interleave within 128-bit lanes, so this transposes each lane of a wider
+// vector independently.
+macro_rules! impl_transpose_unpack {
+    ($trait:ident, $fn:ident, $n:expr, $vec:ty, $feat:expr, $lo:ident, $hi:ident, $fallback:ident) => {
+        impl $trait for $vec {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn $fn(rows: [Self; $n]) -> [Self; $n] {
+                optimized!();
+                let mut rows = rows;
+                let mut round = 1;
+                while round < $n {
+                    let prev = rows;
+                    for i in 0..$n / 2 {
+                        unsafe {
+                            rows[2 * i] = transmute($lo(transmute(prev[i]), transmute(prev[i + $n / 2])));
+                            rows[2 * i + 1] = transmute($hi(transmute(prev[i]), transmute(prev[i + $n / 2])));
+                        }
+                    }
+                    round *= 2;
+                }
+                rows
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn $fn(rows: [Self; $n]) -> [Self; $n] {
+                $fallback!(rows, $n)
+            }
+        }
+    }
+}
+
+// Square matrices can fall back to destriding, which is itself optimized for
+// some targets.
+macro_rules! transpose_fallback_destride {
+    ($rows:expr, $n:expr) => {{
+        fallback!();
+        transpose_destride!($rows, $n)
+    }}
+}
+
+macro_rules! transpose_fallback_polyfill {
+    ($rows:expr, $n:expr) => {{
+        fallback!();
+        let mut out = $rows;
+        transpose_polyfill(&$rows, &mut out);
+        out
+    }}
+}
+
+impl_transpose_unpack!(Transpose4x4, transpose4x4, 4, f32x4, "sse", _mm_unpacklo_ps, _mm_unpackhi_ps, transpose_fallback_destride);
+impl_transpose_unpack!(Transpose4x4, transpose4x4, 4, u32x4, "sse", _mm_unpacklo_ps, _mm_unpackhi_ps, transpose_fallback_destride);
+impl_transpose_unpack!(Transpose4x4, transpose4x4, 4, i32x4, "sse", _mm_unpacklo_ps, _mm_unpackhi_ps, transpose_fallback_destride);
+impl_transpose_unpack!(Transpose4x4, transpose4x4, 4, f32x8, "avx", _mm256_unpacklo_ps, _mm256_unpackhi_ps, transpose_fallback_polyfill);
+impl_transpose_unpack!(Transpose4x4, transpose4x4, 4, u32x8, "avx", _mm256_unpacklo_ps, _mm256_unpackhi_ps, transpose_fallback_polyfill);
+impl_transpose_unpack!(Transpose4x4, transpose4x4, 4, i32x8, "avx", _mm256_unpacklo_ps, _mm256_unpackhi_ps, transpose_fallback_polyfill);
+impl_transpose_polyfill!(Transpose4x4, transpose4x4, 4, f32x16, u32x16, i32x16, f64x4, u64x4, i64x4, f64x8, u64x8, i64x8);
+
+impl_transpose_unpack!(Transpose8x8, transpose8x8, 8, u16x8, "sse2", _mm_unpacklo_epi16, _mm_unpackhi_epi16, transpose_fallback_destride);
+impl_transpose_unpack!(Transpose8x8, transpose8x8, 8, i16x8, "sse2", _mm_unpacklo_epi16, _mm_unpackhi_epi16, transpose_fallback_destride);
+impl_transpose_unpack!(Transpose8x8, transpose8x8, 8, u16x16, "avx2", _mm256_unpacklo_epi16, _mm256_unpackhi_epi16, transpose_fallback_polyfill);
+impl_transpose_unpack!(Transpose8x8, transpose8x8, 8, i16x16, "avx2", _mm256_unpacklo_epi16, _mm256_unpackhi_epi16, transpose_fallback_polyfill);
+impl_transpose_polyfill!(Transpose8x8, transpose8x8, 8, u16x32, i16x32, f32x16, u32x16, i32x16, f64x8, u64x8, i64x8);
+
+impl_transpose_unpack!(Transpose16x16, transpose16x16, 16, u8x16, "sse2", _mm_unpacklo_epi8, _mm_unpackhi_epi8, transpose_fallback_destride);
+impl_transpose_unpack!(Transpose16x16, transpose16x16, 16, i8x16, "sse2", _mm_unpacklo_epi8, _mm_unpackhi_epi8, transpose_fallback_destride);
+impl_transpose_unpack!(Transpose16x16, transpose16x16, 16, u8x32, "avx2", _mm256_unpacklo_epi8, _mm256_unpackhi_epi8, transpose_fallback_polyfill);
+impl_transpose_unpack!(Transpose16x16, transpose16x16, 16, i8x32, "avx2", _mm256_unpacklo_epi8, _mm256_unpackhi_epi8, transpose_fallback_polyfill);
+impl_transpose_polyfill!(Transpose16x16, transpose16x16, 16, u8x64, i8x64, u16x16, i16x16, u16x32, i16x32);
+
+// An 8x8 block spans both lanes of a 256-bit vector, so we transpose the 4x4
+// quadrants within each lane and then swap the off-diagonal quadrants.
+macro_rules! impl_transpose8x8_avx {
+    ($($vec:ty),*) => {
+        $(
+            impl Transpose8x8 for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = "avx")]
+                fn transpose8x8(rows: [Self; 8]) -> [Self; 8] {
+                    optimized!();
+                    unsafe {
+                        let r: [f32x8; 8] = transmute(rows);
+                        let t0 = _mm256_unpacklo_ps(r[0], r[1]);
+                        let t1 = _mm256_unpackhi_ps(r[0], r[1]);
+                        let t2 = _mm256_unpacklo_ps(r[2], r[3]);
+                        let t3 = _mm256_unpackhi_ps(r[2], r[3]);
+                        let t4 = _mm256_unpacklo_ps(r[4], r[5]);
+                        let t5 = _mm256_unpackhi_ps(r[4], r[5]);
+                        let t6 = _mm256_unpacklo_ps(r[6], r[7]);
+                        let t7 = _mm256_unpackhi_ps(r[6], r[7]);
+                        let s0 = _mm256_shuffle_ps(t0, t2, 0x44);
+                        let s1 = _mm256_shuffle_ps(t0, t2, 0xEE);
+                        let s2 = _mm256_shuffle_ps(t1, t3, 0x44);
+                        let s3 = _mm256_shuffle_ps(t1, t3, 0xEE);
+                        let s4 = _mm256_shuffle_ps(t4, t6, 0x44);
+                        let s5 = _mm256_shuffle_ps(t4, t6, 0xEE);
+                        let s6 = _mm256_shuffle_ps(t5, t7, 0x44);
+                        let s7 = _mm256_shuffle_ps(t5, t7, 0xEE);
+                        transmute([_mm256_permute2f128_ps(s0, s4, 0x20),
+                                   _mm256_permute2f128_ps(s1, s5, 0x20),
+                                   _mm256_permute2f128_ps(s2, s6, 0x20),
+                                   _mm256_permute2f128_ps(s3, s7, 0x20),
+                                   _mm256_permute2f128_ps(s0, s4, 0x31),
+                                   _mm256_permute2f128_ps(s1, s5, 0x31),
+                                   _mm256_permute2f128_ps(s2, s6, 0x31),
+                                   _mm256_permute2f128_ps(s3, s7, 0x31)])
+                    }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = "avx"))]
+                fn transpose8x8(rows: [Self; 8]) -> [Self; 8] {
+                    transpose_fallback_destride!(rows, 8)
+                }
+            }
+        )*
+    }
+}
+
+impl_transpose8x8_avx!(f32x8, u32x8, i32x8);
+
+mod tests {
+    #![allow(unused_imports)]
+    use crate::prelude::*;
+    use crate::arch::current::vecs::*;
+    use crate::vecs::*;
+
+    test_packed_transpose!(transpose4x4, 4,
+                           (f32x4, u32x4, i32x4, f32x8, u32x8, i32x8, f32x16, u32x16, i32x16, f64x4, u64x4, i64x4, f64x8, u64x8, i64x8),
+                           (transpose4x4_f32x4, transpose4x4_u32x4, transpose4x4_i32x4, transpose4x4_f32x8, transpose4x4_u32x8, transpose4x4_i32x8, transpose4x4_f32x16, transpose4x4_u32x16, transpose4x4_i32x16, transpose4x4_f64x4, transpose4x4_u64x4, transpose4x4_i64x4, transpose4x4_f64x8, transpose4x4_u64x8, transpose4x4_i64x8));
+    test_packed_transpose!(transpose8x8, 8,
+                           (u16x8, i16x8, u16x16, i16x16, u16x32, i16x32, f32x8, u32x8, i32x8, f32x16, u32x16, i32x16, f64x8, u64x8, i64x8),
+                           (transpose8x8_u16x8, transpose8x8_i16x8, transpose8x8_u16x16, transpose8x8_i16x16, transpose8x8_u16x32, transpose8x8_i16x32, transpose8x8_f32x8, transpose8x8_u32x8, transpose8x8_i32x8, transpose8x8_f32x16, transpose8x8_u32x16, transpose8x8_i32x16, transpose8x8_f64x8, transpose8x8_u64x8, transpose8x8_i64x8));
+    test_packed_transpose!(transpose16x16, 16,
+                           (u8x16, i8x16, u8x32, i8x32, u8x64, i8x64, u16x16, i16x16, u16x32, i16x32),
+                           (transpose16x16_u8x16, transpose16x16_i8x16, transpose16x16_u8x32, transpose16x16_i8x32, transpose16x16_u8x64, transpose16x16_i8x64, transpose16x16_u16x16, transpose16x16_i16x16, transpose16x16_u16x32, transpose16x16_i16x32));
+}
@@ -0,0 +1,35 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::iters::Locality;
+
+/// Hint that the cache line containing `ptr` will be read soon. Prefetching
+/// never faults, so `ptr` needn't point to anything.
+#[inline(always)]
+#[cfg(target_feature = "sse")]
+pub fn prefetch<T>(ptr: *const T, locality: Locality) {
+    #[cfg(target_arch = "x86")]
+    use crate::core::arch::x86::{_mm_prefetch, _MM_HINT_NTA, _MM_HINT_T0, _MM_HINT_T1, _MM_HINT_T2};
+    #[cfg(target_arch = "x86_64")]
+    use crate::core::arch::x86_64::{_mm_prefetch, _MM_HINT_NTA, _MM_HINT_T0, _MM_HINT_T1, _MM_HINT_T2};
+    let ptr = ptr as *const i8;
+    optimized!();
+    unsafe {
+        match locality {
+            Locality::None => _mm_prefetch::<_MM_HINT_NTA>(ptr),
+            Locality::Low => _mm_prefetch::<_MM_HINT_T2>(ptr),
+            Locality::Medium => _mm_prefetch::<_MM_HINT_T1>(ptr),
+            Locality::High => _mm_prefetch::<_MM_HINT_T0>(ptr),
+        }
+    }
+}
+
+/// Hint that the cache line containing `ptr` will be read soon. Prefetching
+/// never faults, so `ptr` needn't point to anything.
+#[inline(always)]
+#[cfg(not(target_feature = "sse"))]
+pub fn prefetch<T>(_ptr: *const T, _locality: Locality) { }
@@ -0,0 +1,21 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+impl_width!(w128, "Vectors which are always 128 bits wide, regardless of the native vector width of the target.",
+            u8, u8s, u8x16, i8, i8s, i8x16, u16, u16s, u16x8, i16, i16s, i16x8,
+            u32, u32s, u32x4, i32, i32s, i32x4, f32, f32s, f32x4,
+            u64, u64s, u64x2, i64, i64s, i64x2, f64, f64s, f64x2);
+
+impl_width!(w256, "Vectors which are always 256 bits wide, regardless of the native vector width of the target.",
+            u8, u8s, u8x32, i8, i8s, i8x32, u16, u16s, u16x16, i16, i16s, i16x16,
+            u32, u32s, u32x8, i32, i32s, i32x8, f32, f32s, f32x8,
+            u64, u64s, u64x4, i64, i64s, i64x4, f64, f64s, f64x4);
+
+impl_width!(w512, "Vectors which are always 512 bits wide, regardless of the native vector width of the target.",
+            u8, u8s, u8x64, i8, i8s, i8x64, u16, u16s, u16x32, i16, i16s, i16x32,
+            u32, u32s, u32x16, i32, i32s, i32x16, f32, f32s, f32x16,
+            u64, u64s, u64x8, i64, i64s, i64x8, f64, f64s, f64x8);
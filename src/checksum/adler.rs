@@ -0,0 +1,348 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::Packed;
+use crate::arch::current::vecs::{u8s, u16s, u32s};
+use crate::intrin::transmute::Transmute;
+use crate::intrin::upcast::Upcast;
+
+// Adler-32 and Fletcher-32 both keep a sum of their input and a sum of the
+// first sum after each element. Over a block of `n` elements, the second sum
+// grows by `n` times the first sum, plus each element weighted by its
+// distance from the end of the block. Both sums are computed in 32-bit lanes
+// and reduced modulo the checksum's prime once per block.
+
+/// The sums of a block of elements, kept in 32-bit lanes.
+struct LaneSums {
+    /// The sum of each lane.
+    sum: u32s,
+    /// The sum of `sum` before each chunk.
+    prefix: u32s,
+    /// The sum of each lane weighted by its distance from the end of its
+    /// chunk.
+    weighted: u32s,
+}
+
+impl LaneSums {
+    #[inline(always)]
+    fn new() -> Self {
+        Self { sum: u32s(0), prefix: u32s(0), weighted: u32s(0) }
+    }
+
+    /// Add a chunk of elements, split into `lanes`, to the sums.
+    #[inline(always)]
+    fn add(&mut self, lanes: &[u32s], weights: &[u32s]) {
+        self.prefix = self.prefix + self.sum;
+        for (&lane, &weight) in lanes.iter().zip(weights) {
+            self.sum = self.sum + lane;
+            self.weighted = self.weighted + lane * weight;
+        }
+    }
+
+    /// Return the sum of the elements, and the sum of the elements weighted
+    /// by their distance from the end of the block, where each chunk held
+    /// `chunk_len` elements.
+    #[inline(always)]
+    fn finish(&self, chunk_len: u64) -> (u64, u64) {
+        let total = |v: u32s| v.scalar_reduce(0u64, |acc, x| acc + x as u64);
+        (total(self.sum), chunk_len * total(self.prefix) + total(self.weighted))
+    }
+}
+
+/// Return the weights of each element of a chunk of `len` elements, split
+/// into `u32s` lanes.
+#[inline(always)]
+fn chunk_weights(len: usize, weights: &mut [u32s; 4]) -> &[u32s] {
+    let lanes = len / u32s::WIDTH;
+    for (j, w) in weights.iter_mut().take(lanes).enumerate() {
+        *w = (0..u32s::WIDTH).fold(u32s(0), |w, i| {
+            w.replace(i, (len - j * u32s::WIDTH - i) as u32)
+        });
+    }
+    &weights[..lanes]
+}
+
+/// Return the sum of the bytes in `data`, and the sum of each byte weighted
+/// by its distance from the end of `data`, counting the last byte as one.
+#[inline]
+fn byte_sums(data: &[u8]) -> (u64, u64) {
+    let mut weights = [u32s(0); 4];
+    let weights = chunk_weights(u8s::WIDTH, &mut weights);
+    let mut sums = LaneSums::new();
+    let mut i = 0;
+    while i + u8s::WIDTH <= data.len() {
+        let (lo, hi): (u16s, u16s) = u8s::load(data, i).upcast();
+        let (a, b): (u32s, u32s) = lo.upcast();
+        let (c, d): (u32s, u32s) = hi.upcast();
+        sums.add(&[a, b, c, d], weights);
+        i += u8s::WIDTH;
+    }
+    let (sum, weighted) = sums.finish(u8s::WIDTH as u64);
+
+    // Each vectorized byte is also weighted by the length of the tail
+    let tail = &data[i..];
+    tail.iter().enumerate().fold((sum, weighted + sum * tail.len() as u64), |(s, w), (j, &b)| {
+        (s + b as u64, w + (tail.len() - j) as u64 * b as u64)
+    })
+}
+
+/// Return the sum of the little-endian 16-bit words in `data`, which must
+/// have an even length, and the sum of each word weighted by its distance
+/// from the end of `data`, counting the last word as one.
+#[inline]
+fn word_sums(data: &[u8]) -> (u64, u64) {
+    debug_assert_eq!(data.len() % 2, 0);
+    let mut weights = [u32s(0); 4];
+    let weights = chunk_weights(u16s::WIDTH, &mut weights);
+    let mut sums = LaneSums::new();
+    let mut i = 0;
+    while i + u8s::WIDTH <= data.len() {
+        let (a, b): (u32s, u32s) = u8s::load(data, i).be_u16s().upcast();
+        sums.add(&[a, b], weights);
+        i += u8s::WIDTH;
+    }
+    let (sum, weighted) = sums.finish(u16s::WIDTH as u64);
+
+    let tail = &data[i..];
+    let words = tail.len() / 2;
+    tail.chunks(2).enumerate().fold((sum, weighted + sum * words as u64), |(s, w), (j, pair)| {
+        let word = u16::from_le_bytes([pair[0], pair[1]]) as u64;
+        (s + word, w + (words - j) as u64 * word)
+    })
+}
+
+const ADLER_MOD: u64 = 65521;
+
+/// The largest number of bytes whose sums fit in 32-bit lanes, rounded down
+/// to a whole number of vectors.
+const ADLER_BLOCK: usize = 5552 / 64 * 64;
+
+/// The state of an Adler-32 checksum over a stream of bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    /// Return the state of a checksum over no bytes.
+    #[inline]
+    pub fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+
+    /// Add `data` to the end of the checksummed stream.
+    pub fn update(&mut self, data: &[u8]) {
+        for block in data.chunks(ADLER_BLOCK) {
+            let (sum, weighted) = byte_sums(block);
+            let (a, b) = (self.a as u64, self.b as u64);
+            self.b = ((b + block.len() as u64 * a + weighted) % ADLER_MOD) as u32;
+            self.a = ((a + sum) % ADLER_MOD) as u32;
+        }
+    }
+
+    /// Return the checksum of the stream.
+    #[inline]
+    pub fn finalize(self) -> u32 {
+        self.b << 16 | self.a
+    }
+
+    /// Return to the state of a checksum over no bytes.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Adler32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Return the Adler-32 checksum of `data`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::checksum::*;
+///
+/// # fn main() {
+/// assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+/// # }
+/// ```
+#[inline]
+pub fn adler32(data: &[u8]) -> u32 {
+    let mut state = Adler32::new();
+    state.update(data);
+    state.finalize()
+}
+
+const FLETCHER_MOD: u64 = 65535;
+
+/// The largest number of bytes whose sums fit in 32-bit lanes, rounded down
+/// to a whole number of vectors.
+const FLETCHER_BLOCK: usize = 359 * 2 / 64 * 64;
+
+/// The state of a Fletcher-32 checksum over a stream of bytes, which are
+/// summed as little-endian 16-bit words. A trailing odd byte is padded with
+/// a zero byte when the checksum is finalized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fletcher32 {
+    sum1: u32,
+    sum2: u32,
+    pending: Option<u8>,
+}
+
+impl Fletcher32 {
+    /// Return the state of a checksum over no bytes.
+    #[inline]
+    pub fn new() -> Self {
+        Self { sum1: 0, sum2: 0, pending: None }
+    }
+
+    fn update_words(&mut self, data: &[u8]) {
+        for block in data.chunks(FLETCHER_BLOCK) {
+            let (sum, weighted) = word_sums(block);
+            let (s1, s2) = (self.sum1 as u64, self.sum2 as u64);
+            self.sum2 = ((s2 + (block.len() / 2) as u64 * s1 + weighted) % FLETCHER_MOD) as u32;
+            self.sum1 = ((s1 + sum) % FLETCHER_MOD) as u32;
+        }
+    }
+
+    /// Add `data` to the end of the checksummed stream.
+    pub fn update(&mut self, data: &[u8]) {
+        let data = match (self.pending.take(), data.split_first()) {
+            (Some(lo), Some((&hi, rest))) => {
+                self.update_words(&[lo, hi]);
+                rest
+            },
+            (pending, _) => {
+                self.pending = pending;
+                data
+            },
+        };
+        let even = data.len() & !1;
+        self.update_words(&data[..even]);
+        if even < data.len() {
+            self.pending = Some(data[even]);
+        }
+    }
+
+    /// Return the checksum of the stream.
+    #[inline]
+    pub fn finalize(self) -> u32 {
+        let mut state = self;
+        if let Some(lo) = state.pending.take() {
+            state.update_words(&[lo, 0]);
+        }
+        state.sum2 << 16 | state.sum1
+    }
+
+    /// Return to the state of a checksum over no bytes.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Fletcher32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Return the Fletcher-32 checksum of `data`, summed as little-endian 16-bit
+/// words.
+///
+/// ```
+/// extern crate faster;
+/// use faster::checksum::*;
+///
+/// # fn main() {
+/// assert_eq!(fletcher32(b"abcdef"), 0x5650_2D2A);
+/// # }
+/// ```
+#[inline]
+pub fn fletcher32(data: &[u8]) -> u32 {
+    let mut state = Fletcher32::new();
+    state.update(data);
+    state.finalize()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn data(len: usize, max: u8) -> Vec<u8> {
+        testing::data(len).into_iter().map(|x| {
+            if max == 0xFF { (x >> 56) as u8 } else { (x >> 56) as u8 % (max + 1) }
+        }).collect()
+    }
+
+    fn adler_reference(data: &[u8]) -> u32 {
+        let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+            let a = (a + byte as u32) % 65521;
+            (a, (b + a) % 65521)
+        });
+        b << 16 | a
+    }
+
+    fn fletcher_reference(data: &[u8]) -> u32 {
+        let (c0, c1) = data.chunks(2).fold((0u32, 0u32), |(c0, c1), pair| {
+            let word = pair[0] as u32 | (*pair.get(1).unwrap_or(&0) as u32) << 8;
+            let c0 = (c0 + word) % 65535;
+            (c0, (c1 + c0) % 65535)
+        });
+        c1 << 16 | c0
+    }
+
+    #[test]
+    fn known_answers() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"a"), 0x0062_0062);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(fletcher32(b""), 0);
+        assert_eq!(fletcher32(b"abcde"), 0xF04F_C729);
+        assert_eq!(fletcher32(b"abcdef"), 0x5650_2D2A);
+        assert_eq!(fletcher32(b"abcdefgh"), 0xEBE1_9591);
+    }
+
+    #[test]
+    fn matches_reference() {
+        for &max in [0xFF, 0x7F, 1].iter() {
+            let input = data(20000, max);
+            for len in (0..300).chain(5500..5600).chain(19990..20000) {
+                assert_eq!(adler32(&input[..len]), adler_reference(&input[..len]), "{}", len);
+                assert_eq!(fletcher32(&input[..len]), fletcher_reference(&input[..len]), "{}", len);
+            }
+        }
+        // Saturated input stresses the lane sums the most
+        let ones = vec![0xFF; 20000];
+        assert_eq!(adler32(&ones), adler_reference(&ones));
+        assert_eq!(fletcher32(&ones), fletcher_reference(&ones));
+    }
+
+    #[test]
+    fn streaming_matches_oneshot() {
+        let input = data(3000, 0xFF);
+        for &(x, y) in [(0, 0), (1, 2), (1, 1000), (3, 7), (64, 65), (999, 2999)].iter() {
+            let mut adler = Adler32::new();
+            let mut fletcher = Fletcher32::default();
+            for part in [&input[..x], &input[x..y], &input[y..]].iter() {
+                adler.update(part);
+                fletcher.update(part);
+            }
+            assert_eq!(adler.finalize(), adler32(&input));
+            assert_eq!(fletcher.finalize(), fletcher32(&input));
+            fletcher.reset();
+            assert_eq!(fletcher, Fletcher32::new());
+        }
+    }
+}
@@ -0,0 +1,314 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Both CRCs are reflected, and their state is kept inverted between updates,
+// so it only needs to be inverted again when finalized.
+
+/// The reflected CRC-32 (IEEE 802.3) polynomial.
+const CRC32_POLY: u32 = 0xEDB8_8320;
+
+/// The reflected CRC-32C (Castagnoli) polynomial.
+const CRC32C_POLY: u32 = 0x82F6_3B78;
+
+/// Return the tables for slicing-by-8 with the reflected polynomial `poly`.
+/// The first table advances the CRC by one byte, and each table after it
+/// advances it by one more byte of zeroes.
+const fn slicing_tables(poly: u32) -> [[u32; 256]; 8] {
+    let mut tables = [[0u32; 256]; 8];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            bit += 1;
+        }
+        tables[0][i] = crc;
+        i += 1;
+    }
+    let mut t = 1;
+    while t < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = tables[t - 1][i];
+            tables[t][i] = (prev >> 8) ^ tables[0][(prev & 0xFF) as usize];
+            i += 1;
+        }
+        t += 1;
+    }
+    tables
+}
+
+static CRC32_TABLES: [[u32; 256]; 8] = slicing_tables(CRC32_POLY);
+static CRC32C_TABLES: [[u32; 256]; 8] = slicing_tables(CRC32C_POLY);
+
+#[inline(always)]
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Advance `crc` over `data` eight bytes at a time.
+fn update_slicing(crc: u32, data: &[u8], tables: &[[u32; 256]; 8]) -> u32 {
+    let mut crc = crc;
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        let lo = le_u32(&chunk[..4]) ^ crc;
+        let hi = le_u32(&chunk[4..]);
+        crc = tables[7][(lo & 0xFF) as usize]
+            ^ tables[6][(lo >> 8 & 0xFF) as usize]
+            ^ tables[5][(lo >> 16 & 0xFF) as usize]
+            ^ tables[4][(lo >> 24) as usize]
+            ^ tables[3][(hi & 0xFF) as usize]
+            ^ tables[2][(hi >> 8 & 0xFF) as usize]
+            ^ tables[1][(hi >> 16 & 0xFF) as usize]
+            ^ tables[0][(hi >> 24) as usize];
+    }
+    for &byte in chunks.remainder() {
+        crc = tables[0][((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+#[inline(always)]
+#[cfg(all(target_arch = "x86_64", target_feature = "sse4.2", not(feature = "force-polyfill")))]
+fn update_crc32c(crc: u32, data: &[u8]) -> u32 {
+    use crate::vektor::x86::_mm_crc32_u8;
+    use crate::vektor::x86_64::_mm_crc32_u64;
+    optimized!();
+    let mut chunks = data.chunks_exact(8);
+    let mut crc = crc as u64;
+    for chunk in &mut chunks {
+        let word = le_u32(&chunk[..4]) as u64 | (le_u32(&chunk[4..]) as u64) << 32;
+        crc = unsafe { _mm_crc32_u64(crc, word) };
+    }
+    let mut crc = crc as u32;
+    for &byte in chunks.remainder() {
+        crc = unsafe { _mm_crc32_u8(crc, byte) };
+    }
+    crc
+}
+
+#[inline(always)]
+#[cfg(any(not(all(target_arch = "x86_64", target_feature = "sse4.2")), feature = "force-polyfill"))]
+fn update_crc32c(crc: u32, data: &[u8]) -> u32 {
+    fallback!();
+    update_slicing(crc, data, &CRC32C_TABLES)
+}
+
+// Folding constants for the CRC-32 polynomial, from Intel's "Fast CRC
+// Computation for Generic Polynomials Using PCLMULQDQ Instruction".
+#[cfg(all(target_feature = "pclmulqdq", not(feature = "force-polyfill")))]
+mod fold {
+    pub const K1: u64 = 0x1_5444_2BD4;
+    pub const K2: u64 = 0x1_C6E4_1596;
+    pub const K3: u64 = 0x1_7519_97D0;
+    pub const K4: u64 = 0x0_CCAA_009E;
+    pub const K5: u64 = 0x1_63CD_6124;
+    pub const P_X: u64 = 0x1_DB71_0641;
+    pub const U_PRIME: u64 = 0x1_F701_1641;
+}
+
+#[inline(always)]
+#[cfg(all(target_feature = "pclmulqdq", not(feature = "force-polyfill")))]
+fn update_crc32(crc: u32, data: &[u8]) -> u32 {
+    use crate::arch::current::vecs::{u8x16, u64x2};
+    use crate::intrin::transmute::Transmute;
+    use crate::vecs::Packed;
+    use crate::vektor::x86::_mm_clmulepi64_si128;
+    use crate::core::mem::transmute;
+    use self::fold::*;
+
+    macro_rules! clmul {
+        ($a:expr, $b:expr, $imm:expr) => {
+            unsafe { transmute::<_, u64x2>(_mm_clmulepi64_si128(transmute($a), transmute($b), $imm)) }
+        }
+    }
+
+    // Multiply the low and high halves of `acc` by the distance they're
+    // being folded over, and add them to `next`
+    let fold = |acc: u64x2, next: u64x2, keys: u64x2| {
+        next ^ clmul!(acc, keys, 0x00) ^ clmul!(acc, keys, 0x11)
+    };
+
+    // Folding needs a few blocks of input to pay for itself
+    if data.len() < 128 {
+        fallback!();
+        return update_slicing(crc, data, &CRC32_TABLES);
+    }
+    optimized!();
+
+    let load = |i: usize| u8x16::load(data, i).be_u64s();
+    let mut acc = [load(0) ^ u64x2::new(crc as u64, 0), load(16), load(32), load(48)];
+    let mut i = 64;
+
+    // Fold four blocks at a time over 512 bits
+    let k1k2 = u64x2::new(K1, K2);
+    while i + 64 <= data.len() {
+        for (j, a) in acc.iter_mut().enumerate() {
+            *a = fold(*a, load(i + j * 16), k1k2);
+        }
+        i += 64;
+    }
+
+    // Fold the four blocks into one, then fold in the remaining blocks over
+    // 128 bits
+    let k3k4 = u64x2::new(K3, K4);
+    let mut x = fold(fold(fold(acc[0], acc[1], k3k4), acc[2], k3k4), acc[3], k3k4);
+    while i + 16 <= data.len() {
+        x = fold(x, load(i), k3k4);
+        i += 16;
+    }
+
+    // Reduce the 128-bit remainder to 64 bits, then to 32 bits
+    let low32 = u64x2::new(0xFFFF_FFFF, 0);
+    x = clmul!(x, k3k4, 0x10) ^ u64x2::new(x.extract(1), 0);
+    x = clmul!(x & low32, u64x2::new(K5, 0), 0x00)
+        ^ u64x2::new(x.extract(0) >> 32 | x.extract(1) << 32, x.extract(1) >> 32);
+
+    // Barrett reduction
+    let pu = u64x2::new(P_X, U_PRIME);
+    let t1 = clmul!(x & low32, pu, 0x10);
+    let t2 = clmul!(t1 & low32, pu, 0x00);
+    let crc = ((x ^ t2).extract(0) >> 32) as u32;
+    update_slicing(crc, &data[i..], &CRC32_TABLES)
+}
+
+#[inline(always)]
+#[cfg(any(not(target_feature = "pclmulqdq"), feature = "force-polyfill"))]
+fn update_crc32(crc: u32, data: &[u8]) -> u32 {
+    fallback!();
+    update_slicing(crc, data, &CRC32_TABLES)
+}
+
+macro_rules! impl_crc {
+    ($name:ident, $fn:ident, $update:ident, $desc:expr, $check:expr) => {
+        #[doc = "The state of a "]
+        #[doc = $desc]
+        #[doc = " checksum over a stream of bytes."]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct $name {
+            state: u32,
+        }
+
+        impl $name {
+            /// Return the state of a checksum over no bytes.
+            #[inline]
+            pub fn new() -> Self {
+                Self { state: !0 }
+            }
+
+            /// Add `data` to the end of the checksummed stream.
+            #[inline]
+            pub fn update(&mut self, data: &[u8]) {
+                self.state = $update(self.state, data);
+            }
+
+            /// Return the checksum of the stream.
+            #[inline]
+            pub fn finalize(self) -> u32 {
+                !self.state
+            }
+
+            /// Return to the state of a checksum over no bytes.
+            #[inline]
+            pub fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+
+        impl Default for $name {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        #[doc = "Return the "]
+        #[doc = $desc]
+        #[doc = " checksum of `data`."]
+        ///
+        /// ```
+        /// extern crate faster;
+        /// use faster::checksum::*;
+        ///
+        /// # fn main() {
+        #[doc = $check]
+        /// # }
+        /// ```
+        #[inline]
+        pub fn $fn(data: &[u8]) -> u32 {
+            let mut state = $name::new();
+            state.update(data);
+            state.finalize()
+        }
+    }
+}
+
+impl_crc!(Crc32, crc32, update_crc32, "CRC-32 (IEEE 802.3)",
+          "assert_eq!(crc32(b\"123456789\"), 0xCBF4_3926);");
+impl_crc!(Crc32c, crc32c, update_crc32c, "CRC-32C (Castagnoli)",
+          "assert_eq!(crc32c(b\"123456789\"), 0xE306_9283);");
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn data(len: usize) -> Vec<u8> {
+        testing::data(len).into_iter().map(|x| (x >> 56) as u8).collect()
+    }
+
+    /// Return the CRC of `data` with the reflected polynomial `poly`, one bit
+    /// at a time.
+    fn crc_reference(data: &[u8], poly: u32) -> u32 {
+        !data.iter().fold(!0u32, |crc, &byte| {
+            (0..8).fold(crc ^ byte as u32, |crc, _| {
+                if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 }
+            })
+        })
+    }
+
+    #[test]
+    fn known_answers() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+        assert_eq!(crc32c(b""), 0);
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+        // RFC 3720, appendix B.4
+        assert_eq!(crc32c(&[0; 32]), 0x8A91_36AA);
+        assert_eq!(crc32c(&[0xFF; 32]), 0x62A8_AB43);
+        assert_eq!(crc32c(&(0..32).collect::<Vec<u8>>()), 0x46DD_794E);
+    }
+
+    #[test]
+    fn matches_reference() {
+        let input = data(2000);
+        for len in (0..300).chain(1990..2000) {
+            assert_eq!(crc32(&input[..len]), crc_reference(&input[..len], CRC32_POLY), "{}", len);
+            assert_eq!(crc32c(&input[..len]), crc_reference(&input[..len], CRC32C_POLY), "{}", len);
+        }
+    }
+
+    #[test]
+    fn streaming_matches_oneshot() {
+        let input = data(1000);
+        for &split in [0, 1, 7, 64, 127, 128, 129, 500, 1000].iter() {
+            let (a, b) = input.split_at(split);
+            let mut crc = Crc32::new();
+            let mut crcc = Crc32c::default();
+            crc.update(a);
+            crc.update(b);
+            crcc.update(a);
+            crcc.update(b);
+            assert_eq!(crc.finalize(), crc32(&input));
+            assert_eq!(crcc.finalize(), crc32c(&input));
+            crc.reset();
+            assert_eq!(crc, Crc32::new());
+        }
+    }
+}
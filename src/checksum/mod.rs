@@ -0,0 +1,45 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Checksums over byte streams.
+//!
+//! Each checksum has a state type, which is fed data with `update` and
+//! produces the checksum with `finalize`, and a function which checksums a
+//! single slice. Feeding a state the same data in several pieces gives the
+//! same checksum as feeding it in one.
+//!
+//! | Checksum      | Accelerated with                   | Otherwise        |
+//! |---------------|------------------------------------|------------------|
+//! | [`Crc32`]     | `pclmulqdq` carry-less folding     | Slicing-by-8     |
+//! | [`Crc32c`]    | The SSE4.2 `crc32` instruction     | Slicing-by-8     |
+//! | [`Adler32`]   | Weighted sums of upcast `u8s`      | Always vectorized |
+//! | [`Fletcher32`]| Weighted sums of upcast `u16s`     | Always vectorized |
+//!
+//! ```
+//! extern crate faster;
+//! use faster::checksum::*;
+//!
+//! # fn main() {
+//! let mut crc = Crc32::new();
+//! crc.update(b"1234");
+//! crc.update(b"56789");
+//! assert_eq!(crc.finalize(), 0xCBF4_3926);
+//! assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+//! assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+//! # }
+//! ```
+//!
+//! [`Crc32`]: struct.Crc32.html
+//! [`Crc32c`]: struct.Crc32c.html
+//! [`Adler32`]: struct.Adler32.html
+//! [`Fletcher32`]: struct.Fletcher32.html
+
+mod adler;
+mod crc;
+
+pub use self::adler::{Adler32, Fletcher32, adler32, fletcher32};
+pub use self::crc::{Crc32, Crc32c, crc32, crc32c};
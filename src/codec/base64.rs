@@ -0,0 +1,458 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Base64 encoding and decoding, as specified by RFC 4648.
+//!
+//! Input is processed in 128-bit groups, which hold 12 bytes of decoded data
+//! or 16 bytes of encoded data. Each group is rearranged with [`Lookup`]
+//! shuffles and converted between bytes and six-bit symbols with shifts on
+//! its 32-bit lanes. Whatever doesn't fill a group is handled by a scalar
+//! implementation.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::codec::base64::*;
+//!
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() { }
+//!
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! assert_eq!(encode(b"faster", STANDARD), "ZmFzdGVy");
+//! assert_eq!(decode(b"ZmFzdGVy", STANDARD), Ok(b"faster".to_vec()));
+//! assert_eq!(decode(b"Zm$zdGVy", STANDARD), Err(DecodeError::InvalidByte(2, b'$')));
+//! # }
+//! ```
+//!
+//! [`Lookup`]: ../../intrin/lookup/trait.Lookup.html
+
+use crate::vecs::Packed;
+use crate::intrin::cmp::Cmp;
+use crate::intrin::eq::Eq as PackedEq;
+use crate::intrin::lookup::Lookup;
+use crate::intrin::saturating_sub::SaturatingSub;
+use crate::intrin::transmute::Transmute;
+use crate::arch::current::widths::w128;
+use crate::core::fmt;
+
+/// The set of symbols which encoded data is written with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    /// The standard alphabet, which uses `+` and `/`.
+    Standard,
+    /// The URL and filename safe alphabet, which uses `-` and `_`.
+    UrlSafe,
+}
+
+/// The alphabet and padding used to encode or decode data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The set of symbols which encoded data is written with.
+    pub alphabet: Alphabet,
+    /// Whether encoded data is padded with `=` to a multiple of four bytes.
+    /// When decoding, padding is required if this is set and rejected
+    /// otherwise.
+    pub padding: bool,
+}
+
+/// The standard alphabet, with padding.
+pub const STANDARD: Config = Config { alphabet: Alphabet::Standard, padding: true };
+
+/// The standard alphabet, without padding.
+pub const STANDARD_NO_PAD: Config = Config { alphabet: Alphabet::Standard, padding: false };
+
+/// The URL-safe alphabet, with padding.
+pub const URL_SAFE: Config = Config { alphabet: Alphabet::UrlSafe, padding: true };
+
+/// The URL-safe alphabet, without padding.
+pub const URL_SAFE_NO_PAD: Config = Config { alphabet: Alphabet::UrlSafe, padding: false };
+
+/// An error returned when decoding invalid data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The byte at the given offset is not part of the alphabet, or is
+    /// padding which isn't at the end of the input.
+    InvalidByte(usize, u8),
+    /// The input has a length which no encoded data can have.
+    InvalidLength(usize),
+    /// The last symbol, at the given offset, has bits set which aren't part of
+    /// the decoded data, so the input is not a canonical encoding.
+    InvalidLastSymbol(usize, u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidByte(offset, byte) =>
+                write!(f, "invalid byte {:#04x} at offset {}", byte, offset),
+            DecodeError::InvalidLength(len) =>
+                write!(f, "invalid encoded length {}", len),
+            DecodeError::InvalidLastSymbol(offset, byte) =>
+                write!(f, "non-canonical last symbol {:#04x} at offset {}", byte, offset),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError { }
+
+impl Alphabet {
+    /// Return the symbols for the values 62 and 63.
+    #[inline(always)]
+    fn specials(self) -> (u8, u8) {
+        match self {
+            Alphabet::Standard => (b'+', b'/'),
+            Alphabet::UrlSafe => (b'-', b'_'),
+        }
+    }
+
+    /// Return the symbol for the six-bit value `value`.
+    #[inline(always)]
+    fn encode(self, value: u8) -> u8 {
+        match value {
+            0..=25 => b'A' + value,
+            26..=51 => b'a' + value - 26,
+            52..=61 => b'0' + value - 52,
+            62 => self.specials().0,
+            _ => self.specials().1,
+        }
+    }
+
+    /// Return the six-bit value of the symbol `symbol`, if it is part of
+    /// this alphabet.
+    #[inline(always)]
+    fn decode(self, symbol: u8) -> Option<u8> {
+        let (s62, s63) = self.specials();
+        match symbol {
+            b'A'..=b'Z' => Some(symbol - b'A'),
+            b'a'..=b'z' => Some(symbol - b'a' + 26),
+            b'0'..=b'9' => Some(symbol - b'0' + 52),
+            _ if symbol == s62 => Some(62),
+            _ if symbol == s63 => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// Return the length of `len` bytes of data once encoded with `config`.
+#[inline]
+pub fn encoded_len(len: usize, config: Config) -> usize {
+    if config.padding {
+        (len + 2) / 3 * 4
+    } else {
+        len / 3 * 4 + [0, 2, 3][len % 3]
+    }
+}
+
+/// Return the maximum length of `len` bytes of encoded data once decoded.
+#[inline]
+pub fn decoded_len_estimate(len: usize) -> usize {
+    (len + 3) / 4 * 3
+}
+
+/// Encode twelve bytes at the beginning of `data` into sixteen symbols.
+#[inline(always)]
+fn encode_block(data: w128::u8s, alphabet: Alphabet) -> w128::u8s {
+    // Move each group of three bytes into a 32-bit lane, most significant
+    // byte first
+    let spread = w128::u8s::new(2, 1, 0, 0x80, 5, 4, 3, 0x80, 8, 7, 6, 0x80, 11, 10, 9, 0x80)
+        .lookup(data).be_u32s();
+
+    // Split each lane into four six-bit values, and store them in the order
+    // they will appear in the output
+    let mask = w128::u32s(0x3F);
+    let values = ((spread >> w128::u32s(18)) & mask)
+        | (((spread >> w128::u32s(12)) & mask) << w128::u32s(8))
+        | (((spread >> w128::u32s(6)) & mask) << w128::u32s(16))
+        | ((spread & mask) << w128::u32s(24));
+    let values = values.be_u8s();
+
+    // Sort the values into ranges which share an offset to their symbol:
+    // 0 for lowercase, 1-10 for digits, 11 and 12 for the specials, and 13
+    // for uppercase
+    let upper = values.min(w128::u8s(25)).eq_mask(values);
+    let range = values.saturating_sub(w128::u8s(51)) | (upper & w128::u8s(13));
+    let (add, sub) = match alphabet {
+        Alphabet::Standard =>
+            (w128::u8s::new(71, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 65, 0, 0),
+             w128::u8s::new(0, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 19, 16, 0, 0, 0)),
+        Alphabet::UrlSafe =>
+            (w128::u8s::new(71, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 32, 65, 0, 0),
+             w128::u8s::new(0, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 17, 0, 0, 0, 0)),
+    };
+    values + range.lookup(add) - range.lookup(sub)
+}
+
+/// Decode sixteen symbols into twelve bytes at the beginning of the returned
+/// vector, or return `None` if any symbol isn't part of `alphabet`.
+#[inline(always)]
+fn decode_block(symbols: w128::u8s, alphabet: Alphabet) -> Option<w128::u8s> {
+    let in_range = |lo, hi| symbols.max(w128::u8s(lo)).min(w128::u8s(hi)).eq_mask(symbols);
+    let (s62, s63) = alphabet.specials();
+    let upper = in_range(b'A', b'Z');
+    let lower = in_range(b'a', b'z');
+    let digit = in_range(b'0', b'9');
+    let is62 = symbols.eq_mask(w128::u8s(s62));
+    let is63 = symbols.eq_mask(w128::u8s(s63));
+    if upper | lower | digit | is62 | is63 != w128::u8s(0xFF) {
+        return None;
+    }
+
+    // Each symbol is in exactly one range, so we can mask away the others
+    let values = (symbols.saturating_sub(w128::u8s(b'A')) & upper)
+        | (symbols.saturating_sub(w128::u8s(b'a' - 26)) & lower)
+        | ((symbols.saturating_sub(w128::u8s(b'0')) & digit) + (w128::u8s(52) & digit))
+        | (w128::u8s(62) & is62)
+        | (w128::u8s(63) & is63);
+
+    // Join each four six-bit values into a 24-bit lane, and move the bytes
+    // of each lane together, most significant byte first
+    let values = values.be_u32s();
+    let mask = w128::u32s(0x3F);
+    let joined = ((values & mask) << w128::u32s(18))
+        | (((values >> w128::u32s(8)) & mask) << w128::u32s(12))
+        | (((values >> w128::u32s(16)) & mask) << w128::u32s(6))
+        | (values >> w128::u32s(24));
+    Some(w128::u8s::new(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, 0x80, 0x80, 0x80, 0x80)
+         .lookup(joined.be_u8s()))
+}
+
+/// Encode `data` into `out` one byte at a time, returning the number of
+/// bytes written.
+fn encode_scalar(data: &[u8], out: &mut [u8], config: Config) -> usize {
+    let alphabet = config.alphabet;
+    let mut o = 0;
+    for chunk in data.chunks(3) {
+        let n = chunk.iter().enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        let symbols = chunk.len() + 1;
+        for i in 0..symbols {
+            out[o + i] = alphabet.encode(((n >> (18 - 6 * i)) & 0x3F) as u8);
+        }
+        o += symbols;
+        if config.padding {
+            for _ in symbols..4 {
+                out[o] = b'=';
+                o += 1;
+            }
+        }
+    }
+    o
+}
+
+/// Decode the unpadded symbols in `data` into `out` one symbol at a time,
+/// returning the number of bytes written. `offset` is the position of `data`
+/// in the original input.
+fn decode_scalar(data: &[u8], out: &mut [u8], offset: usize, alphabet: Alphabet) -> Result<usize, DecodeError> {
+    let mut o = 0;
+    for (c, chunk) in data.chunks(4).enumerate() {
+        let mut n = 0u32;
+        for (i, &symbol) in chunk.iter().enumerate() {
+            let value = alphabet.decode(symbol)
+                .ok_or(DecodeError::InvalidByte(offset + c * 4 + i, symbol))?;
+            n |= (value as u32) << (18 - 6 * i);
+        }
+
+        // A lone symbol can't encode a full byte, and callers should have
+        // rejected it already
+        let bytes = chunk.len() - 1;
+        debug_assert!(bytes > 0);
+        if n & (0xFF_FFFF >> (8 * bytes)) != 0 {
+            let last = chunk.len() - 1;
+            return Err(DecodeError::InvalidLastSymbol(offset + c * 4 + last, chunk[last]));
+        }
+        for i in 0..bytes {
+            out[o + i] = (n >> (16 - 8 * i)) as u8;
+        }
+        o += bytes;
+    }
+    Ok(o)
+}
+
+/// Encode `data` into `out` with `config`, returning the number of bytes
+/// written.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `encoded_len(data.len(), config)`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::base64::*;
+///
+/// # fn main() {
+/// let mut out = [0u8; 8];
+/// let len = encode_to_slice(&[0xFB, 0xFF], &mut out, URL_SAFE_NO_PAD);
+/// assert_eq!(&out[..len], b"-_8");
+/// # }
+/// ```
+pub fn encode_to_slice(data: &[u8], out: &mut [u8], config: Config) -> usize {
+    let len = encoded_len(data.len(), config);
+    assert!(out.len() >= len, "The output buffer is too small to hold the encoded data.");
+
+    // Every group loads 16 bytes but only consumes 12 of them
+    let (mut i, mut o) = (0, 0);
+    while i + 16 <= data.len() {
+        encode_block(w128::u8s::load(data, i), config.alphabet).store(out, o);
+        i += 12;
+        o += 16;
+    }
+    o + encode_scalar(&data[i..], &mut out[o..len], config)
+}
+
+/// Decode `data` into `out` with `config`, returning the number of bytes
+/// written.
+///
+/// # Panics
+///
+/// Panics if `out` is too short to hold the decoded data, which is never
+/// longer than `decoded_len_estimate(data.len())`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::base64::*;
+///
+/// # fn main() {
+/// let mut out = [0u8; 8];
+/// assert_eq!(decode_to_slice(b"-_8", &mut out, URL_SAFE_NO_PAD), Ok(2));
+/// assert_eq!(&out[..2], &[0xFB, 0xFF]);
+/// assert_eq!(decode_to_slice(b"-_8", &mut out, URL_SAFE), Err(DecodeError::InvalidLength(3)));
+/// # }
+/// ```
+pub fn decode_to_slice(data: &[u8], out: &mut [u8], config: Config) -> Result<usize, DecodeError> {
+    let data = if config.padding {
+        if data.len() % 4 != 0 {
+            return Err(DecodeError::InvalidLength(data.len()));
+        }
+        let pad = data.iter().rev().take(2).take_while(|&&b| b == b'=').count();
+        &data[..data.len() - pad]
+    } else {
+        data
+    };
+    if data.len() % 4 == 1 {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+    let len = data.len() / 4 * 3 + [0, 0, 1, 2][data.len() % 4];
+    assert!(out.len() >= len, "The output buffer is too small to hold the decoded data.");
+
+    // Every group stores 16 bytes but only produces 12 of them
+    let (mut i, mut o) = (0, 0);
+    while i + 16 <= data.len() && o + 16 <= len {
+        match decode_block(w128::u8s::load(data, i), config.alphabet) {
+            Some(bytes) => bytes.store(out, o),
+            // Let the scalar decoder find the offending symbol
+            None => break,
+        }
+        i += 16;
+        o += 12;
+    }
+    Ok(o + decode_scalar(&data[i..], &mut out[o..len], i, config.alphabet)?)
+}
+
+/// Return `data` encoded with `config`.
+#[cfg(feature = "std")]
+pub fn encode(data: &[u8], config: Config) -> String {
+    let mut out = vec![0; encoded_len(data.len(), config)];
+    encode_to_slice(data, &mut out, config);
+    // Every symbol is ASCII
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Return `data` decoded with `config`, or the first problem with `data` if
+/// it is not valid.
+#[cfg(feature = "std")]
+pub fn decode(data: &[u8], config: Config) -> Result<Vec<u8>, DecodeError> {
+    let mut out = vec![0; decoded_len_estimate(data.len())];
+    let len = decode_to_slice(data, &mut out, config)?;
+    out.truncate(len);
+    Ok(out)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    const CONFIGS: [Config; 4] = [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD];
+
+    fn data(len: usize) -> Vec<u8> {
+        testing::data(len).into_iter().map(|x| (x >> 56) as u8).collect()
+    }
+
+    fn encode_reference(data: &[u8], config: Config) -> String {
+        let mut out = vec![0; encoded_len(data.len(), config)];
+        let len = encode_scalar(data, &mut out, config);
+        assert_eq!(len, out.len());
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn rfc4648_vectors() {
+        let vectors = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"),
+                       ("foob", "Zm9vYg=="), ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")];
+        for &(plain, encoded) in vectors.iter() {
+            assert_eq!(encode(plain.as_bytes(), STANDARD), encoded);
+            assert_eq!(decode(encoded.as_bytes(), STANDARD).unwrap(), plain.as_bytes());
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(encode(plain.as_bytes(), STANDARD_NO_PAD), unpadded);
+            assert_eq!(decode(unpadded.as_bytes(), STANDARD_NO_PAD).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn round_trip_matches_reference() {
+        for len in 0..300 {
+            let plain = data(len);
+            for &config in CONFIGS.iter() {
+                let encoded = encode(&plain, config);
+                assert_eq!(encoded, encode_reference(&plain, config), "{} {:?}", len, config);
+                assert_eq!(decode(encoded.as_bytes(), config).unwrap(), plain);
+            }
+        }
+    }
+
+    #[test]
+    fn every_symbol() {
+        // Every six-bit value, in order
+        let plain: Vec<u8> = (0..48).map(|i| {
+            let n = (i / 3 * 4) as u32;
+            let n = n << 18 | (n + 1) << 12 | (n + 2) << 6 | (n + 3);
+            (n >> (16 - 8 * (i % 3))) as u8
+        }).collect();
+        assert_eq!(encode(&plain, STANDARD),
+                   "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+        assert_eq!(encode(&plain, URL_SAFE),
+                   "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+    }
+
+    #[test]
+    fn invalid_bytes_are_located() {
+        let encoded = encode(&data(150), STANDARD_NO_PAD).into_bytes();
+        for &bad in [b'=', b'-', b'_', b' ', b'\n', 0x80, 0xFF, b'@', b'[', b'`', b'{', b'/' - 1, b':'].iter() {
+            for pos in 0..encoded.len() {
+                let mut corrupt = encoded.clone();
+                corrupt[pos] = bad;
+                assert_eq!(decode(&corrupt, STANDARD_NO_PAD), Err(DecodeError::InvalidByte(pos, bad)));
+            }
+        }
+        assert_eq!(decode(b"Zm9v+/==", URL_SAFE), Err(DecodeError::InvalidByte(4, b'+')));
+        assert_eq!(decode(b"Zm-_Zm9v", STANDARD), Err(DecodeError::InvalidByte(2, b'-')));
+    }
+
+    #[test]
+    fn invalid_lengths_and_padding() {
+        assert_eq!(decode(b"Zm9vY", STANDARD_NO_PAD), Err(DecodeError::InvalidLength(5)));
+        assert_eq!(decode(b"Zm9vYg=", STANDARD), Err(DecodeError::InvalidLength(7)));
+        assert_eq!(decode(b"Zm9vYg", STANDARD), Err(DecodeError::InvalidLength(6)));
+        assert_eq!(decode(b"Zm9vY===", STANDARD), Err(DecodeError::InvalidByte(5, b'=')));
+        assert_eq!(decode(b"Zm9vYg==", STANDARD_NO_PAD), Err(DecodeError::InvalidByte(6, b'=')));
+        assert_eq!(decode(b"Zm=v", STANDARD), Err(DecodeError::InvalidByte(2, b'=')));
+        assert_eq!(decode(b"Zh==", STANDARD), Err(DecodeError::InvalidLastSymbol(1, b'h')));
+        assert_eq!(decode(b"Zm9=", STANDARD), Err(DecodeError::InvalidLastSymbol(2, b'9')));
+        assert_eq!(decode(b"Zm8=", STANDARD), Ok(b"fo".to_vec()));
+    }
+}
@@ -0,0 +1,319 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Hexadecimal encoding and decoding.
+//!
+//! Each byte is split into its two nibbles, which are converted to digits
+//! with comparison masks and interleaved by upcasting them to `u16s`. Decoding
+//! validates a vector of digits at a time, and joins the nibbles back together
+//! before downcasting them to `u8s`. Whatever doesn't fill a vector is handled
+//! by a scalar implementation.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::codec::hex::*;
+//!
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() { }
+//!
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! assert_eq!(encode(&[0xFA, 0x57, 0xE5]), "fa57e5");
+//! assert_eq!(encode_upper(&[0xFA, 0x57, 0xE5]), "FA57E5");
+//! assert_eq!(decode(b"Fa57e5"), Ok(vec![0xFA, 0x57, 0xE5]));
+//! assert_eq!(decode(b"fa57g5"), Err(DecodeError::InvalidByte(4, b'g')));
+//! # }
+//! ```
+
+use crate::vecs::Packed;
+use crate::arch::current::vecs::{u8s, u16s};
+use crate::intrin::cmp::Cmp;
+use crate::intrin::downcast::Downcast;
+use crate::intrin::eq::Eq as PackedEq;
+use crate::intrin::merge::Merge;
+use crate::intrin::saturating_sub::SaturatingSub;
+use crate::intrin::transmute::Transmute;
+use crate::intrin::upcast::Upcast;
+use crate::core::fmt;
+
+/// An error returned when decoding invalid data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The byte at the given offset is not a hexadecimal digit.
+    InvalidByte(usize, u8),
+    /// The input has an odd length, which is given.
+    InvalidLength(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidByte(offset, byte) =>
+                write!(f, "invalid byte {:#04x} at offset {}", byte, offset),
+            DecodeError::InvalidLength(len) =>
+                write!(f, "invalid encoded length {}", len),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError { }
+
+/// Return the digit for the nibble `nibble`, where `letter` is the digit for
+/// ten.
+#[inline(always)]
+fn encode_nibble(nibble: u8, letter: u8) -> u8 {
+    if nibble < 10 { b'0' + nibble } else { letter + nibble - 10 }
+}
+
+/// Return the value of the hexadecimal digit `digit`, in either case.
+#[inline(always)]
+fn decode_nibble(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Return the digits for each nibble in `nibbles`, where `letter` is the
+/// digit for ten.
+#[inline(always)]
+fn encode_nibbles(nibbles: u8s, letter: u8) -> u8s {
+    let is_letter = nibbles.max(u8s(10)).eq_mask(nibbles);
+    nibbles + u8s(b'0') + (is_letter & u8s(letter - b'0' - 10))
+}
+
+/// Return the value of each hexadecimal digit in `digits`, or `None` if any
+/// of them isn't a digit.
+#[inline(always)]
+fn decode_nibbles(digits: u8s) -> Option<u8s> {
+    let in_range = |lo, hi| digits.max(u8s(lo)).min(u8s(hi)).eq_mask(digits);
+    let digit = in_range(b'0', b'9');
+    let lower = in_range(b'a', b'f');
+    let upper = in_range(b'A', b'F');
+    if digit | lower | upper != u8s(0xFF) {
+        return None;
+    }
+
+    // Each digit is in exactly one range, so we can mask away the others
+    Some((digits.saturating_sub(u8s(b'0')) & digit)
+         | (digits.saturating_sub(u8s(b'a' - 10)) & lower)
+         | (digits.saturating_sub(u8s(b'A' - 10)) & upper))
+}
+
+/// Return the high and low nibbles of each two-digit pair in `pairs`, joined
+/// into a byte and widened to sixteen bits.
+#[inline(always)]
+fn join_nibbles(pairs: u8s) -> u16s {
+    let pairs = pairs.be_u16s();
+    ((pairs & u16s(0xFF)) << u16s(4)) | (pairs >> u16s(8))
+}
+
+fn encode_with<'a>(data: &[u8], fill: &'a mut [u8], letter: u8) -> &'a mut [u8] {
+    let len = data.len() * 2;
+    assert!(fill.len() >= len, "The output buffer is too small to hold the encoded data.");
+
+    let mut i = 0;
+    while i + u8s::WIDTH <= data.len() {
+        let bytes = u8s::load(data, i);
+        let hi = encode_nibbles(bytes >> u8s(4), letter);
+        let lo = encode_nibbles(bytes & u8s(0x0F), letter);
+
+        // Widening puts each digit in the low byte of a u16, so the high
+        // digits stay where they are and the low digits shift into the high
+        // byte; interleaving the bytes then gives the digits in order.
+        let (hi0, hi1): (u16s, u16s) = hi.upcast();
+        let (lo0, lo1): (u16s, u16s) = lo.upcast();
+        hi0.be_u8s().merge_interleaved((lo0 << u16s(8)).be_u8s()).store(fill, i * 2);
+        hi1.be_u8s().merge_interleaved((lo1 << u16s(8)).be_u8s()).store(fill, i * 2 + u8s::WIDTH);
+        i += u8s::WIDTH;
+    }
+
+    for (&byte, digits) in data[i..].iter().zip(fill[i * 2..len].chunks_mut(2)) {
+        digits[0] = encode_nibble(byte >> 4, letter);
+        digits[1] = encode_nibble(byte & 0x0F, letter);
+    }
+    &mut fill[..len]
+}
+
+/// Encode `data` into `fill` with lowercase digits, returning the part of
+/// `fill` which was written to.
+///
+/// # Panics
+///
+/// Panics if `fill` is shorter than twice the length of `data`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::hex::*;
+///
+/// # fn main() {
+/// let mut buf = [0u8; 8];
+/// assert_eq!(encode_fill(&[0xC0, 0xDE], &mut buf), b"c0de");
+/// # }
+/// ```
+pub fn encode_fill<'a>(data: &[u8], fill: &'a mut [u8]) -> &'a mut [u8] {
+    encode_with(data, fill, b'a')
+}
+
+/// Encode `data` into `fill` with uppercase digits, returning the part of
+/// `fill` which was written to.
+///
+/// # Panics
+///
+/// Panics if `fill` is shorter than twice the length of `data`.
+pub fn encode_upper_fill<'a>(data: &[u8], fill: &'a mut [u8]) -> &'a mut [u8] {
+    encode_with(data, fill, b'A')
+}
+
+/// Decode the digits in `data`, which may be of either case, into `fill`,
+/// returning the part of `fill` which was written to.
+///
+/// # Panics
+///
+/// Panics if `fill` is shorter than half the length of `data`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::codec::hex::*;
+///
+/// # fn main() {
+/// let mut buf = [0u8; 4];
+/// assert_eq!(decode_fill(b"C0de", &mut buf), Ok(&mut [0xC0, 0xDE][..]));
+/// assert_eq!(decode_fill(b"C0d", &mut buf), Err(DecodeError::InvalidLength(3)));
+/// # }
+/// ```
+pub fn decode_fill<'a>(data: &[u8], fill: &'a mut [u8]) -> Result<&'a mut [u8], DecodeError> {
+    if data.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength(data.len()));
+    }
+    let len = data.len() / 2;
+    assert!(fill.len() >= len, "The output buffer is too small to hold the decoded data.");
+
+    let mut o = 0;
+    while (o + u8s::WIDTH) * 2 <= data.len() {
+        let first = decode_nibbles(u8s::load(data, o * 2));
+        let second = decode_nibbles(u8s::load(data, o * 2 + u8s::WIDTH));
+        match (first, second) {
+            (Some(first), Some(second)) => {
+                let bytes: u8s = join_nibbles(first).saturating_downcast(join_nibbles(second));
+                bytes.store(fill, o);
+            },
+            // Let the scalar decoder find the offending digit
+            _ => break,
+        }
+        o += u8s::WIDTH;
+    }
+
+    for (i, digits) in data[o * 2..].chunks(2).enumerate() {
+        let pos = (o + i) * 2;
+        let hi = decode_nibble(digits[0]).ok_or(DecodeError::InvalidByte(pos, digits[0]))?;
+        let lo = decode_nibble(digits[1]).ok_or(DecodeError::InvalidByte(pos + 1, digits[1]))?;
+        fill[o + i] = hi << 4 | lo;
+    }
+    Ok(&mut fill[..len])
+}
+
+/// Return `data` encoded with lowercase digits.
+#[cfg(feature = "std")]
+pub fn encode(data: &[u8]) -> String {
+    let mut out = vec![0; data.len() * 2];
+    encode_fill(data, &mut out);
+    // Every digit is ASCII
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Return `data` encoded with uppercase digits.
+#[cfg(feature = "std")]
+pub fn encode_upper(data: &[u8]) -> String {
+    let mut out = vec![0; data.len() * 2];
+    encode_upper_fill(data, &mut out);
+    // Every digit is ASCII
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Return the digits in `data`, which may be of either case, decoded into
+/// bytes, or the position of the first invalid digit.
+#[cfg(feature = "std")]
+pub fn decode(data: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut out = vec![0; data.len() / 2];
+    decode_fill(data, &mut out)?;
+    Ok(out)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use std::fmt::Write;
+
+    fn data(len: usize) -> Vec<u8> {
+        testing::data(len).into_iter().map(|x| (x >> 56) as u8).collect()
+    }
+
+    fn encode_reference(data: &[u8], upper: bool) -> String {
+        data.iter().fold(String::new(), |mut s, b| {
+            let res = if upper { write!(s, "{:02X}", b) } else { write!(s, "{:02x}", b) };
+            res.unwrap();
+            s
+        })
+    }
+
+    #[test]
+    fn every_byte() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(encode(&bytes), encode_reference(&bytes, false));
+        assert_eq!(encode_upper(&bytes), encode_reference(&bytes, true));
+        assert_eq!(decode(encode(&bytes).as_bytes()).unwrap(), bytes);
+        assert_eq!(decode(encode_upper(&bytes).as_bytes()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn round_trip_matches_reference() {
+        for len in 0..300 {
+            let plain = data(len);
+            let lower = encode(&plain);
+            let upper = encode_upper(&plain);
+            assert_eq!(lower, encode_reference(&plain, false));
+            assert_eq!(upper, encode_reference(&plain, true));
+            assert_eq!(decode(lower.as_bytes()).unwrap(), plain);
+            assert_eq!(decode(upper.as_bytes()).unwrap(), plain);
+        }
+    }
+
+    #[test]
+    fn first_invalid_byte_is_located() {
+        let encoded = encode(&data(150)).into_bytes();
+        for &bad in [b'g', b'G', b'/', b':', b'@', b'`', b' ', 0x80, 0xFF, 0].iter() {
+            for pos in 0..encoded.len() {
+                let mut corrupt = encoded.clone();
+                corrupt[pos] = bad;
+                assert_eq!(decode(&corrupt), Err(DecodeError::InvalidByte(pos, bad)));
+                // Only the first invalid digit is reported
+                if pos + 1 < encoded.len() {
+                    corrupt[encoded.len() - 1] = b'x';
+                    assert_eq!(decode(&corrupt), Err(DecodeError::InvalidByte(pos, bad)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert_eq!(decode(b"abc"), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(decode(b"a"), Err(DecodeError::InvalidLength(1)));
+        assert_eq!(decode(b""), Ok(vec![]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fill_too_small() {
+        encode_fill(&[1, 2, 3], &mut [0u8; 5]);
+    }
+}
@@ -0,0 +1,458 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Vectors of complex numbers.
+//!
+//! Complex vectors are stored interleaved, such that the lanes of the
+//! underlying vector read `[re, im, re, im...]`. This is the same layout as a
+//! slice of [`Complex`], so data can be loaded into and stored from complex
+//! vectors without any shuffling.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//!
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() { }
+//!
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! let signal = [Complex::new(1.0f32, 2.0); 100];
+//! let rotated = signal.simd_iter(c32s(Complex::new(0.0, 0.0)))
+//!     .simd_map(|v| v * c32s(Complex::new(0.0, 1.0)))
+//!     .scalar_collect();
+//! assert_eq!(rotated, vec![Complex::new(-2.0, 1.0); 100]);
+//! # }
+//! ```
+//!
+//! [`Complex`]: struct.Complex.html
+
+use crate::vecs::{Packable, Packed};
+use crate::vec_patterns::Pattern;
+use crate::intrin::addsub::AddSub;
+use crate::intrin::merge::Merge;
+use crate::intrin::sqrt::Sqrt;
+use crate::intrin::swizzle::Swizzle;
+use crate::arch::current::vecs::{f32s, f64s};
+use crate::core::ops::*;
+use crate::core::slice::{from_raw_parts, from_raw_parts_mut};
+
+/// A complex number, laid out as its real part followed by its imaginary part.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    #[inline(always)]
+    pub fn new(re: T, im: T) -> Self {
+        Complex { re: re, im: im }
+    }
+}
+
+/// A SIMD vector of complex numbers, stored interleaved in the lanes of `V`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PackedComplex<V>(pub V);
+
+/// A SIMD vector of complex `f32`s.
+#[allow(non_camel_case_types)]
+pub type c32s = PackedComplex<f32s>;
+
+/// A SIMD vector of complex `f64`s.
+#[allow(non_camel_case_types)]
+pub type c64s = PackedComplex<f64s>;
+
+/// Return a vector of complex `f32`s with all elements initialized to `data`.
+#[inline(always)]
+pub fn c32s(data: Complex<f32>) -> c32s {
+    c32s::splat(data)
+}
+
+/// Return a vector of complex `f64`s with all elements initialized to `data`.
+#[inline(always)]
+pub fn c64s(data: Complex<f64>) -> c64s {
+    c64s::splat(data)
+}
+
+impl Packable for Complex<f32> {
+    type Vector = c32s;
+    const SIZE: usize = 8;
+}
+
+impl Packable for Complex<f64> {
+    type Vector = c64s;
+    const SIZE: usize = 16;
+}
+
+/// Reinterpret a slice of interleaved real and imaginary parts as a slice of
+/// complex numbers. Panics if `data` has an odd length.
+#[inline(always)]
+pub fn as_complex<T>(data: &[T]) -> &[Complex<T>] {
+    assert!(data.len() % 2 == 0);
+    unsafe { from_raw_parts(data.as_ptr() as *const Complex<T>, data.len() / 2) }
+}
+
+/// Reinterpret a mutable slice of interleaved real and imaginary parts as a
+/// mutable slice of complex numbers. Panics if `data` has an odd length.
+#[inline(always)]
+pub fn as_complex_mut<T>(data: &mut [T]) -> &mut [Complex<T>] {
+    assert!(data.len() % 2 == 0);
+    unsafe { from_raw_parts_mut(data.as_mut_ptr() as *mut Complex<T>, data.len() / 2) }
+}
+
+#[inline(always)]
+fn flatten<T>(data: &[Complex<T>]) -> &[T] {
+    unsafe { from_raw_parts(data.as_ptr() as *const T, data.len() * 2) }
+}
+
+#[inline(always)]
+fn flatten_mut<T>(data: &mut [Complex<T>]) -> &mut [T] {
+    unsafe { from_raw_parts_mut(data.as_mut_ptr() as *mut T, data.len() * 2) }
+}
+
+impl<V, S> Merge for PackedComplex<V>
+    where V : Packed<Scalar = S>, S : Packable + PartialEq + Mul<Output = S> + Add<Output = S> + Sub<Output = S>,
+          Complex<S> : Packable {
+    #[inline(always)]
+    fn merge_halves(&self, other: Self) -> Self {
+        let mut ret = *self;
+        for i in (Self::WIDTH / 2)..Self::WIDTH {
+            unsafe { ret = ret.replace_unchecked(i, other.extract_unchecked(i)); }
+        }
+        ret
+    }
+
+    #[inline(always)]
+    fn merge_interleaved(&self, other: Self) -> Self {
+        let mut ret = *self;
+        for i in (1..Self::WIDTH).step_by(2) {
+            unsafe { ret = ret.replace_unchecked(i, other.extract_unchecked(i)); }
+        }
+        ret
+    }
+
+    #[inline(always)]
+    fn merge_partitioned(&self, other: Self, offset: usize) -> Self {
+        assert!(offset < Self::WIDTH);
+        PackedComplex(self.0.merge_partitioned(other.0, offset * 2))
+    }
+}
+
+impl<V, S> Packed for PackedComplex<V>
+    where V : Packed<Scalar = S>, S : Packable + PartialEq + Mul<Output = S> + Add<Output = S> + Sub<Output = S>,
+          Complex<S> : Packable {
+    type Scalar = Complex<S>;
+
+    const WIDTH: usize = V::WIDTH / 2;
+
+    #[inline(always)]
+    fn load(data: &[Complex<S>], offset: usize) -> Self {
+        PackedComplex(V::load(flatten(data), offset * 2))
+    }
+
+    #[inline(always)]
+    unsafe fn load_unchecked(data: &[Complex<S>], offset: usize) -> Self {
+        PackedComplex(V::load_unchecked(flatten(data), offset * 2))
+    }
+
+    #[inline(always)]
+    fn store(self, data: &mut [Complex<S>], offset: usize) {
+        self.0.store(flatten_mut(data), offset * 2)
+    }
+
+    #[inline(always)]
+    unsafe fn store_unchecked(self, data: &mut [Complex<S>], offset: usize) {
+        self.0.store_unchecked(flatten_mut(data), offset * 2)
+    }
+
+    #[inline(always)]
+    fn coalesce(self) -> Self::Scalar {
+        for i in 1..Self::WIDTH {
+            debug_assert!(self.extract(i - 1) == self.extract(i));
+        }
+        self.extract(0)
+    }
+
+    #[inline(always)]
+    fn splat(data: Self::Scalar) -> Self {
+        let mut ret = V::default();
+        for i in 0..Self::WIDTH {
+            unsafe {
+                ret = ret.replace_unchecked(i * 2, data.re);
+                ret = ret.replace_unchecked(i * 2 + 1, data.im);
+            }
+        }
+        PackedComplex(ret)
+    }
+
+    #[inline(always)]
+    fn default() -> Self {
+        PackedComplex(V::default())
+    }
+
+    #[inline(always)]
+    fn extract(&self, idx: usize) -> Self::Scalar {
+        Complex::new(self.0.extract(idx * 2), self.0.extract(idx * 2 + 1))
+    }
+
+    #[inline(always)]
+    unsafe fn extract_unchecked(&self, idx: usize) -> Self::Scalar {
+        debug_assert!(idx < Self::WIDTH);
+        Complex::new(self.0.extract_unchecked(idx * 2), self.0.extract_unchecked(idx * 2 + 1))
+    }
+
+    #[inline(always)]
+    fn replace(&mut self, idx: usize, data: Self::Scalar) -> Self {
+        assert!(idx < Self::WIDTH);
+        unsafe { self.replace_unchecked(idx, data) }
+    }
+
+    #[inline(always)]
+    unsafe fn replace_unchecked(&mut self, idx: usize, data: Self::Scalar) -> Self {
+        debug_assert!(idx < Self::WIDTH);
+        let mut ret = self.0.replace_unchecked(idx * 2, data.re);
+        PackedComplex(ret.replace_unchecked(idx * 2 + 1, data.im))
+    }
+
+    #[inline(always)]
+    fn product(&self) -> Self::Scalar {
+        let mut acc = self.extract(0);
+        for i in 1..Self::WIDTH {
+            acc = acc * self.extract(i);
+        }
+        acc
+    }
+
+    #[inline(always)]
+    fn scalar_reduce<T, F>(&self, mut acc: T, mut func: F) -> T
+    where F: FnMut(T, Self::Scalar) -> T {
+        for i in 0..Self::WIDTH {
+            acc = func(acc, self.extract(i))
+        }
+        acc
+    }
+}
+
+impl<T> Add for Complex<T> where T : Add<Output = T> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl<T> Sub for Complex<T> where T : Sub<Output = T> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, other: Self) -> Self {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl<T> Mul for Complex<T> where T : Copy + Mul<Output = T> + Add<Output = T> + Sub<Output = T> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, other: Self) -> Self {
+        Complex::new(self.re * other.re - self.im * other.im,
+                     self.re * other.im + self.im * other.re)
+    }
+}
+
+impl<T> Neg for Complex<T> where T : Neg<Output = T> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<V> Add for PackedComplex<V> where V : Add<Output = V> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        PackedComplex(self.0 + other.0)
+    }
+}
+
+impl<V> Sub for PackedComplex<V> where V : Sub<Output = V> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, other: Self) -> Self {
+        PackedComplex(self.0 - other.0)
+    }
+}
+
+impl<V> Neg for PackedComplex<V> where V : Neg<Output = V> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        PackedComplex(-self.0)
+    }
+}
+
+impl<V> Mul for PackedComplex<V> where V : Packed + Mul<Output = V> + AddSub + Swizzle + Merge {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, other: Self) -> Self {
+        // (a + bi)(c + di) = (ac - bd) + (ad + bc)i, so we multiply
+        // [a, a] by [c, d] and [b, b] by [d, c], then subtract the even lanes
+        // and add the odd lanes.
+        let flipped = self.0.flip();
+        let re = self.0.merge_interleaved(flipped);
+        let im = flipped.merge_interleaved(self.0);
+        PackedComplex((re * other.0).addsub(im * other.0.flip()))
+    }
+}
+
+impl<V> AddAssign for PackedComplex<V> where Self : Copy + Add<Output = Self> {
+    #[inline(always)]
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<V> SubAssign for PackedComplex<V> where Self : Copy + Sub<Output = Self> {
+    #[inline(always)]
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<V> MulAssign for PackedComplex<V> where Self : Copy + Mul<Output = Self> {
+    #[inline(always)]
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<V, S> PackedComplex<V>
+    where V : Pattern<Scalar = S> + Mul<Output = V> + Add<Output = V> + Swizzle, S : From<i8> {
+    /// Return the complex conjugate of every element of this vector.
+    #[inline(always)]
+    pub fn conj(&self) -> Self {
+        PackedComplex(self.0 * V::interleave(S::from(1), S::from(-1)))
+    }
+
+    /// Return the squared magnitude of every element of this vector. Each
+    /// magnitude is present in both the real and imaginary lane of its
+    /// element, such that the returned vector may divide or multiply `self.0`
+    /// directly.
+    #[inline(always)]
+    pub fn norm_sqr(&self) -> V {
+        let sq = self.0 * self.0;
+        sq + sq.flip()
+    }
+
+    /// Return the magnitude of every element of this vector, laid out as in
+    /// `norm_sqr`.
+    #[inline(always)]
+    pub fn abs(&self) -> V where V : Sqrt {
+        self.norm_sqr().sqrt()
+    }
+}
+
+macro_rules! impl_complex_real_ops {
+    ($($el:tt),*) => {
+        $(
+            impl<V> Mul<$el> for PackedComplex<V> where V : Packed<Scalar = $el> + Mul<Output = V> {
+                type Output = Self;
+
+                /// Multiply the real and imaginary parts of every element of
+                /// this vector by `other`.
+                #[inline(always)]
+                fn mul(self, other: $el) -> Self {
+                    PackedComplex(self.0 * V::splat(other))
+                }
+            }
+
+            impl Complex<$el> {
+                /// Return the complex conjugate of this number.
+                #[inline(always)]
+                pub fn conj(&self) -> Self {
+                    Complex::new(self.re, -self.im)
+                }
+
+                /// Return the squared magnitude of this number.
+                #[inline(always)]
+                pub fn norm_sqr(&self) -> $el {
+                    self.re * self.re + self.im * self.im
+                }
+
+                /// Return the magnitude of this number.
+                #[cfg(feature = "std")]
+                #[inline(always)]
+                pub fn abs(&self) -> $el {
+                    self.norm_sqr().sqrt()
+                }
+            }
+        )*
+    }
+}
+
+impl_complex_real_ops!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use super::*;
+
+    macro_rules! test_complex {
+        ($cvec:tt, $el:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                let mut a = $cvec::default();
+                let mut b = $cvec::default();
+                for i in 0..$cvec::WIDTH {
+                    a = a.replace(i, Complex::new(i as $el + 1.0, 2.0 - i as $el));
+                    b = b.replace(i, Complex::new(3.0 * i as $el, i as $el - 4.0));
+                }
+
+                let (sum, diff, prod, conj, scaled) = (a + b, a - b, a * b, a.conj(), a * (2.0 as $el));
+                let norm = a.norm_sqr();
+                for i in 0..$cvec::WIDTH {
+                    let (x, y) = (a.extract(i), b.extract(i));
+                    assert_eq!(sum.extract(i), x + y);
+                    assert_eq!(diff.extract(i), x - y);
+                    assert_eq!(prod.extract(i), x * y);
+                    assert_eq!(conj.extract(i), x.conj());
+                    assert_eq!(scaled.extract(i), Complex::new(x.re * 2.0, x.im * 2.0));
+                    assert_eq!(norm.extract(i * 2), x.norm_sqr());
+                    assert_eq!(norm.extract(i * 2 + 1), x.norm_sqr());
+                }
+            }
+        }
+    }
+
+    test_complex!(c32s, f32, complex_c32s);
+    test_complex!(c64s, f64, complex_c64s);
+
+    #[test]
+    fn complex_abs() {
+        assert_eq!(c32s(Complex::new(3.0, 4.0)).abs(), f32s(5.0));
+        assert_eq!(c64s(Complex::new(3.0, 4.0)).abs(), f64s(5.0));
+    }
+
+    #[test]
+    fn complex_iter() {
+        let data = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let mut out = [Complex::new(0.0f32, 0.0); 3];
+        as_complex(&data[..6]).simd_iter(c32s(Complex::new(0.0, 0.0)))
+            .simd_map(|v| v.conj())
+            .scalar_fill(&mut out);
+        assert_eq!(out, [Complex::new(1.0, -2.0), Complex::new(3.0, -4.0), Complex::new(5.0, -6.0)]);
+    }
+}
@@ -1,63 +1,21 @@
 #![allow(unused_macros, dead_code)]
 
-use std::collections::HashSet;
-use std::cell::RefCell;
-
-thread_local! {
-    // Not perfect as it might print multiple times (once per thread),
-    // but better than a `global` hack to prevent multiple prints of the
-    // same warning.
-    pub(crate) static OUTPUT_GUARD: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
-}
-
-
-macro_rules! debug_append_log {
-    ($str:expr) => {
-        use std::io::Write;
-
-        // Allows the user to configure debug file path at compile time,
-        // e.g., when building for embedded / Android.
-        let file_name = option_env!("FASTER_DEBUG_FILE").unwrap_or("faster-debug.txt");
-
-        std::fs::OpenOptions::new()
-            .write(true)
-            .create(true)
-            .append(true)
-            .open(file_name).and_then(|mut file| {
-                writeln!(file, "{}", $str)
-        }).ok(); // `ok` suppresses warning about unused results, about which we don't care.
-    }
-}
-
-
-/// Prints the given string once (for the current thread).
-/// Useful for not spamming the console.
-macro_rules! debug_output_once {
-    ($str:expr) => {
-        let output = $str;
-
-        crate::debug::OUTPUT_GUARD.with(|f| {
-            let mut output_guard = f.borrow_mut();
-
-            if output_guard.contains(&output) {
-                return;
-            }
-
-            // Also print to file (if enabled).
-            debug_append_log!(output);
-            println!("{}", output);
-
-            output_guard.insert(output);
-        });
-    }
+/// Count a run of this call site in `crate::trace`. The type name of a nested
+/// function tells which trait, vector and function the call site is in.
+#[cfg(feature="trace")]
+macro_rules! trace_site {
+    ($backend:expr) => {{
+        static SITE: crate::trace::Site = crate::trace::Site::new($backend, file!(), line!());
+        fn __site() { }
+        crate::trace::hit(&SITE, crate::trace::type_name_of(__site));
+    }}
 }
 
-
 /// Signal a software fallback is executed.
 #[cfg(feature="trace")]
 macro_rules! fallback {
     () => {
-        debug_output_once!(format!("⛔ faster is using SOFTWARE emulation here ({}:{}).", file!(), line!()));
+        trace_site!(crate::trace::Backend::Software);
     }
 }
 
@@ -65,7 +23,7 @@ macro_rules! fallback {
 #[cfg(feature="trace")]
 macro_rules! optimized {
     () => {
-        debug_output_once!(format!("🚄 faster is using HARDWARE acceleration here ({}:{}).", file!(), line!()));
+        trace_site!(crate::trace::Backend::Hardware);
     }
 }
 
@@ -78,4 +36,3 @@ macro_rules! fallback {
 macro_rules! optimized {
     () => { }
 }
-
@@ -0,0 +1,53 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::core::fmt;
+
+/// An error returned by the fallible counterparts of operations which would
+/// otherwise panic, such as `Packed::try_load` and `IntoSIMDZip::try_zip`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A vector of `width` elements doesn't fit into a slice of `len`
+    /// elements at `offset`.
+    OutOfBounds { offset: usize, width: usize, len: usize },
+    /// The zipped iterators have different lengths. `expected` is the length
+    /// of the first iterator, and `found` is the length of the first one
+    /// which differs from it.
+    LengthMismatch { expected: usize, found: usize },
+    /// A buffer of `len` elements is too short, as at least `needed`
+    /// elements are required.
+    BufferTooShort { needed: usize, len: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::OutOfBounds { offset, width, len } =>
+                write!(f, "{} elements at offset {} are out of bounds of a slice of length {}",
+                       width, offset, len),
+            Error::LengthMismatch { expected, found } =>
+                write!(f, "can only zip iterators of the same length, but found lengths {} and {}",
+                       expected, found),
+            Error::BufferTooShort { needed, len } =>
+                write!(f, "buffer of length {} is too short, as at least {} elements are required",
+                       len, needed),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error { }
+
+/// Return an error unless `width` elements fit into a slice of `len` elements
+/// at `offset`.
+#[inline(always)]
+pub(crate) fn check_bounds(len: usize, offset: usize, width: usize) -> Result<(), Error> {
+    match offset.checked_add(width) {
+        Some(end) if end <= len => Ok(()),
+        _ => Err(Error::OutOfBounds { offset, width, len }),
+    }
+}
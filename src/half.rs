@@ -0,0 +1,365 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Half-precision storage vectors.
+//!
+//! IEEE 754 binary16 and bfloat16 values have no native Rust type, so they
+//! are stored as their bit patterns in `u16`s. The vectors in this module pack
+//! those bits, and may be iterated over any `&[u16]` holding half data. They
+//! support no arithmetic of their own; instead, they are converted losslessly
+//! into `f32s` with [`Upcast`], and converted back with round-to-nearest-even
+//! via [`Downcast`].
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//!
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() { }
+//!
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! // 1.5 and -2.0, as IEEE halfs
+//! let weights = [0x3E00u16, 0xC000].iter().cycle().take(100).cloned().collect::<Vec<u16>>();
+//! let sum = weights.simd_iter(f16s(0))
+//!     .simd_reduce(f32s(0.0), |acc, v| {
+//!         let (lo, hi): (f32s, f32s) = v.upcast();
+//!         acc + lo + hi
+//!     }).sum();
+//! assert_eq!(sum, -25.0);
+//! # }
+//! ```
+//!
+//! [`Upcast`]: ../intrin/upcast/trait.Upcast.html
+//! [`Downcast`]: ../intrin/downcast/trait.Downcast.html
+
+use crate::vecs::Packed;
+use crate::intrin::merge::Merge;
+use crate::arch::current::vecs::u16s;
+
+/// A SIMD vector of IEEE 754 half-precision floats, stored as the bits of
+/// the `u16`s in `V`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PackedF16<V>(pub V);
+
+/// A SIMD vector of bfloat16 floats, stored as the bits of the `u16`s in
+/// `V`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PackedBF16<V>(pub V);
+
+/// A SIMD vector of IEEE 754 half-precision floats.
+#[allow(non_camel_case_types)]
+pub type f16s = PackedF16<u16s>;
+
+/// A SIMD vector of bfloat16 floats.
+#[allow(non_camel_case_types)]
+pub type bf16s = PackedBF16<u16s>;
+
+/// Return a vector of half-precision floats with all elements initialized to
+/// the bits in `data`.
+#[inline(always)]
+pub fn f16s(data: u16) -> f16s {
+    f16s::splat(data)
+}
+
+/// Return a vector of bfloat16 floats with all elements initialized to the
+/// bits in `data`.
+#[inline(always)]
+pub fn bf16s(data: u16) -> bf16s {
+    bf16s::splat(data)
+}
+
+macro_rules! impl_packed_half {
+    ($($half:ident),*) => {
+        $(
+            impl<V> Merge for $half<V> where V : Packed<Scalar = u16> {
+                #[inline(always)]
+                fn merge_halves(&self, other: Self) -> Self {
+                    $half(self.0.merge_halves(other.0))
+                }
+
+                #[inline(always)]
+                fn merge_interleaved(&self, other: Self) -> Self {
+                    $half(self.0.merge_interleaved(other.0))
+                }
+
+                #[inline(always)]
+                fn merge_partitioned(&self, other: Self, offset: usize) -> Self {
+                    $half(self.0.merge_partitioned(other.0, offset))
+                }
+            }
+
+            impl<V> Packed for $half<V> where V : Packed<Scalar = u16> {
+                type Scalar = u16;
+
+                const WIDTH: usize = V::WIDTH;
+
+                #[inline(always)]
+                fn load(data: &[u16], offset: usize) -> Self {
+                    $half(V::load(data, offset))
+                }
+
+                #[inline(always)]
+                unsafe fn load_unchecked(data: &[u16], offset: usize) -> Self {
+                    $half(V::load_unchecked(data, offset))
+                }
+
+                #[inline(always)]
+                fn store(self, data: &mut [u16], offset: usize) {
+                    self.0.store(data, offset)
+                }
+
+                #[inline(always)]
+                unsafe fn store_unchecked(self, data: &mut [u16], offset: usize) {
+                    self.0.store_unchecked(data, offset)
+                }
+
+                #[inline(always)]
+                fn coalesce(self) -> u16 {
+                    self.0.coalesce()
+                }
+
+                #[inline(always)]
+                fn splat(data: u16) -> Self {
+                    $half(V::splat(data))
+                }
+
+                #[inline(always)]
+                fn default() -> Self {
+                    $half(V::default())
+                }
+
+                #[inline(always)]
+                fn extract(&self, idx: usize) -> u16 {
+                    self.0.extract(idx)
+                }
+
+                #[inline(always)]
+                unsafe fn extract_unchecked(&self, idx: usize) -> u16 {
+                    self.0.extract_unchecked(idx)
+                }
+
+                #[inline(always)]
+                fn replace(&mut self, idx: usize, data: u16) -> Self {
+                    $half(self.0.replace(idx, data))
+                }
+
+                #[inline(always)]
+                unsafe fn replace_unchecked(&mut self, idx: usize, data: u16) -> Self {
+                    $half(self.0.replace_unchecked(idx, data))
+                }
+
+                /// Return the bitwise product of the elements of this vector.
+                /// This is not meaningful for floats, and only exists to
+                /// satisfy `Packed`.
+                #[inline(always)]
+                fn product(&self) -> u16 {
+                    self.0.product()
+                }
+
+                #[inline(always)]
+                fn scalar_reduce<T, F>(&self, acc: T, func: F) -> T
+                where F: FnMut(T, u16) -> T {
+                    self.0.scalar_reduce(acc, func)
+                }
+            }
+        )*
+    }
+}
+
+impl_packed_half!(PackedF16, PackedBF16);
+
+/// Convert the bits of an IEEE half-precision float into an `f32`. This
+/// conversion is exact.
+#[inline(always)]
+pub fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exp = ((half >> 10) & 0x1F) as u32;
+    let man = (half & 0x03FF) as u32;
+
+    let bits = if exp == 0 {
+        if man == 0 {
+            sign
+        } else {
+            // Subnormal halfs are normal floats; shift the mantissa until its
+            // leading bit is implicit
+            let mut exp = 127 - 15 + 1;
+            let mut man = man;
+            while man & 0x0400 == 0 {
+                man <<= 1;
+                exp -= 1;
+            }
+            sign | (exp << 23) | ((man & 0x03FF) << 13)
+        }
+    } else if exp == 0x1F {
+        // Infinity or NaN, keeping the NaN payload
+        sign | 0x7F80_0000 | (man << 13)
+    } else {
+        sign | ((exp + 127 - 15) << 23) | (man << 13)
+    };
+    f32::from_bits(bits)
+}
+
+/// Convert an `f32` into the bits of the nearest IEEE half-precision float,
+/// rounding ties to even. Values too large for a half become infinite.
+#[inline(always)]
+pub fn f32_to_f16(float: f32) -> u16 {
+    let bits = float.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xFF) as i32;
+    let man = bits & 0x007F_FFFF;
+
+    if exp == 0xFF {
+        // Infinity or NaN; NaNs stay NaN even if their payload is truncated
+        return sign | 0x7C00 | if man != 0 { 0x0200 | (man >> 13) as u16 } else { 0 };
+    }
+
+    let exp = exp - 127 + 15;
+    if exp >= 0x1F {
+        return sign | 0x7C00;
+    }
+
+    let (man, shift, exp) = if exp <= 0 {
+        if exp < -10 {
+            // Smaller than half of the smallest subnormal half
+            return sign;
+        }
+        // The result is subnormal, so the implicit bit becomes explicit
+        (man | 0x0080_0000, (14 - exp) as u32, 0)
+    } else {
+        (man, 13, exp as u32)
+    };
+
+    let halfway = 1 << (shift - 1);
+    let rem = man & ((1 << shift) - 1);
+    let mut ret = (exp << 10) | (man >> shift);
+    if rem > halfway || (rem == halfway && ret & 1 == 1) {
+        // A carry out of the mantissa correctly increments the exponent
+        ret += 1;
+    }
+    sign | ret as u16
+}
+
+/// Convert the bits of a bfloat16 into an `f32`. This conversion is exact.
+#[inline(always)]
+pub fn bf16_to_f32(half: u16) -> f32 {
+    f32::from_bits((half as u32) << 16)
+}
+
+/// Convert an `f32` into the bits of the nearest bfloat16, rounding ties to
+/// even.
+#[inline(always)]
+pub fn f32_to_bf16(float: f32) -> u16 {
+    let bits = float.to_bits();
+    if float.is_nan() {
+        // Truncate, and make sure the NaN is quiet so it stays a NaN
+        ((bits >> 16) | 0x0040) as u16
+    } else {
+        ((bits + 0x7FFF + ((bits >> 16) & 1)) >> 16) as u16
+    }
+}
+
+/// Convert the elements of `half` into two vectors of `f32`s, using `conv`
+/// on each element.
+#[inline(always)]
+pub(crate) fn upcast_half_polyfill<H, F>(half: H, conv: fn(u16) -> f32) -> (F, F)
+    where H : Packed<Scalar = u16>, F : Packed<Scalar = f32> {
+    debug_assert_eq!(H::WIDTH, F::WIDTH * 2);
+    let (mut lo, mut hi) = (F::default(), F::default());
+    for i in 0..F::WIDTH {
+        unsafe {
+            lo = lo.replace_unchecked(i, conv(half.extract_unchecked(i)));
+            hi = hi.replace_unchecked(i, conv(half.extract_unchecked(i + F::WIDTH)));
+        }
+    }
+    (lo, hi)
+}
+
+/// Convert the elements of `lo` and `hi` into a single vector of halfs,
+/// using `conv` on each element.
+#[inline(always)]
+pub(crate) fn downcast_half_polyfill<F, H>(lo: F, hi: F, conv: fn(f32) -> u16) -> H
+    where H : Packed<Scalar = u16>, F : Packed<Scalar = f32> {
+    debug_assert_eq!(H::WIDTH, F::WIDTH * 2);
+    let mut ret = H::default();
+    for i in 0..F::WIDTH {
+        unsafe {
+            ret = ret.replace_unchecked(i, conv(lo.extract_unchecked(i)));
+            ret = ret.replace_unchecked(i + F::WIDTH, conv(hi.extract_unchecked(i)));
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use super::*;
+
+    #[test]
+    fn f16_round_trip() {
+        // Every half which isn't a NaN survives a trip through f32
+        for half in 0..=0xFFFFu16 {
+            let float = f16_to_f32(half);
+            if !float.is_nan() {
+                assert_eq!(f32_to_f16(float), half);
+            } else {
+                assert!(f16_to_f32(f32_to_f16(float)).is_nan());
+            }
+        }
+    }
+
+    #[test]
+    fn f16_rounding() {
+        assert_eq!(f32_to_f16(1.0), 0x3C00);
+        assert_eq!(f32_to_f16(65504.0), 0x7BFF);
+        assert_eq!(f32_to_f16(65520.0), 0x7C00);
+        assert_eq!(f32_to_f16(-1.0e10), 0xFC00);
+        // 1 + 2^-11 is exactly between 1 and the next half; ties go to even
+        assert_eq!(f32_to_f16(1.0 + 1.0 / 2048.0), 0x3C00);
+        assert_eq!(f32_to_f16(1.0 + 3.0 / 2048.0), 0x3C02);
+        // The smallest subnormal, and half of it
+        assert_eq!(f32_to_f16(5.960464477539063e-8), 0x0001);
+        assert_eq!(f32_to_f16(2.9802322387695312e-8), 0x0000);
+        assert_eq!(f32_to_f16(2.9802326e-8), 0x0001);
+    }
+
+    #[test]
+    fn bf16_rounding() {
+        for half in 0..=0xFFFFu16 {
+            let float = bf16_to_f32(half);
+            if !float.is_nan() {
+                assert_eq!(f32_to_bf16(float), half);
+            }
+        }
+        assert_eq!(f32_to_bf16(f32::from_bits(0x3F80_8000)), 0x3F80);
+        assert_eq!(f32_to_bf16(f32::from_bits(0x3F81_8000)), 0x3F82);
+        assert_eq!(f32_to_bf16(f32::from_bits(0x3F80_8001)), 0x3F81);
+        assert!(bf16_to_f32(f32_to_bf16(f32::from_bits(0x7F80_0001))).is_nan());
+    }
+
+    #[test]
+    fn half_vectors() {
+        let halfs = (0..f16s::WIDTH).fold(f16s(0), |mut v, i| v.replace(i, f32_to_f16(i as f32 - 3.5)));
+        let (lo, hi): (f32s, f32s) = halfs.upcast();
+        for i in 0..f32s::WIDTH {
+            assert_eq!(lo.extract(i), i as f32 - 3.5);
+            assert_eq!(hi.extract(i), (i + f32s::WIDTH) as f32 - 3.5);
+        }
+        let back: f16s = lo.saturating_downcast(hi);
+        assert_eq!(back, halfs);
+
+        let bhalfs = (0..bf16s::WIDTH).fold(bf16s(0), |mut v, i| v.replace(i, f32_to_bf16(i as f32 - 3.5)));
+        let (lo, hi): (f32s, f32s) = bhalfs.upcast();
+        for i in 0..f32s::WIDTH {
+            assert_eq!(lo.extract(i), i as f32 - 3.5);
+            assert_eq!(hi.extract(i), (i + f32s::WIDTH) as f32 - 3.5);
+        }
+        let back: bf16s = lo.saturating_downcast(hi);
+        assert_eq!(back, bhalfs);
+    }
+}
@@ -0,0 +1,320 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Non-cryptographic hashing of fixed-width keys.
+//!
+//! Each mixer is implemented for `u32` and `u64`, and lane-wise for `u32s`
+//! and `u64s`. A lane of a vector always hashes to the same value as the
+//! scalar it holds, so hashes may be computed a vector at a time and compared
+//! or stored alongside hashes computed one key at a time.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//! use faster::hash::*;
+//!
+//! # fn main() {
+//! let keys = [1u64, 2, 3, 4, 5, 6, 7, 8, 9];
+//! let mut iter = (&keys[..]).simd_iter(u64s(0)).simd_hash(Mixer::Murmur3, 4);
+//! for (hashes, partitions) in iter.by_ref() {
+//!     assert!(partitions.extract(0) < 16);
+//!     assert_eq!(partitions.extract(0), hashes.extract(0).partition(4));
+//! }
+//! // The last few keys are right-aligned in a partially full vector
+//! let (hashes, _, empty) = iter.end().unwrap();
+//! assert_eq!(hashes.extract(u64s::WIDTH - 1), 9u64.murmur3());
+//! assert!(empty < u64s::WIDTH);
+//! # }
+//! ```
+
+use crate::vecs::Packed;
+use crate::iters::SIMDIterator;
+use crate::arch::current::vecs::{u32s, u64s};
+use crate::core::ops::{BitXor, Shr};
+
+/// A function which scrambles the bits of a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mixer {
+    /// The finalizer of MurmurHash3, `fmix32` or `fmix64`.
+    Murmur3,
+    /// The avalanche step of xxHash: XXH32's for 32-bit keys, and XXH3's for
+    /// 64-bit keys.
+    Xxh3,
+    /// A multiplication by the odd integer closest to the golden ratio times
+    /// the key's range. Only the high bits of the result are well mixed, so
+    /// this should only be used with [`Mix::partition`].
+    ///
+    /// [`Mix::partition`]: trait.Mix.html#tymethod.partition
+    MultiplyShift,
+}
+
+/// Lane-wise hash mixers.
+pub trait Mix : Copy {
+    /// The number of bits in each hash.
+    const BITS: u32;
+
+    /// Return the MurmurHash3 finalizer of each key.
+    fn murmur3(self) -> Self;
+
+    /// Return the xxHash avalanche of each key.
+    fn xxh3(self) -> Self;
+
+    /// Return each key multiplied by the golden ratio, in the key's range.
+    fn multiply_shift(self) -> Self;
+
+    /// Return the top `bits` bits of each hash, which may be used as an index
+    /// into `1 << bits` partitions. `bits` must be no larger than
+    /// `Self::BITS`; zero puts every hash in the same partition.
+    fn partition(self, bits: u32) -> Self;
+
+    /// Return each key scrambled by `mixer`.
+    #[inline(always)]
+    fn mix(self, mixer: Mixer) -> Self {
+        match mixer {
+            Mixer::Murmur3 => self.murmur3(),
+            Mixer::Xxh3 => self.xxh3(),
+            Mixer::MultiplyShift => self.multiply_shift(),
+        }
+    }
+}
+
+/// A key or vector of keys which mixers operate on.
+trait Word : Copy + BitXor<Output = Self> + Shr<Self, Output = Self> {
+    /// Return `x` truncated and splatted into this type.
+    fn lit(x: u64) -> Self;
+
+    /// Return `self * rhs`, wrapping on overflow.
+    fn mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_word {
+    ($ty:ty, |$x:ident| $lit:expr, |$a:ident, $b:ident| $mul:expr) => {
+        impl Word for $ty {
+            #[inline(always)]
+            fn lit($x: u64) -> Self {
+                $lit
+            }
+
+            #[inline(always)]
+            fn mul(self, rhs: Self) -> Self {
+                let ($a, $b) = (self, rhs);
+                $mul
+            }
+        }
+    }
+}
+
+impl_word!(u32, |x| x as u32, |a, b| a.wrapping_mul(b));
+impl_word!(u64, |x| x, |a, b| a.wrapping_mul(b));
+impl_word!(u32s, |x| u32s(x as u32), |a, b| a * b);
+impl_word!(u64s, |x| u64s(x), |a, b| a * b);
+
+/// Return `h` with each of its bits shifted down by `shift` and XORed in.
+#[inline(always)]
+fn xorshift<W : Word>(h: W, shift: u64) -> W {
+    h ^ (h >> W::lit(shift))
+}
+
+#[inline(always)]
+fn fmix32<W : Word>(h: W) -> W {
+    let h = xorshift(h, 16).mul(W::lit(0x85EB_CA6B));
+    let h = xorshift(h, 13).mul(W::lit(0xC2B2_AE35));
+    xorshift(h, 16)
+}
+
+#[inline(always)]
+fn fmix64<W : Word>(h: W) -> W {
+    let h = xorshift(h, 33).mul(W::lit(0xFF51_AFD7_ED55_8CCD));
+    let h = xorshift(h, 33).mul(W::lit(0xC4CE_B9FE_1A85_EC53));
+    xorshift(h, 33)
+}
+
+#[inline(always)]
+fn xxh32_avalanche<W : Word>(h: W) -> W {
+    let h = xorshift(h, 15).mul(W::lit(0x85EB_CA77));
+    let h = xorshift(h, 13).mul(W::lit(0xC2B2_AE3D));
+    xorshift(h, 16)
+}
+
+#[inline(always)]
+fn xxh3_avalanche<W : Word>(h: W) -> W {
+    let h = xorshift(h, 37).mul(W::lit(0x1656_6791_9E37_79F9));
+    xorshift(h, 32)
+}
+
+macro_rules! impl_mix {
+    ($ty:ty, $bits:expr, $murmur3:ident, $xxh3:ident, $golden:expr) => {
+        impl Mix for $ty {
+            const BITS: u32 = $bits;
+
+            #[inline(always)]
+            fn murmur3(self) -> Self {
+                $murmur3(self)
+            }
+
+            #[inline(always)]
+            fn xxh3(self) -> Self {
+                $xxh3(self)
+            }
+
+            #[inline(always)]
+            fn multiply_shift(self) -> Self {
+                self.mul(Self::lit($golden))
+            }
+
+            #[inline(always)]
+            fn partition(self, bits: u32) -> Self {
+                assert!(bits <= Self::BITS, "A partition may have at most as many bits as a hash.");
+                // Shifting by the full width of a type is undefined
+                if bits == 0 { Self::lit(0) } else { self >> Self::lit((Self::BITS - bits) as u64) }
+            }
+        }
+    }
+}
+
+impl_mix!(u32, 32, fmix32, xxh32_avalanche, 0x9E37_79B9);
+impl_mix!(u64, 64, fmix64, xxh3_avalanche, 0x9E37_79B9_7F4A_7C15);
+impl_mix!(u32s, 32, fmix32, xxh32_avalanche, 0x9E37_79B9);
+impl_mix!(u64s, 64, fmix64, xxh3_avalanche, 0x9E37_79B9_7F4A_7C15);
+
+/// An iterator which hashes each vector of keys, and computes the partition
+/// of each hash.
+#[derive(Debug)]
+pub struct SIMDHashIter<I> {
+    iter: I,
+    mixer: Mixer,
+    bits: u32,
+}
+
+impl<I> SIMDHashIter<I> where I : SIMDIterator, I::Vector : Mix {
+    /// Return the hashes and partitions of the partially full vector of keys
+    /// at the end of the iterator, along with the number of lanes on the left
+    /// which don't hold a key, or `None` if no keys are left.
+    #[inline(always)]
+    pub fn end(&mut self) -> Option<(I::Vector, I::Vector, usize)> {
+        let (keys, empty) = self.iter.end()?;
+        let hashes = keys.mix(self.mixer);
+        Some((hashes, hashes.partition(self.bits), empty))
+    }
+}
+
+impl<I> Iterator for SIMDHashIter<I> where I : SIMDIterator, I::Vector : Mix {
+    type Item = (I::Vector, I::Vector);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let hashes = self.iter.next()?.mix(self.mixer);
+        Some((hashes, hashes.partition(self.bits)))
+    }
+}
+
+impl<I> ExactSizeIterator for SIMDHashIter<I> where I : SIMDIterator, I::Vector : Mix {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// An iterator of keys which may be hashed a vector at a time.
+pub trait SIMDHash : SIMDIterator where Self::Vector : Mix {
+    /// Return an iterator over the hashes of each vector of keys scrambled by
+    /// `mixer`, and the top `bits` bits of each hash. The partially full
+    /// vector at the end of the iterator is returned by
+    /// [`SIMDHashIter::end`].
+    ///
+    /// [`SIMDHashIter::end`]: struct.SIMDHashIter.html#method.end
+    #[inline(always)]
+    fn simd_hash(self, mixer: Mixer, bits: u32) -> SIMDHashIter<Self> {
+        assert!(bits <= Self::Vector::BITS, "A partition may have at most as many bits as a hash.");
+        SIMDHashIter { iter: self, mixer, bits }
+    }
+}
+
+impl<I> SIMDHash for I where I : SIMDIterator, I::Vector : Mix { }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::testing::data;
+
+    const MIXERS: [Mixer; 3] = [Mixer::Murmur3, Mixer::Xxh3, Mixer::MultiplyShift];
+
+    fn keys(len: usize) -> Vec<u64> {
+        data(len).into_iter().enumerate().map(|(i, x)| if i % 4 == 0 { i as u64 } else { x }).collect()
+    }
+
+    #[test]
+    fn known_answers() {
+        // The finalizers fix zero, and MurmurHash3's are widely published
+        assert_eq!(0u32.murmur3(), 0);
+        assert_eq!(0u64.murmur3(), 0);
+        assert_eq!(0u64.xxh3(), 0);
+        assert_eq!(1u32.murmur3(), 0x514E_28B7);
+        assert_eq!(1u64.murmur3(), 0xB456_BCFC_34C2_CB2C);
+        assert_eq!(1u64.multiply_shift(), 0x9E37_79B9_7F4A_7C15);
+        assert_eq!(0xFFFF_FFFF_FFFF_FFFFu64.partition(4), 0xF);
+        assert_eq!(0x8000_0000u32.partition(1), 1);
+        assert_eq!(0x1234u64.partition(0), 0);
+        assert_eq!(0x1234u64.partition(64), 0x1234);
+    }
+
+    macro_rules! check_vectors_match_scalars {
+        ($keys:expr, $vec:ident, $mixer:expr, $bits:expr) => {{
+            let (keys, mixer, bits) = (&$keys[..], $mixer, $bits);
+            let mut iter = keys.simd_iter($vec(0)).simd_hash(mixer, bits);
+            let mut i = 0;
+            for (hashes, partitions) in iter.by_ref() {
+                for lane in 0..$vec::WIDTH {
+                    assert_eq!(hashes.extract(lane), keys[i + lane].mix(mixer));
+                    assert_eq!(partitions.extract(lane), keys[i + lane].mix(mixer).partition(bits));
+                }
+                i += $vec::WIDTH;
+            }
+            if let Some((hashes, partitions, empty)) = iter.end() {
+                assert_eq!(i + $vec::WIDTH - empty, keys.len());
+                for lane in empty..$vec::WIDTH {
+                    let key = keys[keys.len() - $vec::WIDTH + lane];
+                    assert_eq!(hashes.extract(lane), key.mix(mixer));
+                    assert_eq!(partitions.extract(lane), key.mix(mixer).partition(bits));
+                }
+            } else {
+                assert_eq!(i, keys.len());
+            }
+        }}
+    }
+
+    #[test]
+    fn vectors_match_scalars() {
+        for &len in [0, 1, 999, 1000].iter() {
+            let keys = keys(len);
+            let keys32: Vec<u32> = keys.iter().map(|&k| k as u32 ^ (k >> 32) as u32).collect();
+            for &mixer in MIXERS.iter() {
+                for bits in 0..=32 {
+                    check_vectors_match_scalars!(keys, u64s, mixer, bits);
+                    check_vectors_match_scalars!(keys32, u32s, mixer, bits);
+                }
+                check_vectors_match_scalars!(keys, u64s, mixer, 64);
+            }
+        }
+    }
+
+    #[test]
+    fn partitions_are_balanced() {
+        // Sequential keys are the worst case for a weak mixer
+        let keys: Vec<u64> = (0..1 << 14).collect();
+        for &mixer in MIXERS.iter() {
+            let mut counts = [0usize; 16];
+            for &k in keys.iter() {
+                counts[k.mix(mixer).partition(4) as usize] += 1;
+            }
+            for &c in counts.iter() {
+                assert!(c > 800 && c < 1250, "{:?} {:?}", mixer, counts);
+            }
+        }
+    }
+}
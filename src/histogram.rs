@@ -0,0 +1,223 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Histograms and counts of matching elements.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::histogram::*;
+//!
+//! # fn main() {
+//! let data = b"mississippi";
+//! let histogram = byte_histogram(data);
+//! assert_eq!(histogram[b's' as usize], 4);
+//! assert_eq!(count_eq(data, b'p'), 2);
+//! assert_eq!(count_in_range(&[1.5f32, -2.0, 8.0, 3.0][..], 0.0, 4.0), 2);
+//! # }
+//! ```
+
+use crate::arch::current::vecs::{u8s, i8s, u16s, i16s, u32s, i32s, f32s, u64s, i64s, f64s};
+use crate::intrin::cmp::Cmp;
+use crate::intrin::eq::Eq as PackedEq;
+use crate::intrin::sum::UpcastSum;
+use crate::vecs::Packed;
+
+/// The number of sub-histograms bytes are spread across. Consecutive bytes
+/// are usually the same, and incrementing the same counter back to back
+/// stalls on the store of the previous increment.
+const SUB_HISTOGRAMS: usize = 4;
+
+/// Return the number of times each byte occurs in `data`, indexed by the
+/// byte.
+///
+/// ```
+/// extern crate faster;
+/// use faster::histogram::byte_histogram;
+///
+/// # fn main() {
+/// let histogram = byte_histogram(&[0, 1, 1, 255]);
+/// assert_eq!(histogram[0], 1);
+/// assert_eq!(histogram[1], 2);
+/// assert_eq!(histogram[2], 0);
+/// assert_eq!(histogram[255], 1);
+/// # }
+/// ```
+pub fn byte_histogram(data: &[u8]) -> [usize; 256] {
+    let mut histogram = [0usize; 256];
+    // Keep each sub-histogram's counters small enough not to overflow
+    for block in data.chunks(u32::MAX as usize) {
+        let mut subs = [[0u32; 256]; SUB_HISTOGRAMS];
+        let mut chunks = block.chunks_exact(SUB_HISTOGRAMS);
+        for chunk in &mut chunks {
+            for (sub, &byte) in subs.iter_mut().zip(chunk) {
+                sub[byte as usize] += 1;
+            }
+        }
+        for &byte in chunks.remainder() {
+            subs[0][byte as usize] += 1;
+        }
+        for sub in subs.iter() {
+            for (total, &count) in histogram.iter_mut().zip(sub.iter()) {
+                *total += count as usize;
+            }
+        }
+    }
+    histogram
+}
+
+/// A primitive whose matching elements can be counted.
+pub trait Countable : Copy {
+    #[doc(hidden)]
+    fn count_eq(data: &[Self], needle: Self) -> usize;
+
+    #[doc(hidden)]
+    fn count_in_range(data: &[Self], lo: Self, hi: Self) -> usize;
+}
+
+/// Return the number of elements of `data` which are equal to `needle`.
+/// Floats are compared as with `==`, so NaNs are never counted.
+///
+/// ```
+/// extern crate faster;
+/// use faster::histogram::count_eq;
+///
+/// # fn main() {
+/// assert_eq!(count_eq(&[1u16, 2, 1, 3, 1][..], 1), 3);
+/// assert_eq!(count_eq(&[0.0f64, -0.0, 1.0][..], 0.0), 2);
+/// # }
+/// ```
+#[inline]
+pub fn count_eq<T : Countable>(data: &[T], needle: T) -> usize {
+    T::count_eq(data, needle)
+}
+
+/// Return the number of elements of `data` which are at least `lo` and at
+/// most `hi`. NaNs are never counted.
+///
+/// ```
+/// extern crate faster;
+/// use faster::histogram::count_in_range;
+///
+/// # fn main() {
+/// assert_eq!(count_in_range(&[-3i32, 0, 5, 10, 11][..], 0, 10), 3);
+/// # }
+/// ```
+#[inline]
+pub fn count_in_range<T : Countable>(data: &[T], lo: T, hi: T) -> usize {
+    T::count_in_range(data, lo, hi)
+}
+
+/// Count the elements `$x` of `$data` for which `$scalar` holds, given a mask
+/// `$mask` of the vectors `$v` for which it holds.
+macro_rules! count_matching {
+    ($data:expr, $vec:ty, $count:ty, $cel:ty, |$v:ident| $mask:expr, |$x:ident| $scalar:expr) => {{
+        let data = $data;
+        let width = <$vec>::WIDTH;
+        let ones = <$count>::splat(1);
+        let mut total = 0usize;
+        let mut counts = <$count>::splat(0);
+        let mut pending = 0usize;
+        let mut i = 0;
+        while i + width <= data.len() {
+            let $v = <$vec>::load(data, i);
+            counts = counts + ($mask & ones);
+            pending += 1;
+            // Add the counts up before any of them can overflow
+            if pending == <$cel>::MAX as usize {
+                total += counts.sum_upcast() as usize;
+                counts = <$count>::splat(0);
+                pending = 0;
+            }
+            i += width;
+        }
+        total += counts.sum_upcast() as usize;
+        total + data[i..].iter().filter(|&&$x| $scalar).count()
+    }}
+}
+
+macro_rules! impl_countable {
+    ($($el:ty, $vec:ty, $count:ty, $cel:ty);*) => {
+        $(
+            impl Countable for $el {
+                #[inline]
+                fn count_eq(data: &[Self], needle: Self) -> usize {
+                    let needles = <$vec>::splat(needle);
+                    count_matching!(data, $vec, $count, $cel,
+                                    |v| v.eq_mask(needles),
+                                    |x| x == needle)
+                }
+
+                #[inline]
+                fn count_in_range(data: &[Self], lo: Self, hi: Self) -> usize {
+                    let (los, his) = (<$vec>::splat(lo), <$vec>::splat(hi));
+                    count_matching!(data, $vec, $count, $cel,
+                                    |v| Cmp::max(&v, los).eq_mask(v) & Cmp::min(&v, his).eq_mask(v),
+                                    |x| lo <= x && x <= hi)
+                }
+            }
+        )*
+    }
+}
+
+impl_countable!(u8, u8s, u8s, u8; i8, i8s, u8s, u8;
+                u16, u16s, u16s, u16; i16, i16s, u16s, u16;
+                u32, u32s, u32s, u32; i32, i32s, u32s, u32; f32, f32s, u32s, u32;
+                u64, u64s, u64s, u64; i64, i64s, u64s, u64; f64, f64s, u64s, u64);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing::data;
+
+    #[test]
+    fn byte_histogram_matches_scalar() {
+        for &len in [0, 1, 3, 4, 5, 100, 4097].iter() {
+            let input = data(len).iter().map(|&x| (x % 7) as u8 * 37).collect::<Vec<u8>>();
+            let mut expected = [0usize; 256];
+            for &byte in input.iter() {
+                expected[byte as usize] += 1;
+            }
+            assert_eq!(&byte_histogram(&input)[..], &expected[..]);
+        }
+        assert_eq!(byte_histogram(&[9; 1000])[9], 1000);
+    }
+
+    macro_rules! test_count {
+        ($($el:ty, $fn:ident);*) => {
+            $(
+                #[test]
+                fn $fn() {
+                    // Long enough to flush saturated u8 counts several times
+                    for &len in [0, 1, 15, 16, 17, 64, 1000, 70000].iter() {
+                        let input = data(len).iter().map(|&x| (x % 5) as $el).collect::<Vec<$el>>();
+                        for needle in 0..6 {
+                            let needle = needle as $el;
+                            assert_eq!(count_eq(&input, needle),
+                                       input.iter().filter(|&&x| x == needle).count());
+                        }
+                        assert_eq!(count_in_range(&input, 1 as $el, 3 as $el),
+                                   input.iter().filter(|&&x| 1 as $el <= x && x <= 3 as $el).count());
+                        assert_eq!(count_in_range(&input, 3 as $el, 1 as $el), 0);
+                    }
+                }
+            )*
+        }
+    }
+
+    test_count!(u8, count_u8; i8, count_i8; u16, count_u16; i16, count_i16;
+                u32, count_u32; i32, count_i32; f32, count_f32;
+                u64, count_u64; i64, count_i64; f64, count_f64);
+
+    #[test]
+    fn count_nans() {
+        let input = [1.0f32, ::std::f32::NAN, 2.0, 3.0, ::std::f32::NAN, 0.5, 7.0, 1.0, -0.0];
+        assert_eq!(count_eq(&input[..], ::std::f32::NAN), 0);
+        assert_eq!(count_eq(&input[..], 0.0), 1);
+        assert_eq!(count_in_range(&input[..], 0.0, 3.0), 6);
+        assert_eq!(count_in_range(&input[..], ::std::f32::NEG_INFINITY, ::std::f32::INFINITY), 7);
+    }
+}
@@ -5,129 +5,138 @@
 // License, v. 2.0. If a copy owf the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::iters::{SIMDIter, SIMDIterator, SIMDObject};
+use crate::iters::{SIMDIter, SIMDIterator, SIMDObject, PackedAs, Prefetch};
 #[allow(unused_imports)] // Remove for specialization
 use crate::iters::SIMDAdapter;
-use crate::arch::current::vecs::*;
+use crate::vecs::{Packable, Packed};
 
 /// A trait which transforms a contiguous collection into an owned stream of
-/// vectors.
-pub trait IntoSIMDIterator {
-    type Iter : SIMDIterator;
+/// vectors of type `V`.
+pub trait IntoSIMDIterator<V> where V : Packed {
+    type Iter : SIMDIterator<Vector = V>;
 
     /// Return an iterator over this data which will automatically pack
     /// values into SIMD vectors. See `SIMDIterator::simd_map` and
     /// `SIMDIterator::simd_reduce` for more information.
-    fn into_simd_iter(self, default: <Self::Iter as SIMDObject>::Vector) -> Self::Iter;
+    ///
+    /// The type of `default` selects the vector type of the iterator, so any
+    /// vector with the same scalar type as the collection may be used.
+    fn into_simd_iter(self, default: V) -> Self::Iter;
 }
 
 /// A trait which transforms a contiguous collection into a slice-backed stream
-/// of vectors.
-pub trait IntoSIMDRefIterator<'a> {
-    type Iter : SIMDIterator;
+/// of vectors of type `V`.
+pub trait IntoSIMDRefIterator<'a, V> where V : Packed {
+    type Iter : SIMDIterator<Vector = V>;
 
     /// Return an iterator over this data which will automatically pack
     /// values into SIMD vectors. See `SIMDIterator::simd_map` and
     /// `SIMDIterator::simd_reduce` for more information.
-    fn simd_iter(&'a self, default: <Self::Iter as SIMDObject>::Vector) -> Self::Iter;
+    ///
+    /// The type of `default` selects the vector type of the iterator, so any
+    /// vector with the same scalar type as the collection may be used.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let sum = [1u32; 100].simd_iter(w128::u32s(0))
+    ///     .simd_reduce(w128::u32s(0), |acc, v| acc + v)
+    ///     .sum();
+    /// assert_eq!(sum, 100);
+    /// # }
+    /// ```
+    fn simd_iter(&'a self, default: V) -> Self::Iter;
 }
 
 /// A trait which transforms a contiguous collection into a mutable slice-backed
-/// stream of vectors.
-pub trait IntoSIMDRefMutIterator<'a> {
-    type Iter : SIMDIterator;
+/// stream of vectors of type `V`.
+pub trait IntoSIMDRefMutIterator<'a, V> where V : Packed {
+    type Iter : SIMDIterator<Vector = V>;
 
     /// Return an iterator over this data which will automatically pack
     /// values into SIMD vectors. See `SIMDIterator::simd_map` and
     /// `SIMDIterator::simd_reduce` for more information.
-    fn simd_iter_mut(&'a mut self, default: <Self::Iter as SIMDObject>::Vector) -> Self::Iter;
+    ///
+    /// The type of `default` selects the vector type of the iterator, so any
+    /// vector with the same scalar type as the collection may be used.
+    fn simd_iter_mut(&'a mut self, default: V) -> Self::Iter;
 }
 
-macro_rules! impl_array_intos {
-    ($($el:ty, $vec:ty),*) => {
-        $(
-            #[cfg(feature = "std")]
-            impl IntoSIMDIterator for Vec<$el> {
-                type Iter = SIMDIter<Self>;
-
-                #[inline(always)]
-                fn into_simd_iter(self, default: $vec) -> Self::Iter {
-                    SIMDIter {
-                        data: self,
-                        position: 0,
-                        default: default,
-                    }
-                }
-            }
-
-            impl<'a> IntoSIMDRefIterator<'a> for &'a [$el] {
-                type Iter = SIMDIter<Self>;
-
-                #[inline(always)]
-                fn simd_iter(&'a self, default: $vec) -> Self::Iter {
-                    SIMDIter {
-                        data: self,
-                        position: 0,
-                        default: default,
-                    }
-                }
-            }
-
-            impl<'a> IntoSIMDRefMutIterator<'a> for &'a mut [$el] {
-                type Iter = SIMDIter<Self>;
-
-                #[inline(always)]
-                fn simd_iter_mut(&'a mut self, default: $vec) -> Self::Iter {
-                    SIMDIter {
-                        data: self,
-                        position: 0,
-                        default: default,
-                    }
-                }
-            }
-
-            impl<'a> IntoSIMDRefMutIterator<'a> for [$el] {
-                type Iter = SIMDIter<&'a mut Self>;
-
-                #[inline(always)]
-                fn simd_iter_mut(&'a mut self, default: $vec) -> Self::Iter {
-                    SIMDIter {
-                        data: self,
-                        position: 0,
-                        default: default,
-                    }
-                }
-            }
-
-            impl<'a> IntoSIMDRefIterator<'a> for [$el] {
-                type Iter = SIMDIter<&'a Self>;
-
-                #[inline(always)]
-                fn simd_iter(&'a self, default: $vec) -> Self::Iter {
-                    SIMDIter {
-                        data: self,
-                        position: 0,
-                        default: default,
-                    }
-                }
-            }
-        )*
+#[cfg(feature = "std")]
+impl<S, V> IntoSIMDIterator<V> for Vec<S> where S : Packable, V : Packed<Scalar = S> {
+    type Iter = SIMDIter<PackedAs<Self, V>>;
+
+    #[inline(always)]
+    fn into_simd_iter(self, default: V) -> Self::Iter {
+        SIMDIter {
+            data: PackedAs::new(self),
+            position: 0,
+            default: default,
+            prefetch: Prefetch::default(),
+        }
     }
 }
 
-impl_array_intos!(u8, u8s,
-                  i8, i8s,
-                  u16, u16s,
-                  i16, i16s,
-                  u32, u32s,
-                  i32, i32s,
-                  f32, f32s,
-                  u64, u64s,
-                  i64, i64s,
-                  f64, f64s);
+impl<'a, S, V> IntoSIMDRefIterator<'a, V> for &'a [S] where S : 'a + Packable, V : Packed<Scalar = S> {
+    type Iter = SIMDIter<PackedAs<Self, V>>;
+
+    #[inline(always)]
+    fn simd_iter(&'a self, default: V) -> Self::Iter {
+        SIMDIter {
+            data: PackedAs::new(*self),
+            position: 0,
+            default: default,
+            prefetch: Prefetch::default(),
+        }
+    }
+}
+
+impl<'a, S, V> IntoSIMDRefMutIterator<'a, V> for &'a mut [S] where S : 'a + Packable, V : Packed<Scalar = S> {
+    type Iter = SIMDIter<PackedAs<&'a mut [S], V>>;
+
+    #[inline(always)]
+    fn simd_iter_mut(&'a mut self, default: V) -> Self::Iter {
+        SIMDIter {
+            data: PackedAs::new(&mut **self),
+            position: 0,
+            default: default,
+            prefetch: Prefetch::default(),
+        }
+    }
+}
+
+impl<'a, S, V> IntoSIMDRefMutIterator<'a, V> for [S] where S : 'a + Packable, V : Packed<Scalar = S> {
+    type Iter = SIMDIter<PackedAs<&'a mut Self, V>>;
+
+    #[inline(always)]
+    fn simd_iter_mut(&'a mut self, default: V) -> Self::Iter {
+        SIMDIter {
+            data: PackedAs::new(self),
+            position: 0,
+            default: default,
+            prefetch: Prefetch::default(),
+        }
+    }
+}
+
+impl<'a, S, V> IntoSIMDRefIterator<'a, V> for [S] where S : 'a + Packable, V : Packed<Scalar = S> {
+    type Iter = SIMDIter<PackedAs<&'a Self, V>>;
+
+    #[inline(always)]
+    fn simd_iter(&'a self, default: V) -> Self::Iter {
+        SIMDIter {
+            data: PackedAs::new(self),
+            position: 0,
+            default: default,
+            prefetch: Prefetch::default(),
+        }
+    }
+}
 
 // TODO: Specialization
-// impl<I, S> IntoSIMDIterator for I where I : ExactSizeIterator + Iterator<Item = S>, S : Packable {
+// impl<I, S> IntoSIMDIterator<S::Vector> for I where I : ExactSizeIterator + Iterator<Item = S>, S : Packable {
 //     type Iter = SIMDAdapter<Self, S::Vector>;
 
 //     #[inline(always)]
@@ -6,5 +6,53 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub trait AddSub {
+    /// Return a vector containing the difference of the even elements of
+    /// `self` and `other`, interleaved with the sum of the odd elements of
+    /// `self` and `other`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s(3.0).addsub(f32s(1.0)), f32s::interleave(2.0, 4.0));
+    /// # }
+    /// ```
     fn addsub(&self, other: Self) -> Self;
 }
+
+macro_rules! impl_packed_addsub {
+    ($vec:tt, $feat:expr, $mmfn:tt, $(($a:expr, $b:expr)),*) => {
+        impl AddSub for $vec {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn addsub(&self, other: Self) -> Self {
+                use crate::core::mem::transmute;
+                optimized!();
+                unsafe { transmute($mmfn(transmute(*self), transmute(other))) }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn addsub(&self, other: Self) -> Self {
+                fallback!();
+                $vec::new($(self.extract($a) - other.extract($a),
+                            self.extract($b) + other.extract($b)),*)
+            }
+        }
+    }
+}
+
+macro_rules! test_packed_addsub {
+    (($($vec:tt),*), ($($fn:tt),*)) => {
+        $(
+            #[test]
+            fn $fn() {
+                let a = $vec::interleave(3.0, 5.0);
+                let b = $vec::interleave(1.0, 2.0);
+                assert_eq!(a.addsub(b), $vec::interleave(2.0, 7.0));
+                assert_eq!(b.addsub(a), $vec::interleave(-2.0, 7.0));
+            }
+        )*
+    }
+}
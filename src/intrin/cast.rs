@@ -36,14 +36,37 @@ pub trait Asu16s {
 pub trait Asf32s {
     type Cast;
 
-    /// Return a vector containing all elements of `self` cast to f32s.
+    /// Return a vector containing all elements of `self` cast to f32s,
+    /// rounded to the nearest float as with `as`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(i32s::halfs(-3, 16_777_217).as_f32s(), f32s::halfs(-3.0, 16_777_216.0));
+    /// # }
+    /// ```
     fn as_f32s(self) -> Self::Cast;
 }
 
 pub trait Asi32s {
     type Cast;
 
-    /// Return a vector containing all elements of `self` cast to i32s.
+    /// Return a vector containing all elements of `self` cast to i32s. Floats
+    /// are truncated toward zero as with `as`, but the result of casting a
+    /// NaN or a float out of the range of an i32 is unspecified. Unsigned
+    /// integers are reinterpreted.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(f32s::halfs(-2.7, 2.7).as_i32s(), i32s::halfs(-2, 2));
+    /// assert_eq!(u32s(0xFFFF_FFFF).as_i32s(), i32s(-1));
+    /// # }
+    /// ```
     fn as_i32s(self) -> Self::Cast;
 }
 
@@ -75,15 +98,61 @@ pub trait Asu64s {
     fn as_u64s(self) -> Self::Cast;
 }
 
-// macro_rules! impl_cast {
-//     ($trait:path, $from:ty, $to:ty, $name:ident, $rsname:ident) => (
-//         impl $trait for $from {
-//             type Cast = $to;
-
-//             #[inline(always)]
-//             fn $name(self) -> Self::Cast {
-//                 self.$rsname()
-//             }
-//         }
-//     );
-// }
+macro_rules! impl_packed_cast {
+    ($trait:tt, $fn:ident, $from:tt, $to:tt, $el:tt, reinterpret) => {
+        impl $trait for $from {
+            type Cast = $to;
+
+            #[inline(always)]
+            fn $fn(self) -> Self::Cast {
+                use crate::core::mem::transmute;
+                optimized!();
+                unsafe { transmute::<Self, Self::Cast>(self) }
+            }
+        }
+    };
+    ($trait:tt, $fn:ident, $from:tt, $to:tt, $el:tt, $feat:expr, $mmfn:tt) => {
+        impl $trait for $from {
+            type Cast = $to;
+
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn $fn(self) -> Self::Cast {
+                use crate::core::mem::transmute;
+                optimized!();
+                unsafe { transmute($mmfn(transmute(self))) }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn $fn(self) -> Self::Cast {
+                fallback!();
+                let mut ret = $to::splat(0 as $el);
+                for i in 0..Self::WIDTH {
+                    ret = ret.replace(i, self.extract(i) as $el);
+                }
+                ret
+            }
+        }
+    };
+}
+
+macro_rules! test_packed_cast {
+    (($($from:tt),*), ($($fel:tt),*), ($($fn:ident),*), ($($to:tt),*), ($($tel:tt),*), ($($name:ident),*)) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut v = $from::splat(0 as $fel);
+                for i in 0..$from::WIDTH {
+                    // Stay in range of both types, with a few fractions
+                    v = v.replace(i, ((i as i32 * 7919 % 2001 - 1000) as $fel) / (1 + i % 3) as $fel);
+                }
+                let cast = v.$fn();
+                for i in 0..$from::WIDTH {
+                    assert_eq!(cast.extract(i), v.extract(i) as $tel);
+                }
+                assert_eq!($from::splat(100 as $fel).$fn(), $to::splat(100 as $tel));
+            }
+        )*
+    }
+}
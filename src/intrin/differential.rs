@@ -0,0 +1,408 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// A differential test of the intrinsics against scalar models. Each operation
+// runs on every combination of edge cases, such as the extremes of integers
+// and the zeroes, subnormals, infinities and NaNs of floats, and on random
+// vectors. Each lane of its result is compared against the model of the
+// operation. The inputs come from an xorshift generator with a fixed seed, so
+// a failure reproduces on every run.
+
+use crate::vecs::Packed;
+use crate::testing::Rng;
+use crate::core::fmt::Debug;
+
+/// The number of random vectors to run each operation on.
+const RANDOM_CASES: usize = 1024;
+
+/// The number of mismatched lanes to report for each operation.
+const MAX_REPORTED: usize = 16;
+
+trait Arbitrary : Copy + Debug {
+    /// Return the values most likely to expose a mistake in an operation.
+    fn edges() -> Vec<Self>;
+
+    /// Return a value drawn from `rng`.
+    fn random(rng: &mut Rng) -> Self;
+
+    /// Return whether `self` and `other` are the same value. Unlike `==`,
+    /// floats are compared by their bits, except that all NaNs are the same.
+    fn same(self, other: Self) -> bool;
+}
+
+macro_rules! impl_arbitrary_int {
+    ($($el:tt),*) => {
+        $(
+            impl Arbitrary for $el {
+                fn edges() -> Vec<Self> {
+                    vec![$el::min_value(), $el::min_value() + 1, $el::max_value() - 1, $el::max_value(),
+                         0, 1, 2, !0, !1, 0x5555_5555_5555_5555u64 as $el, 0xAAAA_AAAA_AAAA_AAAAu64 as $el,
+                         $el::max_value() >> 1, ($el::max_value() >> 1) + 1]
+                }
+
+                fn random(rng: &mut Rng) -> Self {
+                    rng.next() as $el
+                }
+
+                fn same(self, other: Self) -> bool {
+                    self == other
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! impl_arbitrary_float {
+    ($($el:tt, $bits:tt);*) => {
+        $(
+            impl Arbitrary for $el {
+                fn edges() -> Vec<Self> {
+                    vec![0.0, -0.0, 1.0, -1.0, 0.5, -0.5, 1.5, 2.5, -2.5, $el::EPSILON,
+                         $el::MIN_POSITIVE, -$el::MIN_POSITIVE, $el::from_bits(1), -$el::from_bits(1),
+                         $el::MIN_POSITIVE - $el::from_bits(1), $el::MAX, $el::MIN,
+                         $el::INFINITY, $el::NEG_INFINITY, $el::NAN]
+                }
+
+                fn random(rng: &mut Rng) -> Self {
+                    // Arbitrary bits are mostly huge or tiny, so also draw
+                    // values of a moderate magnitude
+                    if rng.next() & 1 == 0 {
+                        $el::from_bits(rng.next() as $bits)
+                    } else {
+                        (rng.next() as i32) as $el / 65536.0
+                    }
+                }
+
+                fn same(self, other: Self) -> bool {
+                    (self.is_nan() && other.is_nan()) || self.to_bits() == other.to_bits()
+                }
+            }
+        )*
+    }
+}
+
+impl_arbitrary_int!(u8, i8, u16, i16, u32, i32, u64, i64, usize);
+impl_arbitrary_float!(f32, u32; f64, u64);
+
+/// Return the operands of each case of an operation on `arity` vectors of
+/// `width` lanes. Every combination of edge cases appears in some lane.
+fn cases<T : Arbitrary>(arity: usize, width: usize) -> Vec<Vec<Vec<T>>> {
+    let mut rng = Rng::new();
+    let edges = T::edges();
+    let mut tuples: Vec<Vec<T>> = vec![Vec::new()];
+    for _ in 0..arity {
+        tuples = tuples.iter().flat_map(|tuple| edges.iter().map(move |&edge| {
+            let mut tuple = tuple.clone();
+            tuple.push(edge);
+            tuple
+        })).collect();
+    }
+
+    // Shuffle the combinations, so horizontal operations see different edges
+    // in neighbouring lanes
+    for i in (1..tuples.len()).rev() {
+        tuples.swap(i, rng.next() as usize % (i + 1));
+    }
+
+    tuples.extend((0..RANDOM_CASES * width).map(|_| (0..arity).map(|_| T::random(&mut rng)).collect()));
+    tuples.chunks(width).map(|chunk| {
+        (0..arity).map(|operand| (0..width).map(|lane| chunk[lane % chunk.len()][operand]).collect()).collect()
+    }).collect()
+}
+
+/// Run `op` on each case of an operation on `arity` vectors of `width` lanes,
+/// and panic with the operands of each lane where `agree` rejects the result
+/// given by `model`.
+fn check<T, U, F, M, A>(name: &str, arity: usize, width: usize, op: F, model: M, agree: A)
+    where T : Arbitrary, U : Arbitrary,
+          F : Fn(&[Vec<T>]) -> Vec<U>, M : Fn(&[Vec<T>]) -> Vec<U>, A : Fn(&[Vec<T>], usize, U, U) -> bool {
+    let mut mismatches = 0;
+    let mut reports = Vec::new();
+    for operands in cases::<T>(arity, width) {
+        let (actual, expected) = (op(&operands), model(&operands));
+        assert_eq!(actual.len(), expected.len(), "{} returned the wrong number of lanes", name);
+        for (lane, (&actual, &expected)) in actual.iter().zip(expected.iter()).enumerate() {
+            if !agree(&operands, lane, actual, expected) {
+                mismatches += 1;
+                if reports.len() < MAX_REPORTED {
+                    reports.push(format!("lane {} of {:?}: expected {:?}, found {:?}",
+                                         lane, operands, expected, actual));
+                }
+            }
+        }
+    }
+    if mismatches > 0 {
+        panic!("{} disagrees with its model in {} lanes:\n{}", name, mismatches, reports.join("\n"));
+    }
+}
+
+fn lanes<V : Packed>(v: V) -> Vec<V::Scalar> {
+    (0..V::WIDTH).map(|i| v.extract(i)).collect()
+}
+
+/// Apply `model` to each lane of the operands.
+fn lanewise<T : Copy, U>(operands: &[Vec<T>], model: impl Fn(&[T]) -> U) -> Vec<U> {
+    (0..operands[0].len()).map(|lane| {
+        model(&operands.iter().map(|operand| operand[lane]).collect::<Vec<T>>())
+    }).collect()
+}
+
+/// Apply `model` to each pair of neighbouring lanes, alternating between the
+/// pairs of the first and second operands, as horizontal operations do.
+fn pairwise<T : Copy, U>(operands: &[Vec<T>], model: impl Fn(T, T) -> U) -> Vec<U> {
+    (0..operands[0].len()).map(|lane| {
+        let pair = lane - lane % 2;
+        model(operands[lane % 2][pair], operands[lane % 2][pair + 1])
+    }).collect()
+}
+
+fn exact<T, U : Arbitrary>(_: &[Vec<T>], _: usize, actual: U, expected: U) -> bool {
+    actual.same(expected)
+}
+
+macro_rules! differential {
+    ($name:ident, [$($vec:ty),*], |$($v:ident),+| $op:expr, $model:expr) => {
+        differential!($name, [$($vec),*], |$($v),+| $op, $model, exact);
+    };
+    ($name:ident, [$($vec:ty),*], |$($v:ident),+| $op:expr, $model:expr, $agree:expr) => {
+        #[test]
+        fn $name() {
+            $(
+                check::<<$vec as Packed>::Scalar, _, _, _, _>(
+                    concat!(stringify!($name), " of ", stringify!($vec)),
+                    [$(stringify!($v)),+].len(), <$vec as Packed>::WIDTH,
+                    |operands| {
+                        let mut operands = operands.iter();
+                        $(let $v = <$vec as Packed>::load(operands.next().unwrap(), 0);)+
+                        $op
+                    },
+                    $model, $agree);
+            )*
+        }
+    }
+}
+
+// Instantiate the tests over a set of vector types. The methods are called
+// through their traits, as the vectors may have inherent methods of the same
+// name.
+macro_rules! differential_suite {
+    ($u8s:ty, $i8s:ty, $u16s:ty, $i16s:ty, $u32s:ty, $i32s:ty, $f32s:ty, $u64s:ty, $i64s:ty, $f64s:ty) => {
+        differential!(abs_int, [$i8s, $i16s, $i32s, $i64s],
+                      |a| lanes(Abs::abs(&a)),
+                      |o| lanewise(o, |x| x[0].wrapping_abs() as _));
+
+        differential!(abs_float, [$f32s, $f64s],
+                      |a| lanes(Abs::abs(&a)),
+                      |o| lanewise(o, |x| x[0].abs()));
+
+        differential!(min_int, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s],
+                      |a, b| lanes(Cmp::min(&a, b)),
+                      |o| lanewise(o, |x| x[0].min(x[1])));
+
+        differential!(max_int, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s],
+                      |a, b| lanes(Cmp::max(&a, b)),
+                      |o| lanewise(o, |x| x[0].max(x[1])));
+
+        // Either operand is fine when one is NaN or they are zeroes of
+        // different signs
+        differential!(min_float, [$f32s, $f64s],
+                      |a, b| lanes(Cmp::min(&a, b)),
+                      |o| lanewise(o, |x| x[0].min(x[1])),
+                      |o, i, actual, expected| actual.same(expected) ||
+                          ((o[0][i].is_nan() || o[1][i].is_nan() || o[0][i] == o[1][i]) &&
+                           (actual.same(o[0][i]) || actual.same(o[1][i]))));
+
+        differential!(max_float, [$f32s, $f64s],
+                      |a, b| lanes(Cmp::max(&a, b)),
+                      |o| lanewise(o, |x| x[0].max(x[1])),
+                      |o, i, actual, expected| actual.same(expected) ||
+                          ((o[0][i].is_nan() || o[1][i].is_nan() || o[0][i] == o[1][i]) &&
+                           (actual.same(o[0][i]) || actual.same(o[1][i]))));
+
+        differential!(saturating_add, [$u8s, $i8s, $u16s, $i16s],
+                      |a, b| lanes(SaturatingAdd::saturating_add(&a, b)),
+                      |o| lanewise(o, |x| x[0].saturating_add(x[1])));
+
+        differential!(saturating_sub, [$u8s, $i8s, $u16s, $i16s],
+                      |a, b| lanes(SaturatingSub::saturating_sub(&a, b)),
+                      |o| lanewise(o, |x| x[0].saturating_sub(x[1])));
+
+        differential!(hadd_int, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $u64s, $i64s],
+                      |a, b| lanes(HAdd::hadd(&a, b)),
+                      |o| pairwise(o, |x, y| x.wrapping_add(y)));
+
+        differential!(hadd_float, [$f32s, $f64s],
+                      |a, b| lanes(HAdd::hadd(&a, b)),
+                      |o| pairwise(o, |x, y| x + y));
+
+        differential!(hsub_int, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $u64s, $i64s],
+                      |a, b| lanes(HSub::hsub(&a, b)),
+                      |o| pairwise(o, |x, y| x.wrapping_sub(y)));
+
+        differential!(hsub_float, [$f32s, $f64s],
+                      |a, b| lanes(HSub::hsub(&a, b)),
+                      |o| pairwise(o, |x, y| x - y));
+
+        differential!(saturating_hadd, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $u64s, $i64s],
+                      |a, b| lanes(SaturatingHAdd::saturating_hadd(&a, b)),
+                      |o| pairwise(o, |x, y| x.saturating_add(y)));
+
+        differential!(saturating_hsub, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $u64s, $i64s],
+                      |a, b| lanes(SaturatingHSub::saturating_hsub(&a, b)),
+                      |o| pairwise(o, |x, y| x.saturating_sub(y)));
+
+        differential!(addsub, [$f32s, $f64s],
+                      |a, b| lanes(AddSub::addsub(&a, b)),
+                      |o| (0..o[0].len()).map(|i| if i % 2 == 0 { o[0][i] - o[1][i] } else { o[0][i] + o[1][i] }).collect());
+
+        // Fusing the multiplication is optional
+        differential!(mul_add, [$f32s, $f64s],
+                      |a, b, c| lanes(MulAdd::mul_add(&a, b, c)),
+                      |o| lanewise(o, |x| x[0].mul_add(x[1], x[2])),
+                      |o, i, actual, expected| actual.same(expected) ||
+                          actual.same(o[0][i] * o[1][i] + o[2][i]));
+
+        // Ties may round either away from zero or to even
+        differential!(round, [$f32s, $f64s],
+                      |a| lanes(Round::round(&a)),
+                      |o| lanewise(o, |x| x[0].round()),
+                      |o, i, actual, expected| actual.same(expected) ||
+                          ((expected - o[0][i]).abs() == 0.5 && (expected - o[0][i].signum()) % 2.0 == 0.0 &&
+                           actual.same((expected - o[0][i].signum()).copysign(o[0][i]))));
+
+        differential!(ceil, [$f32s, $f64s],
+                      |a| lanes(Round::ceil(&a)),
+                      |o| lanewise(o, |x| x[0].ceil()));
+
+        differential!(floor, [$f32s, $f64s],
+                      |a| lanes(Round::floor(&a)),
+                      |o| lanewise(o, |x| x[0].floor()));
+
+        differential!(trunc, [$f32s, $f64s],
+                      |a| lanes(Round::trunc(&a)),
+                      |o| lanewise(o, |x| x[0].trunc()));
+
+        differential!(sqrt, [$f32s, $f64s],
+                      |a| lanes(Sqrt::sqrt(&a)),
+                      |o| lanewise(o, |x| x[0].sqrt()));
+
+        // The reciprocals may be approximations, which are only specified
+        // within a relative error of 1.5 * 2^-12 for normal numbers
+        differential!(recip, [$f32s],
+                      |a| lanes(Recip::recip(&a)),
+                      |o| lanewise(o, |x| 1.0 / x[0]),
+                      |o, i, actual, expected| actual.same(expected) ||
+                          (actual.is_nan() == expected.is_nan() &&
+                           (!o[0][i].is_normal() || !expected.is_normal() ||
+                            ((actual - expected) / expected).abs() <= 1.5 / 4096.0)));
+
+        differential!(rsqrt, [$f32s],
+                      |a| lanes(Rsqrt::rsqrt(&a)),
+                      |o| lanewise(o, |x| 1.0 / x[0].sqrt()),
+                      |o, i, actual, expected| actual.same(expected) ||
+                          (actual.is_nan() == expected.is_nan() &&
+                           (!o[0][i].is_normal() || !expected.is_normal() ||
+                            ((actual - expected) / expected).abs() <= 1.5 / 4096.0)));
+
+        differential!(eq_mask, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $f32s, $u64s, $i64s, $f64s],
+                      |a, b| lanes(crate::intrin::eq::Eq::eq_mask(&a, b)),
+                      |o| lanewise(o, |x| if x[0] == x[1] { !0 } else { 0 }));
+
+        differential!(ne_mask, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $f32s, $u64s, $i64s, $f64s],
+                      |a, b| lanes(crate::intrin::eq::Eq::ne_mask(&a, b)),
+                      |o| lanewise(o, |x| if x[0] != x[1] { !0 } else { 0 }));
+
+        differential!(flip, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $f32s, $u64s, $i64s, $f64s],
+                      |a| lanes(Swizzle::flip(&a)),
+                      |o| (0..o[0].len()).map(|i| o[0][i ^ 1]).collect());
+
+        differential!(merge_halves, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $f32s, $u64s, $i64s, $f64s],
+                      |a, b| lanes(Merge::merge_halves(&a, b)),
+                      |o| (0..o[0].len()).map(|i| o[(i >= o[0].len() / 2) as usize][i]).collect());
+
+        differential!(merge_interleaved, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $f32s, $u64s, $i64s, $f64s],
+                      |a, b| lanes(Merge::merge_interleaved(&a, b)),
+                      |o| (0..o[0].len()).map(|i| o[i % 2][i]).collect());
+
+        differential!(swap_bytes, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s, $u64s, $i64s],
+                      |a| lanes(Reendianize::swap_bytes(&a)),
+                      |o| lanewise(o, |x| x[0].swap_bytes()));
+
+        differential!(sort_lanes, [$u8s, $i8s, $u16s, $i16s, $u32s, $i32s],
+                      |a| lanes(SortLanes::sort_lanes(&a)),
+                      |o| { let mut sorted = o[0].clone(); sorted.sort(); sorted });
+
+        // Indices with their top bit set select zero, and the others select
+        // from the same 128-bit lane of the table
+        differential!(lookup, [$u8s, $i8s],
+                      |a, b| lanes(Lookup::lookup(&a, b)),
+                      |o| (0..o[0].len()).map(|i| {
+                          let index = o[0][i] as u8;
+                          if index & 0x80 != 0 { 0 } else { o[1][i - i % 16 + (index & 0x0F) as usize] }
+                      }).collect());
+
+        differential!(movemask, [$u8s, $i8s],
+                      |a| vec![Movemask::movemask(&a)],
+                      |o| vec![o[0].iter().enumerate().fold(0u64, |mask, (i, &x)| mask | ((x as u8 >> 7) as u64) << i)]);
+
+        differential!(upcast_i8, [$i8s], |a| { let (lo, hi): ($i16s, $i16s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as i16));
+        differential!(upcast_u8, [$u8s], |a| { let (lo, hi): ($u16s, $u16s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as u16));
+        differential!(upcast_i16, [$i16s], |a| { let (lo, hi): ($i32s, $i32s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as i32));
+        differential!(upcast_u16, [$u16s], |a| { let (lo, hi): ($u32s, $u32s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as u32));
+        differential!(upcast_i32, [$i32s], |a| { let (lo, hi): ($i64s, $i64s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as i64));
+        differential!(upcast_u32, [$u32s], |a| { let (lo, hi): ($u64s, $u64s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as u64));
+        differential!(upcast_f32, [$f32s], |a| { let (lo, hi): ($f64s, $f64s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as f64));
+        differential!(upcast_i32_f64, [$i32s], |a| { let (lo, hi): ($f64s, $f64s) = Upcast::upcast(a); [lanes(lo), lanes(hi)].concat() },
+                      |o| lanewise(o, |x| x[0] as f64));
+
+        // Saturating casts of the first operand fill the first half, and those
+        // of the second fill the second half
+        differential!(downcast_i16, [$i16s], |a, b| lanes::<$i8s>(a.saturating_downcast(b)),
+                      |o| [&o[0][..], &o[1][..]].concat().iter().map(|&x| x.max(-0x80).min(0x7F) as i8).collect());
+        differential!(downcast_u16, [$u16s], |a, b| lanes::<$u8s>(a.saturating_downcast(b)),
+                      |o| [&o[0][..], &o[1][..]].concat().iter().map(|&x| x.min(0xFF) as u8).collect());
+        differential!(downcast_i32, [$i32s], |a, b| lanes::<$i16s>(a.saturating_downcast(b)),
+                      |o| [&o[0][..], &o[1][..]].concat().iter().map(|&x| x.max(-0x8000).min(0x7FFF) as i16).collect());
+        differential!(downcast_u32, [$u32s], |a, b| lanes::<$u16s>(a.saturating_downcast(b)),
+                      |o| [&o[0][..], &o[1][..]].concat().iter().map(|&x| x.min(0xFFFF) as u16).collect());
+        differential!(downcast_i64, [$i64s], |a, b| lanes::<$i32s>(a.saturating_downcast(b)),
+                      |o| [&o[0][..], &o[1][..]].concat().iter().map(|&x| x.max(-0x8000_0000).min(0x7FFF_FFFF) as i32).collect());
+        differential!(downcast_u64, [$u64s], |a, b| lanes::<$u32s>(a.saturating_downcast(b)),
+                      |o| [&o[0][..], &o[1][..]].concat().iter().map(|&x| x.min(0xFFFF_FFFF) as u32).collect());
+        differential!(downcast_f64, [$f64s], |a, b| lanes::<$f32s>(a.saturating_downcast(b)),
+                      |o| [&o[0][..], &o[1][..]].concat().iter().map(|&x| x as f32).collect());
+    }
+}
+
+mod native {
+    use super::*;
+    use crate::prelude::*;
+
+    differential_suite!(u8s, i8s, u16s, i16s, u32s, i32s, f32s, u64s, i64s, f64s);
+
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "force-polyfill")))]
+    differential!(count_ones, [u8s, i8s, u16s, i16s, u32s, i32s, u64s, i64s],
+                  |a| vec![Popcnt::count_ones(&a)],
+                  |o| vec![o[0].iter().map(|x| x.count_ones() as usize).sum()]);
+}
+
+#[cfg(all(feature = "polyfill", not(feature = "force-polyfill"),
+          any(target_arch = "x86", target_arch = "x86_64")))]
+mod polyfill {
+    use super::*;
+    use crate::intrin::prelude::*;
+    use crate::arch::polyfill::vecs::*;
+
+    differential_suite!(u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2);
+}
@@ -0,0 +1,77 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub trait Lookup {
+    /// Return a vector where each element is replaced with the element of
+    /// `table` indexed by its low four bits, or with zero if its high bit is
+    /// set. Each group of 16 elements indexes into the corresponding group of
+    /// 16 elements in `table`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let table = u8s::interleave(10, 20);
+    /// assert_eq!(u8s(3).lookup(table), u8s(20));
+    /// assert_eq!(u8s(0x84).lookup(table), u8s(0));
+    /// # }
+    /// ```
+    fn lookup(&self, table: Self) -> Self;
+}
+
+macro_rules! impl_packed_lookup {
+    ($vec:tt, $el:tt, $feat:expr, $mmfn:tt) => {
+        impl Lookup for $vec {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn lookup(&self, table: Self) -> Self {
+                use crate::core::mem::transmute;
+                optimized!();
+                unsafe { transmute($mmfn(transmute(table), transmute(*self))) }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn lookup(&self, table: Self) -> Self {
+                fallback!();
+                let mut ret = *self;
+                for i in 0..Self::WIDTH {
+                    let idx = self.extract(i) as u8;
+                    ret = ret.replace(i, if idx & 0x80 != 0 {
+                        0 as $el
+                    } else {
+                        table.extract(i - i % 16 + (idx & 0x0F) as usize)
+                    });
+                }
+                ret
+            }
+        }
+    }
+}
+
+macro_rules! test_packed_lookup {
+    (($($vec:tt),*), ($($el:tt),*), ($($fn:ident),*)) => {
+        $(
+            #[test]
+            fn $fn() {
+                let mut idx = $vec::splat(0);
+                let mut table = $vec::splat(0);
+                for i in 0..$vec::WIDTH {
+                    idx = idx.replace(i, ((i * 7) % 16) as $el);
+                    table = table.replace(i, (i + 1) as $el);
+                }
+                let ret = idx.lookup(table);
+                for i in 0..$vec::WIDTH {
+                    assert_eq!(ret.extract(i), (i - i % 16 + (i * 7) % 16 + 1) as $el);
+                }
+                assert_eq!($vec::splat(0x80u8 as $el).lookup(table), $vec::splat(0));
+                assert_eq!($vec::splat(0x9Fu8 as $el).lookup(table), $vec::splat(0));
+            }
+        )*
+    }
+}
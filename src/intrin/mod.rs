@@ -6,19 +6,24 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub mod abs;
-pub mod addsub;
-pub mod cast;
+#[macro_use] pub mod addsub;
+#[macro_use] pub mod cast;
 pub mod cmp;
 #[macro_use] pub mod destride;
+#[cfg(all(test, feature = "std"))] mod differential;
 pub mod downcast;
 #[macro_use] pub mod endian;
 #[macro_use] pub mod eq;
 pub mod hadd;
 pub mod hsub;
+#[macro_use] pub mod lookup;
 #[macro_use] pub mod macros;
 #[macro_use] pub mod merge;
+#[macro_use] pub mod movemask;
+#[macro_use] pub mod mul_add;
 #[macro_use] pub mod popcnt;
 pub mod recip;
+pub mod restride;
 pub mod round;
 pub mod rsqrt;
 #[macro_use] pub mod sum;
@@ -26,8 +31,12 @@ pub mod saturating_add;
 pub mod saturating_hadd;
 pub mod saturating_hsub;
 pub mod saturating_sub;
+#[macro_use] pub mod sort;
 pub mod sqrt;
+#[macro_use] pub mod stream;
+#[macro_use] pub mod swizzle;
 #[macro_use] pub mod transmute;
+#[macro_use] pub mod transpose;
 pub mod upcast;
 
 // We use an internal prelude not to clutter the namespace when we import
@@ -43,9 +52,13 @@ pub(crate) mod prelude {
     pub use super::eq::*;
     pub use super::hadd::*;
     pub use super::hsub::*;
+    pub use super::lookup::*;
     pub use super::merge::*;
+    pub use super::movemask::*;
+    pub use super::mul_add::*;
     pub use super::popcnt::*;
     pub use super::recip::*;
+    pub use super::restride::*;
     pub use super::round::*;
     pub use super::rsqrt::*;
     pub use super::sum::*;
@@ -53,7 +66,11 @@ pub(crate) mod prelude {
     pub use super::saturating_hadd::*;
     pub use super::saturating_hsub::*;
     pub use super::saturating_sub::*;
+    pub use super::sort::*;
     pub use super::sqrt::*;
+    pub use super::stream::*;
+    pub use super::swizzle::*;
     pub use super::transmute::*;
+    pub use super::transpose::*;
     pub use super::upcast::*;
 }
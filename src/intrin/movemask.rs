@@ -0,0 +1,69 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub trait Movemask {
+    /// Return an integer whose `n`th bit is the most significant bit of the
+    /// `n`th element of this vector. Bits past the width of the vector are
+    /// zero.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u8s(0x80).movemask(), !0u64 >> (64 - u8s::WIDTH));
+    /// assert_eq!(u8s::interleave(0xFF, 0x7F).movemask(), 0x5555_5555_5555_5555 >> (64 - u8s::WIDTH));
+    /// # }
+    /// ```
+    fn movemask(&self) -> u64;
+}
+
+macro_rules! impl_packed_movemask {
+    ($vec:tt, $uint:tt, $feat:expr, $mmfn:tt) => {
+        impl Movemask for $vec {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn movemask(&self) -> u64 {
+                use crate::core::mem::transmute;
+                optimized!();
+                unsafe { $mmfn(transmute(*self)) as $uint as u64 }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn movemask(&self) -> u64 {
+                fallback!();
+                (0..Self::WIDTH).fold(0, |mask, i| {
+                    mask | ((self.extract(i) as u8 >> 7) as u64) << i
+                })
+            }
+        }
+    }
+}
+
+macro_rules! test_packed_movemask {
+    (($($vec:tt),*), ($($el:tt),*), ($($fn:ident),*)) => {
+        $(
+            #[test]
+            fn $fn() {
+                let mut v = $vec::splat(0);
+                let mut expected = 0u64;
+                for i in 0..$vec::WIDTH {
+                    if i % 3 == 0 || i % 7 == 0 {
+                        v = v.replace(i, (0x80 | i) as u8 as $el);
+                        expected |= 1 << i;
+                    } else {
+                        v = v.replace(i, (0x7F & i) as $el);
+                    }
+                }
+                assert_eq!(v.movemask(), expected);
+                assert_eq!($vec::splat(0).movemask(), 0);
+                assert_eq!($vec::splat(0xFFu8 as $el).movemask(), !0u64 >> (64 - $vec::WIDTH));
+            }
+        )*
+    }
+}
@@ -0,0 +1,58 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub trait MulAdd {
+    /// Return a vector containing the product of the elements of `self` and
+    /// `mul`, added to the elements of `add`. This is computed with a single
+    /// rounding if the CPU supports fused multiply-add, and with two roundings
+    /// otherwise.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(MulAdd::mul_add(&f32s(3.0), f32s(2.0), f32s(1.0)), f32s(7.0));
+    /// # }
+    /// ```
+    fn mul_add(&self, mul: Self, add: Self) -> Self;
+}
+
+macro_rules! impl_packed_mul_add {
+    ($vec:tt, $feat:expr, $mmfn:tt) => {
+        impl MulAdd for $vec {
+            #[inline(always)]
+            #[cfg(target_feature = $feat)]
+            fn mul_add(&self, mul: Self, add: Self) -> Self {
+                use crate::core::mem::transmute;
+                optimized!();
+                unsafe { transmute($mmfn(transmute(*self), transmute(mul), transmute(add))) }
+            }
+
+            #[inline(always)]
+            #[cfg(not(target_feature = $feat))]
+            fn mul_add(&self, mul: Self, add: Self) -> Self {
+                fallback!();
+                *self * mul + add
+            }
+        }
+    }
+}
+
+macro_rules! test_packed_mul_add {
+    (($($vec:tt),*), ($($fn:tt),*)) => {
+        $(
+            #[test]
+            fn $fn() {
+                let a = $vec::interleave(3.0, -5.0);
+                let b = $vec::interleave(2.0, 0.5);
+                assert_eq!(MulAdd::mul_add(&a, b, $vec::splat(1.0)), $vec::interleave(7.0, -1.5));
+                assert_eq!(MulAdd::mul_add(&a, $vec::splat(0.0), b), b);
+            }
+        )*
+    }
+}
@@ -0,0 +1,132 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::*;
+
+/// The inverse of `Destride`: operations which interleave the elements of
+/// several vectors, such that storing the returned vectors contiguously
+/// produces an array of structures.
+pub trait Restride : Packed {
+    /// Return two vectors containing the elements of `self` interleaved with
+    /// the elements of `other`, starting with the first element of `self`.
+    /// The first vector contains the first half of the interleaved elements,
+    /// and the second vector contains the second half.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u8s(1).interleave_two(u8s(2)), (u8s::interleave(1, 2), u8s::interleave(1, 2)));
+    /// assert_eq!(u8s::halfs(1, 2).interleave_two(u8s(3)), (u8s::interleave(1, 3), u8s::interleave(2, 3)));
+    /// # }
+    /// ```
+    fn interleave_two(self, other: Self) -> (Self, Self);
+
+    /// Return three vectors containing the elements of `self`, `b`, and `c`
+    /// interleaved, starting with the first element of `self`. Storing the
+    /// returned vectors contiguously produces `[a0, b0, c0, a1, b1, c1...]`.
+    #[inline(always)]
+    fn interleave_three(self, b: Self, c: Self) -> (Self, Self, Self) {
+        fallback!();
+        let mut out = [self; 3];
+        interleave_polyfill(&[self, b, c], &mut out);
+        (out[0], out[1], out[2])
+    }
+
+    /// Return four vectors containing the elements of `self`, `b`, `c`, and
+    /// `d` interleaved, starting with the first element of `self`. Storing the
+    /// returned vectors contiguously produces
+    /// `[a0, b0, c0, d0, a1, b1, c1, d1...]`.
+    #[inline(always)]
+    fn interleave_four(self, b: Self, c: Self, d: Self) -> (Self, Self, Self, Self) {
+        // Interleaving [a, c] and [b, d] gives us [a0, c0, a1, c1...] and
+        // [b0, d0, b1, d1...], which interleave into [a0, b0, c0, d0...]
+        let (ac_lo, ac_hi) = self.interleave_two(c);
+        let (bd_lo, bd_hi) = b.interleave_two(d);
+        let (w, x) = ac_lo.interleave_two(bd_lo);
+        let (y, z) = ac_hi.interleave_two(bd_hi);
+        (w, x, y, z)
+    }
+}
+
+/// Interleave the vectors in `src` into `dst` one element at a time, such
+/// that reading `dst` sequentially yields `src[0][0], src[1][0], ...,
+/// src[0][1], src[1][1]...`. Both slices must be of the same length.
+#[inline(always)]
+pub(crate) fn interleave_polyfill<V>(src: &[V], dst: &mut [V]) where V : Packed {
+    debug_assert_eq!(src.len(), dst.len());
+    let count = src.len();
+    for i in 0..(count * V::WIDTH) {
+        unsafe {
+            dst[i / V::WIDTH] = dst[i / V::WIDTH].replace_unchecked(
+                i % V::WIDTH, src[i % count].extract_unchecked(i / count));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    macro_rules! test_restride {
+        ($vec:tt, $el:tt, $name:ident) => {
+            #[test]
+            fn $name() {
+                let a = (0..$vec::WIDTH).fold($vec::splat(0 as $el), |v, i| v.replace(i, i as $el));
+                let b = a + $vec::splat(($vec::WIDTH * 1) as $el);
+                let c = a + $vec::splat(($vec::WIDTH * 2) as $el);
+                let d = a + $vec::splat(($vec::WIDTH * 3) as $el);
+
+                // Check the layout against a scalar interleave
+                let mut out = [0 as $el; 4 * $vec::WIDTH];
+                let (w, x) = a.interleave_two(b);
+                w.store(&mut out, 0);
+                x.store(&mut out, $vec::WIDTH);
+                for i in 0..(2 * $vec::WIDTH) {
+                    assert_eq!(out[i], [a, b][i % 2].extract(i / 2));
+                }
+
+                let (w, x, y) = a.interleave_three(b, c);
+                w.store(&mut out, 0);
+                x.store(&mut out, $vec::WIDTH);
+                y.store(&mut out, $vec::WIDTH * 2);
+                for i in 0..(3 * $vec::WIDTH) {
+                    assert_eq!(out[i], [a, b, c][i % 3].extract(i / 3));
+                }
+
+                let (w, x, y, z) = a.interleave_four(b, c, d);
+                w.store(&mut out, 0);
+                x.store(&mut out, $vec::WIDTH);
+                y.store(&mut out, $vec::WIDTH * 2);
+                z.store(&mut out, $vec::WIDTH * 3);
+                for i in 0..(4 * $vec::WIDTH) {
+                    assert_eq!(out[i], [a, b, c, d][i % 4].extract(i / 4));
+                }
+            }
+        }
+    }
+
+    test_restride!(u8s, u8, restride_u8s);
+    test_restride!(i8s, i8, restride_i8s);
+    test_restride!(u16s, u16, restride_u16s);
+    test_restride!(i16s, i16, restride_i16s);
+    test_restride!(u32s, u32, restride_u32s);
+    test_restride!(i32s, i32, restride_i32s);
+    test_restride!(f32s, f32, restride_f32s);
+    test_restride!(u64s, u64, restride_u64s);
+    test_restride!(i64s, i64, restride_i64s);
+    test_restride!(f64s, f64, restride_f64s);
+
+    #[test]
+    fn restride_inverts_destride() {
+        let a = (0..u8s::WIDTH).fold(u8s(0), |v, i| v.replace(i, i as u8));
+        let b = a + u8s(u8s::WIDTH as u8);
+        let (lo, hi) = a.interleave_two(b);
+        assert_eq!(lo.destride_two(hi), (a, b));
+    }
+}
@@ -0,0 +1,85 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub trait SortLanes : Sized {
+    /// Return a vector containing the elements of this vector in ascending
+    /// order. The elements are sorted with a bitonic network, so the order of
+    /// floats which don't compare (NaNs) is unspecified.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// assert_eq!(u32s::interleave(3, 1).sort_lanes(), u32s::halfs(1, 3));
+    /// assert_eq!(i8s::halfs(5, -5).sort_lanes(), i8s::halfs(-5, 5));
+    /// # }
+    /// ```
+    fn sort_lanes(&self) -> Self;
+
+    /// Given two vectors whose elements are in ascending order, return a
+    /// vector of the smallest elements of both and a vector of the largest
+    /// elements of both, each in ascending order.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let (lo, hi) = u32s::halfs(1, 3).merge_sorted(u32s::halfs(2, 4));
+    /// assert_eq!(lo, u32s::halfs(1, 2));
+    /// assert_eq!(hi, u32s::halfs(3, 4));
+    /// # }
+    /// ```
+    fn merge_sorted(&self, other: Self) -> (Self, Self);
+}
+
+macro_rules! test_packed_sort_lanes {
+    (($($vec:tt),*), ($($el:tt),*), ($($fn:ident),*)) => {
+        $(
+            #[test]
+            fn $fn() {
+                let mut rng = crate::testing::Rng::new();
+                // Keep the range small enough for plenty of duplicates
+                let mut next = || (rng.next() % 97) as $el;
+                let sorted = |v: $vec| {
+                    let mut lanes = [0 as $el; 64];
+                    for i in 0..$vec::WIDTH {
+                        lanes[i] = v.extract(i);
+                    }
+                    lanes[..$vec::WIDTH].sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+                    lanes
+                };
+                for _ in 0..100 {
+                    let (mut a, mut b) = ($vec::splat(0 as $el), $vec::splat(0 as $el));
+                    for i in 0..$vec::WIDTH {
+                        a = a.replace(i, next());
+                        b = b.replace(i, next());
+                    }
+                    let expected = sorted(a);
+                    let a = a.sort_lanes();
+                    let b = b.sort_lanes();
+                    for i in 0..$vec::WIDTH {
+                        assert_eq!(a.extract(i), expected[i]);
+                    }
+
+                    let (lo, hi) = a.merge_sorted(b);
+                    let mut expected = [0 as $el; 128];
+                    for i in 0..$vec::WIDTH {
+                        expected[i] = a.extract(i);
+                        expected[i + $vec::WIDTH] = b.extract(i);
+                    }
+                    expected[..$vec::WIDTH * 2].sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+                    for i in 0..$vec::WIDTH {
+                        assert_eq!(lo.extract(i), expected[i]);
+                        assert_eq!(hi.extract(i), expected[i + $vec::WIDTH]);
+                    }
+                }
+            }
+        )*
+    }
+}
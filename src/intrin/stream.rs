@@ -0,0 +1,109 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::Packed;
+
+pub trait Stream : Packed {
+    /// Write `Self::WIDTH` elements from this vector to `data`, beginning at
+    /// `offset`, without bringing the destination into the cache. The
+    /// destination must be aligned to the alignment of this vector, and the
+    /// stores aren't ordered with respect to other stores until
+    /// `Self::stream_fence` is called.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let mut buf = [u32s(0); 2];
+    /// let data = unsafe {
+    ///     std::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u32, u32s::WIDTH * 2)
+    /// };
+    /// unsafe { u32s(7).stream_unchecked(data, u32s::WIDTH); }
+    /// u32s::stream_fence();
+    /// assert_eq!(data[u32s::WIDTH], 7);
+    /// # }
+    /// ```
+    unsafe fn stream_unchecked(self, data: &mut [Self::Scalar], offset: usize);
+
+    /// Wait until every streaming store is visible before any later store.
+    fn stream_fence();
+}
+
+macro_rules! impl_packed_stream {
+    (plain, $($vec:tt),*) => {
+        $(
+            impl Stream for $vec {
+                #[inline(always)]
+                unsafe fn stream_unchecked(self, data: &mut [Self::Scalar], offset: usize) {
+                    fallback!();
+                    self.store_unchecked(data, offset);
+                }
+
+                #[inline(always)]
+                fn stream_fence() {}
+            }
+        )*
+    };
+    ($feat:expr, $mmfn:tt, $fence:tt, $($vec:tt),*) => {
+        $(
+            impl Stream for $vec {
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                unsafe fn stream_unchecked(self, data: &mut [Self::Scalar], offset: usize) {
+                    use crate::core::mem::transmute;
+                    debug_assert!(data[offset..].len() >= Self::WIDTH);
+                    debug_assert!(data[offset..].as_ptr() as usize % crate::core::mem::align_of::<Self>() == 0);
+                    optimized!();
+                    $mmfn(data.as_mut_ptr().add(offset) as *mut _, transmute(self));
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                unsafe fn stream_unchecked(self, data: &mut [Self::Scalar], offset: usize) {
+                    fallback!();
+                    self.store_aligned_unchecked(data, offset);
+                }
+
+                #[inline(always)]
+                #[cfg(target_feature = $feat)]
+                fn stream_fence() {
+                    unsafe { $fence() }
+                }
+
+                #[inline(always)]
+                #[cfg(not(target_feature = $feat))]
+                fn stream_fence() {}
+            }
+        )*
+    };
+}
+
+macro_rules! test_packed_stream {
+    (($($vec:tt),*), ($($el:tt),*), ($($name:ident),*)) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut buf = [$vec::splat(0 as $el); 3];
+                let data = unsafe {
+                    crate::core::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut $el, $vec::WIDTH * 3)
+                };
+                let mut v = $vec::splat(0 as $el);
+                for i in 0..$vec::WIDTH {
+                    v = v.replace(i, (i + 1) as $el);
+                }
+                unsafe { v.stream_unchecked(data, $vec::WIDTH); }
+                $vec::stream_fence();
+                for i in 0..$vec::WIDTH {
+                    assert_eq!(data[i], 0 as $el);
+                    assert_eq!(data[i + $vec::WIDTH], (i + 1) as $el);
+                    assert_eq!(data[i + $vec::WIDTH * 2], 0 as $el);
+                }
+            }
+        )*
+    }
+}
@@ -0,0 +1,123 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::Packed;
+
+pub trait Transpose4x4 : Sized {
+    /// Transpose the 4x4 matrix whose rows are `rows`, returning its columns.
+    /// Vectors with more than four elements are treated as several 4x4
+    /// matrices side-by-side, each of which is transposed independently.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let rows = [f32s(1.0), f32s(2.0), f32s(3.0), f32s(4.0)];
+    /// let cols = f32s::transpose4x4(rows);
+    /// assert_eq!(cols[0].extract(0), 1.0);
+    /// assert_eq!(cols[0].extract(3), 4.0);
+    /// # }
+    /// ```
+    fn transpose4x4(rows: [Self; 4]) -> [Self; 4];
+}
+
+pub trait Transpose8x8 : Sized {
+    /// Transpose the 8x8 matrix whose rows are `rows`, returning its columns.
+    /// Vectors with more than eight elements are treated as several 8x8
+    /// matrices side-by-side, each of which is transposed independently.
+    fn transpose8x8(rows: [Self; 8]) -> [Self; 8];
+}
+
+pub trait Transpose16x16 : Sized {
+    /// Transpose the 16x16 matrix whose rows are `rows`, returning its
+    /// columns. Vectors with more than sixteen elements are treated as several
+    /// 16x16 matrices side-by-side, each of which is transposed independently.
+    fn transpose16x16(rows: [Self; 16]) -> [Self; 16];
+}
+
+/// Transpose each `rows.len()`-square block of `rows` into `out`, one element
+/// at a time.
+#[inline(always)]
+pub(crate) fn transpose_polyfill<V : Packed>(rows: &[V], out: &mut [V]) {
+    let n = rows.len();
+    debug_assert_eq!(out.len(), n);
+    debug_assert_eq!(V::WIDTH % n, 0);
+    for block in (0..V::WIDTH).step_by(n) {
+        for (r, row) in rows.iter().enumerate() {
+            for (c, col) in out.iter_mut().enumerate() {
+                unsafe {
+                    *col = col.replace_unchecked(block + r, row.extract_unchecked(block + c));
+                }
+            }
+        }
+    }
+}
+
+/// Transpose an array of `$n` rows, each of which is exactly `$n` elements
+/// wide.
+///
+/// Interleaving rows `i` and `i + n / 2` into rows `2i` and `2i + 1` for
+/// log2(n) rounds transposes a square matrix. As a transpose is its own
+/// inverse, so does destriding rows `2i` and `2i + 1` into rows `i` and
+/// `i + n / 2` for the same number of rounds.
+macro_rules! transpose_destride {
+    ($rows:expr, $n:expr) => {{
+        let mut rows = $rows;
+        let mut round = 1;
+        while round < $n {
+            let prev = rows;
+            for i in 0..$n / 2 {
+                let (lo, hi) = prev[2 * i].destride_two(prev[2 * i + 1]);
+                rows[i] = lo;
+                rows[i + $n / 2] = hi;
+            }
+            round *= 2;
+        }
+        rows
+    }}
+}
+
+macro_rules! impl_transpose_polyfill {
+    ($trait:ident, $fn:ident, $n:expr, $($vec:ty),*) => {
+        $(
+            impl $trait for $vec {
+                #[inline(always)]
+                fn $fn(rows: [Self; $n]) -> [Self; $n] {
+                    fallback!();
+                    let mut out = rows;
+                    transpose_polyfill(&rows, &mut out);
+                    out
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! test_packed_transpose {
+    ($fn:ident, $n:expr, ($($vec:tt),*), ($($name:ident),*)) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut rows = [$vec::splat(0 as <$vec as Packed>::Scalar); $n];
+                for (r, row) in rows.iter_mut().enumerate() {
+                    for i in 0..$vec::WIDTH {
+                        *row = row.replace(i, (r * $vec::WIDTH + i) as <$vec as Packed>::Scalar);
+                    }
+                }
+                let cols = $vec::$fn(rows);
+                for (c, col) in cols.iter().enumerate() {
+                    for i in 0..$vec::WIDTH {
+                        let (block, r) = (i - i % $n, i % $n);
+                        assert_eq!(col.extract(i), (r * $vec::WIDTH + block + c) as <$vec as Packed>::Scalar);
+                    }
+                }
+                assert_eq!($vec::$fn(cols), rows);
+            }
+        )*
+    }
+}
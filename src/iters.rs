@@ -6,7 +6,13 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::vecs::{Packable, Packed};
-use crate::core::slice::from_raw_parts;
+use crate::intrin::stream::Stream;
+use crate::error::Error;
+use crate::arch::current::prefetch::prefetch;
+use crate::core::mem::align_of;
+use crate::core::slice::from_raw_parts_mut;
+use crate::core::marker::PhantomData;
+use crate::core::ops::{Deref, DerefMut};
 
 pub trait SIMDObject : Sized {
     type Scalar : Packable;
@@ -59,13 +65,39 @@ pub trait SIMDIterable : SIMDObject + SIMDSized + ExactSizeIterator<Item = <Self
     }
 
     #[inline(always)]
-    /// Create an iterator which returns `amt` vectors at a time.
-    fn unroll<'a>(&'a mut self, amt: usize) -> Unrolled<'a, Self> {
-        assert!(amt <= 8);
+    /// Create an iterator which returns `N` vectors at a time. Any vectors
+    /// after the last group of `N` are left in [`Unrolled::tail`].
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let data = [1u32; 100];
+    /// let mut iter = data.simd_iter(u32s(0));
+    /// let mut unrolled = iter.unroll::<2>();
+    /// let mut accs = [u32s(0); 2];
+    /// for vs in &mut unrolled {
+    ///     accs[0] = accs[0] + vs[0];
+    ///     accs[1] = accs[1] + vs[1];
+    /// }
+    /// for v in unrolled.tail() {
+    ///     accs[0] = accs[0] + *v;
+    /// }
+    /// if let Some((v, _)) = unrolled.end() {
+    ///     accs[1] = accs[1] + v;
+    /// }
+    /// assert_eq!((accs[0] + accs[1]).sum(), 100);
+    /// # }
+    /// ```
+    ///
+    /// [`Unrolled::tail`]: struct.Unrolled.html#method.tail
+    fn unroll<const N: usize>(&mut self) -> Unrolled<'_, Self, N> {
+        let default = self.default();
         Unrolled {
             iter: self,
-            amt: amt,
-            scratch: [<Self as SIMDObject>::Vector::default(); 8]
+            tail: [default; N],
+            tail_len: 0,
         }
     }
 }
@@ -165,6 +197,53 @@ pub trait SIMDIterator : SIMDIterable {
         }
         start
     }
+
+    #[inline(always)]
+    /// Return the result of reducing `func` over the values of this
+    /// iterator, as with `simd_reduce`, but with an independent accumulator
+    /// for each element of `start`. The `n`th accumulator is given every
+    /// `N`th vector of the iterator, so up to `N` calls of `func` may run at
+    /// once rather than waiting on each other. The accumulators are then
+    /// merged in order with `combine`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let sum = [3u32; 1000].simd_iter(u32s(0))
+    ///     .simd_reduce_unrolled([u32s(0); 4], |acc, v| acc + v, |a, b| a + b)
+    ///     .sum();
+    /// assert_eq!(sum, 3000);
+    /// # }
+    /// ```
+    ///
+    /// # Footgun Warning
+    ///
+    /// Each element of `start` is an initial accumulator, so it should
+    /// usually be an identity of `func`. The vectors are grouped differently
+    /// than with `simd_reduce`, and differently for each `N`, so the results
+    /// of reductions which aren't associative (such as floating-point sums)
+    /// may differ slightly between them.
+    fn simd_reduce_unrolled<A, F, G, const N: usize>(&mut self, start: [A; N], mut func: F, mut combine: G) -> A
+        where A : Copy, F : FnMut(A, Self::Vector) -> A, G : FnMut(A, A) -> A {
+        assert!(N > 0);
+        let mut accs = start;
+        let mut unrolled = self.unroll::<N>();
+        for vs in &mut unrolled {
+            for (acc, v) in accs.iter_mut().zip(vs.iter()) {
+                *acc = func(*acc, *v);
+            }
+        }
+        let tail_len = unrolled.tail().len();
+        for (acc, v) in accs.iter_mut().zip(unrolled.tail().iter()) {
+            *acc = func(*acc, *v);
+        }
+        if let Some((v, _)) = unrolled.end() {
+            accs[tail_len] = func(accs[tail_len], v);
+        }
+        accs[1..].iter().fold(accs[0], |a, b| combine(a, *b))
+    }
 }
 
 /// A trait defining a SIMD iterator over a mutable blob of primitive data
@@ -192,6 +271,43 @@ pub trait SIMDArray : SIMDObject + SIMDSized {
     unsafe fn load_unchecked(&self, offset: usize) -> Self::Vector;
     fn load_scalar(&self, offset: usize) -> Self::Scalar;
     unsafe fn load_scalar_unchecked(&self, offset: usize) -> Self::Scalar;
+
+    /// Hint that the scalar at `offset` will be loaded soon, so its cache
+    /// line is fetched into the caches chosen by `locality`. The offset
+    /// needn't be in bounds. Arrays which aren't backed by memory ignore
+    /// this.
+    #[inline(always)]
+    fn prefetch_scalar(&self, _offset: usize, _locality: Locality) {}
+}
+
+/// How soon and how often prefetched data is expected to be used, which
+/// decides the caches it is fetched into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locality {
+    /// Used once, and fetched so as to disturb the caches as little as
+    /// possible.
+    None,
+    /// Fetched into the last level cache.
+    Low,
+    /// Fetched into the second level cache and below.
+    Medium,
+    /// Fetched into every level of cache.
+    High,
+}
+
+/// How far ahead of its loads an iterator prefetches, in vectors, and into
+/// which caches. A distance of zero disables prefetching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Prefetch {
+    pub distance: usize,
+    pub locality: Locality,
+}
+
+impl Default for Prefetch {
+    #[inline(always)]
+    fn default() -> Self {
+        Prefetch { distance: 0, locality: Locality::High }
+    }
 }
 
 /// A trait defining a random-access mutable blob of data which can be loaded
@@ -210,6 +326,87 @@ pub struct SIMDIter<A : SIMDArray> {
     pub position: usize,
     pub data: A,
     pub default: A::Vector,
+    pub(crate) prefetch: Prefetch,
+}
+
+impl<A> SIMDIter<A> where A : SIMDArray {
+    /// Return an iterator over `data` from its first element, which fills the
+    /// unused lanes of its last vector from `default` and doesn't prefetch.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let data = [1u32; 100];
+    /// let sum = SIMDIter::new(PackedAs::new(&data[..]), u32s(0))
+    ///     .simd_reduce(u32s(0), |acc, v| acc + v)
+    ///     .sum();
+    /// assert_eq!(sum, 100);
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn new(data: A, default: A::Vector) -> Self {
+        SIMDIter { position: 0, data, default, prefetch: Prefetch::default() }
+    }
+
+    /// Prefetch the data `distance` vectors ahead of each vector loaded by
+    /// this iterator into the caches chosen by `locality`. This may speed up
+    /// scans of data much larger than the cache, if the hardware prefetcher
+    /// doesn't already keep up with them.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let sum = [1u32; 100].simd_iter(u32s(0))
+    ///     .prefetch(8, Locality::None)
+    ///     .simd_reduce(u32s(0), |acc, v| acc + v)
+    ///     .sum();
+    /// assert_eq!(sum, 100);
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn prefetch(mut self, distance: usize, locality: Locality) -> Self {
+        self.prefetch = Prefetch { distance, locality };
+        self
+    }
+}
+
+/// A collection of scalars which is loaded into and stored from vectors of
+/// type `V`, rather than the native vector type of its scalars. This allows a
+/// collection to be iterated over with vectors of an explicit width.
+#[derive(Clone, Debug)]
+pub struct PackedAs<A, V> {
+    pub data: A,
+    pub vector: PhantomData<V>,
+}
+
+impl<A, V> PackedAs<A, V> {
+    #[inline(always)]
+    pub fn new(data: A) -> Self {
+        PackedAs {
+            data: data,
+            vector: PhantomData,
+        }
+    }
+}
+
+impl<A, S, V> Deref for PackedAs<A, V> where A : Deref<Target = [S]> {
+    type Target = [S];
+
+    #[inline(always)]
+    fn deref(&self) -> &[S] {
+        &self.data
+    }
+}
+
+impl<A, S, V> DerefMut for PackedAs<A, V> where A : DerefMut<Target = [S]> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [S] {
+        &mut self.data
+    }
 }
 
 /// A lazy mapping iterator which applies its function to a stream of vectors.
@@ -359,45 +556,64 @@ impl<T> Unpacked<T> where T : SIMDIterable {
     }
 }
 
-/// An iterator which yields multiple elements of a PackedIter
+/// An iterator which yields `N` vectors of a SIMDIterable at a time.
 #[derive(Debug)]
-pub struct Unrolled<'a, T : 'a + SIMDIterable> {
+pub struct Unrolled<'a, T : 'a + SIMDIterable, const N: usize> {
     iter: &'a mut T,
-    amt: usize,
-    scratch: [T::Vector; 8],
+    tail: [T::Vector; N],
+    tail_len: usize,
 }
 
-impl<'a, T> Unrolled<'a, T> where T : 'a + SIMDIterable {
+impl<'a, T, const N: usize> Unrolled<'a, T, N> where T : 'a + SIMDIterable {
     #[inline(always)]
     pub fn chunk_len(&self) -> usize {
-        self.amt
+        N
     }
 
     #[inline(always)]
     pub fn chunk_pos(&self) -> usize {
         self.iter.vector_pos() / self.chunk_len()
     }
+
+    /// Return the whole vectors after the last group of `N` vectors, once
+    /// this iterator has returned `None`.
+    #[inline(always)]
+    pub fn tail(&self) -> &[T::Vector] {
+        &self.tail[..self.tail_len]
+    }
 }
 
-impl<'a, T> Iterator for Unrolled<'a, T> where T : 'a + SIMDIterator {
-    type Item = &'a [T::Vector];
+impl<'a, T, const N: usize> Unrolled<'a, T, N> where T : 'a + SIMDIterator {
+    /// Return the partially full vector after the tail, as with
+    /// `SIMDIterator::end`.
+    #[inline(always)]
+    pub fn end(&mut self) -> Option<(T::Vector, usize)> {
+        self.iter.end()
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Unrolled<'a, T, N> where T : 'a + SIMDIterable {
+    type Item = [T::Vector; N];
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        let mut i = 0;
-        while i < self.amt {
-            if let Some(vec) = self.iter.next() {
-                self.scratch[i] = vec;
-                i += 1;
-            } else {
-                break;
+        let mut ret = self.tail;
+        let mut len = 0;
+        while len < N {
+            match self.iter.next() {
+                Some(vec) => ret[len] = vec,
+                None => break,
             }
+            len += 1;
         }
-        if i > 0 {
-            unsafe { // TODO: Is this unsafe? Contravariant lifetimes?
-                Some(from_raw_parts((&mut self.scratch).as_mut_ptr(), i))
-            }
+        if len == N {
+            Some(ret)
         } else {
+            if len > 0 {
+                // Keep the incomplete group for `tail`
+                self.tail = ret;
+                self.tail_len = len;
+            }
             None
         }
     }
@@ -432,6 +648,11 @@ macro_rules! impl_iter {
                 debug_assert!(offset < self.len());
                 *self.get_unchecked(offset)
             }
+
+            #[inline(always)]
+            fn prefetch_scalar(&self, offset: usize, locality: Locality) {
+                prefetch(self.as_ptr().wrapping_add(offset), locality)
+            }
         }
 
         impl< $($genera),* > SIMDSized for $name $($pred )* {
@@ -447,6 +668,30 @@ macro_rules! impl_iter {
 impl_iter!(Vec<S>, ('a, S, V) where S : Packable<Vector = V>, V : Packed<Scalar = S>);
 impl_iter!(&'a [S], ('a, S, V) where S : Packable<Vector = V>, V : Packed<Scalar = S>);
 impl_iter!(&'a mut [S], ('a, S, V) where S : Packable<Vector = V>, V : Packed<Scalar = S>);
+impl_iter!(PackedAs<A, V>, (A, S, V) where A : Deref<Target = [S]>, S : Packable, V : Packed<Scalar = S>);
+
+impl<A, S, V> SIMDArrayMut for PackedAs<A, V> where A : DerefMut<Target = [S]>, S : Packable, V : Packed<Scalar = S> {
+    #[inline(always)]
+    fn store(&mut self, value: Self::Vector, offset: usize) {
+        value.store(self, offset)
+    }
+
+    #[inline(always)]
+    unsafe fn store_unchecked(&mut self, value: Self::Vector, offset: usize) {
+        value.store_unchecked(self, offset)
+    }
+
+    #[inline(always)]
+    fn store_scalar(&mut self, value: Self::Scalar, offset: usize) {
+        self[offset] = value;
+    }
+
+    #[inline(always)]
+    unsafe fn store_scalar_unchecked(&mut self, value: Self::Scalar, offset: usize) {
+        debug_assert!(offset < self.len());
+        *self.get_unchecked_mut(offset) = value;
+    }
+}
 
 impl<A> SIMDObject for SIMDIter<A> where A : SIMDArray, A::Vector : Packed, A::Scalar : Packable {
     type Vector = A::Vector;
@@ -466,6 +711,10 @@ impl<A> Iterator for SIMDIter<A> where A : SIMDArray, A::Vector : Packed, A::Sca
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
         if self.position + self.width() <= self.scalar_len() {
+            if self.prefetch.distance != 0 {
+                let ahead = self.position.saturating_add(self.prefetch.distance.saturating_mul(self.width()));
+                self.data.prefetch_scalar(ahead, self.prefetch.locality);
+            }
             let ret = unsafe { self.load_unchecked(self.position) };
             let width = self.width(); // Appease borrow checker
             self.advance(width);
@@ -496,6 +745,11 @@ impl<A> SIMDArray for SIMDIter<A> where A : SIMDArray, A::Vector : Packed, A::Sc
     unsafe fn load_scalar_unchecked(&self, offset: usize) -> Self::Scalar {
         self.data.load_scalar_unchecked(offset)
     }
+
+    #[inline(always)]
+    fn prefetch_scalar(&self, offset: usize, locality: Locality) {
+        self.data.prefetch_scalar(offset, locality)
+    }
 }
 
 impl<A> SIMDSized for SIMDIter<A> where A : SIMDArray, A::Vector : Packed, A::Scalar : Packable {
@@ -684,8 +938,29 @@ pub trait IntoScalar<T> : SIMDObject where T : Packable {
     fn scalar_collect(&mut self) -> Vec<T>;
 
     /// Take an iterator of SIMD vectors and store them in-order in `fill`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is too short to hold every element.
     fn scalar_fill<'a>(&mut self, fill: &'a mut [T]) -> &'a mut [T];
 
+    /// Take an iterator of SIMD vectors and store them in-order in `fill`,
+    /// or return an error if `fill` is too short to hold every element. The
+    /// iterator and `fill` are left partially consumed and written if an
+    /// error is returned.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let data = [1u8; 100];
+    /// let mut fill = [0u8; 99];
+    /// assert!(data.simd_iter(u8s(0)).try_scalar_fill(&mut fill).is_err());
+    /// # }
+    /// ```
+    fn try_scalar_fill<'a>(&mut self, fill: &'a mut [T]) -> Result<&'a mut [T], Error>;
+
     /// Take an iterator of SIMD vectors, and store them in-order in a Vec,
     /// including possibly redundant elements at the end of the iterator.
     #[cfg(feature = "std")]
@@ -694,6 +969,39 @@ pub trait IntoScalar<T> : SIMDObject where T : Packable {
     /// Take an iterator of SIMD vectors and store them in-order in `fill`,
     /// including possibly redundant elements at the end of the iterator.
     fn scalar_fill_all<'a>(&mut self, fill: &'a mut [T]) -> &'a mut [T];
+
+    /// Take an iterator of SIMD vectors, and store them in-order in a Vec
+    /// with non-temporal stores, which don't bring the Vec into the cache.
+    /// This is faster than `scalar_collect` for outputs much larger than the
+    /// cache which won't be read again soon.
+    #[cfg(feature = "std")]
+    fn scalar_collect_streaming(&mut self) -> Vec<T> where Self::Vector : Stream;
+
+    /// Take an iterator of SIMD vectors and store them in-order in `fill`
+    /// with non-temporal stores, which don't bring `fill` into the cache.
+    /// This is faster than `scalar_fill` for outputs much larger than the
+    /// cache which won't be read again soon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is too short to hold every element.
+    fn scalar_fill_streaming<'a>(&mut self, fill: &'a mut [T]) -> &'a mut [T] where Self::Vector : Stream;
+
+    /// Take an iterator of SIMD vectors and store them in-order in `fill`
+    /// with non-temporal stores, or return an error if `fill` is too short
+    /// to hold every element. Nothing is stored if an error is returned.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let data = [1u32; 100];
+    /// let mut fill = [0u32; 99];
+    /// assert!(data.simd_iter(u32s(0)).try_scalar_fill_streaming(&mut fill).is_err());
+    /// # }
+    /// ```
+    fn try_scalar_fill_streaming<'a>(&mut self, fill: &'a mut [T]) -> Result<&'a mut [T], Error> where Self::Vector : Stream;
 }
 
 impl<'a, T, I> IntoScalar<T> for I
@@ -735,25 +1043,40 @@ impl<'a, T, I> IntoScalar<T> for I
 
     #[inline(always)]
     fn scalar_fill<'b>(&mut self, fill: &'b mut [Self::Scalar]) -> &'b mut [Self::Scalar] {
+        match self.try_scalar_fill(fill) {
+            Ok(fill) => fill,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[inline(always)]
+    fn try_scalar_fill<'b>(&mut self, fill: &'b mut [Self::Scalar]) -> Result<&'b mut [Self::Scalar], Error> {
+        let width = self.width();
         let mut offset = 0;
         let mut lastvec = Self::Vector::default();
 
-        while let Some(vec) = self.next() {
+        for vec in &mut *self {
+            if fill.len() < offset + width {
+                return Err(Error::BufferTooShort { needed: offset + width, len: fill.len() });
+            }
             unsafe { vec.store_unchecked(fill, offset); }
-            offset += self.width();
+            offset += width;
             lastvec = vec;
         }
 
         if let Some((p, n)) = self.end() {
+            if fill.len() < offset + width - n {
+                return Err(Error::BufferTooShort { needed: offset + width - n, len: fill.len() });
+            }
             if offset > 0 {
                 // We stored a vector in this buffer; overwrite the unused elements
                 unsafe {
                     p.store_unchecked(fill, offset - n);
-                    lastvec.store_unchecked(fill, offset - self.width());
+                    lastvec.store_unchecked(fill, offset - width);
                 }
             } else {
                 // The buffer won't fit one vector; store elementwise
-                for i in 0..(self.width() - n) {
+                for i in 0..(width - n) {
                     unsafe {
                         fill[offset + i] = p.extract_unchecked(i + n);
                     }
@@ -761,7 +1084,7 @@ impl<'a, T, I> IntoScalar<T> for I
             }
         }
 
-        fill
+        Ok(fill)
     }
 
     #[inline(always)]
@@ -791,6 +1114,95 @@ impl<'a, T, I> IntoScalar<T> for I
 
         fill
     }
+
+    #[inline(always)]
+    #[cfg(feature = "std")]
+    fn scalar_collect_streaming(&mut self) -> Vec<Self::Scalar> where Self::Vector : Stream {
+        let len = self.scalar_len() - self.scalar_pos();
+        let mut ret = Vec::with_capacity((self.len() + 1) * self.width());
+
+        unsafe {
+            ret.set_len((self.len() + 1) * self.width());
+            self.scalar_fill_streaming(ret.as_mut_slice());
+            ret.set_len(len);
+        }
+        ret
+    }
+
+    #[inline(always)]
+    fn scalar_fill_streaming<'b>(&mut self, fill: &'b mut [Self::Scalar]) -> &'b mut [Self::Scalar] where Self::Vector : Stream {
+        match self.try_scalar_fill_streaming(fill) {
+            Ok(fill) => fill,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[inline(always)]
+    fn try_scalar_fill_streaming<'b>(&mut self, fill: &'b mut [Self::Scalar]) -> Result<&'b mut [Self::Scalar], Error> where Self::Vector : Stream {
+        let len = self.scalar_len() - self.scalar_pos();
+        if fill.len() < len {
+            return Err(Error::BufferTooShort { needed: len, len: fill.len() });
+        }
+
+        let width = self.width();
+        // The number of elements before the first aligned vector of `fill`
+        let head = fill.as_ptr().align_offset(align_of::<Self::Vector>());
+        if head >= width {
+            // The elements of `fill` can't be aligned to a vector
+            return self.try_scalar_fill(fill);
+        }
+
+        let mut offset = 0;
+        let mut lastvec = Self::Vector::default();
+        // Room for two vectors, to shift consecutive vectors into place when
+        // `fill` begins partway through a vector
+        let mut scratch = [Self::Vector::default(); 2];
+        let scratch = unsafe {
+            from_raw_parts_mut(scratch.as_mut_ptr() as *mut Self::Scalar, width * 2)
+        };
+
+        while let Some(vec) = self.next() {
+            unsafe {
+                if head == 0 {
+                    vec.stream_unchecked(fill, offset);
+                } else if offset == 0 {
+                    for i in 0..head {
+                        *fill.get_unchecked_mut(i) = vec.extract_unchecked(i);
+                    }
+                } else {
+                    lastvec.store_unchecked(scratch, 0);
+                    vec.store_unchecked(scratch, width);
+                    Self::Vector::load_unchecked(scratch, head)
+                        .stream_unchecked(fill, offset - width + head);
+                }
+            }
+            offset += width;
+            lastvec = vec;
+        }
+
+        unsafe {
+            if head != 0 && offset > 0 {
+                // The end of the last vector hasn't been stored yet
+                lastvec.store_unchecked(fill, offset - width);
+            }
+
+            if let Some((p, n)) = self.end() {
+                if offset > 0 {
+                    // We stored a vector in this buffer; overwrite the unused elements
+                    p.store_unchecked(fill, offset - n);
+                    lastvec.store_unchecked(fill, offset - width);
+                } else {
+                    // The buffer won't fit one vector; store elementwise
+                    for i in 0..(width - n) {
+                        fill[offset + i] = p.extract_unchecked(i + n);
+                    }
+                }
+            }
+        }
+
+        Self::Vector::stream_fence();
+        Ok(fill)
+    }
 }
 
 mod tests {
@@ -830,4 +1242,78 @@ mod tests {
                      3, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0,
                      5, 0, 0, 0, 0, 0, 0, 0u8][..]);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn scalar_fill_streaming_matches_scalar_fill() {
+        for len in [0, 1, 3, 4, 5, 8, 15, 16, 17, 31, 64, 100, 1000].iter().cloned() {
+            let data = (0..len as u32).collect::<Vec<u32>>();
+            let expected = data.simd_iter(u32s(0))
+                .simd_map(|v| v * u32s(3))
+                .scalar_collect();
+            assert_eq!(data.simd_iter(u32s(0))
+                       .simd_map(|v| v * u32s(3))
+                       .scalar_collect_streaming(), expected);
+
+            // Try every alignment of the output
+            for start in 0..u32s::WIDTH {
+                let mut buf = vec![!0u32; len + u32s::WIDTH * 2];
+                data.simd_iter(u32s(0))
+                    .simd_map(|v| v * u32s(3))
+                    .scalar_fill_streaming(&mut buf[start..start + len]);
+                assert_eq!(&buf[start..start + len], &expected[..]);
+                assert!(buf[..start].iter().chain(&buf[start + len..]).all(|&x| x == !0));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn scalar_fill_streaming_rejects_short_buffer() {
+        let data = [1u32; 100];
+        let mut fill = [0u32; 99];
+        data.simd_iter(u32s(0)).scalar_fill_streaming(&mut fill);
+    }
+
+    #[test]
+    fn simd_reduce_unrolled_matches_simd_reduce() {
+        let data = [7u32; 300];
+        for len in [0, 1, 7, 8, 9, 31, 32, 33, 100, 300].iter().cloned() {
+            let expected = (&data[..len]).simd_iter(u32s(0))
+                .simd_reduce(u32s(0), |acc, v| acc + v)
+                .sum();
+            assert_eq!(expected, 7 * len as u32);
+            let add = |a: u32s, b: u32s| a + b;
+            assert_eq!((&data[..len]).simd_iter(u32s(0))
+                       .simd_reduce_unrolled([u32s(0); 1], add, add).sum(), expected);
+            assert_eq!((&data[..len]).simd_iter(u32s(0))
+                       .simd_reduce_unrolled([u32s(0); 3], add, add).sum(), expected);
+            assert_eq!((&data[..len]).simd_iter(u32s(0))
+                       .simd_reduce_unrolled([u32s(0); 8], add, add).sum(), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn unroll_leaves_tail() {
+        let data = (0..100u32).collect::<Vec<u32>>();
+        let mut iter = (&data[..]).simd_iter(u32s(0));
+        let mut unrolled = iter.unroll::<3>();
+        let mut seen = Vec::new();
+        for vs in &mut unrolled {
+            seen.extend_from_slice(&vs);
+        }
+        assert!(unrolled.next().is_none());
+        let tail = unrolled.tail().to_vec();
+        assert_eq!(tail.len(), (100 / u32s::WIDTH) % 3);
+        seen.extend_from_slice(&tail);
+        let whole = seen.len() * u32s::WIDTH;
+        for (i, v) in seen.iter().enumerate() {
+            assert_eq!(v.extract(0), (i * u32s::WIDTH) as u32);
+        }
+        match unrolled.end() {
+            Some((v, n)) => assert_eq!(v.extract(n), whole as u32),
+            None => assert_eq!(whole, 100),
+        }
+    }
 }
@@ -151,6 +151,32 @@
 //! # }
 //! ```
 //!
+//! ## Interleaving Collections
+//!
+//! The inverse of striding is also vectorized. Tuples of vectors from a
+//! zipped iterator may be written to a single collection in an interleaved
+//! fashion with [`scalar_fill_interleaved`].
+//!
+//! [`scalar_fill_interleaved`]: restride/trait.IntoScalarInterleaved.html#tymethod.scalar_fill_interleaved
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//!
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() { }
+//!
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//!     // Transposes matrices arranged as [a, b, c, d, a, b, c...]
+//!     let slice: &[f32] = &[1.0f32; 1024];
+//!     let mut transposed = [0f32; 1024];
+//!     slice.stride_four(tuplify!(4, f32s(0.0))).zip()
+//!         .simd_map(|(a, b, c, d)| (a, c, b, d))
+//!         .scalar_fill_interleaved(&mut transposed);
+//! # }
+//! ```
+//!
 //! # Portability
 //!
 //! While `faster` does most of the work ensuring your code stays portable
@@ -195,6 +221,28 @@
 //! exactly what is happening under the hood. It's also important to remember
 //! that these problems will crop up even if you only support x86; the width
 //! difference between AVX and SSE is the primary source of these issues!
+//!
+//! If an algorithm must behave identically on every target, its vector width
+//! may be pinned by using the vectors in [`w128`], [`w256`], or [`w512`]
+//! instead of the native ones. Explicitly-sized vectors are emulated on
+//! targets which cannot operate on them natively, although only [`w128`] is
+//! available outside of x86.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//!
+//! # fn main() {
+//! let length_dependent = [0i8; 10].simd_iter(w128::i8s(0))
+//!    .simd_reduce(w128::i8s(0), |acc, v| acc + v + w128::i8s(1)).sum();
+//! // `length_dependent` is the same on every target
+//! assert_eq!(length_dependent, 16);
+//! # }
+//! ```
+//!
+//! [`w128`]: w128/index.html
+//! [`w256`]: w256/index.html
+//! [`w512`]: w512/index.html
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(test, feature(test))]
@@ -212,6 +260,11 @@ extern crate packed_simd;
 extern crate vektor;
 
 #[macro_use] pub(crate) mod debug;
+#[cfg(test)]
+pub(crate) mod testing;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod error;
 #[macro_use] pub mod zip;
 #[macro_use] pub mod vecs;
 pub mod vec_patterns;
@@ -222,5 +275,22 @@ pub mod into_iters;
 pub mod prelude;
 pub mod stride_zip;
 pub mod stride;
+pub mod restride;
+pub mod complex;
+pub mod half;
+pub mod linalg;
+pub mod text;
+pub mod codec;
+pub mod scan;
+pub mod checksum;
+pub mod hash;
+pub mod sort;
+pub mod histogram;
+pub mod stats;
+pub mod quant;
+#[cfg(feature = "std")]
+pub mod aligned;
+pub mod summation;
 
 pub use crate::prelude::*;
+pub use crate::error::Error;
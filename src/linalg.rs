@@ -0,0 +1,615 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Dense linear algebra kernels over slices of `f32` and `f64`.
+//!
+//! These cover the BLAS level 1 and 2 routines which are most often rebuilt
+//! on top of zipped iterators, as well as a cache-blocked matrix multiply.
+//! Every kernel uses fused multiply-add when the CPU supports it, and keeps
+//! several independent accumulators in flight to hide its latency.
+//!
+//! Matrices are dense and stored contiguously, so the leading dimension of a
+//! row-major matrix is its number of columns, and that of a column-major
+//! matrix is its number of rows.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::linalg::*;
+//!
+//! # fn main() {
+//! // [ 1 2 3 ]   [ 1 ]   [ 14 ]
+//! // [ 4 5 6 ] * [ 2 ] = [ 32 ]
+//! //             [ 3 ]
+//! let a = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+//! let x = [1.0f32, 2.0, 3.0];
+//! let mut y = [0.0f32; 2];
+//! gemv(Layout::RowMajor, 2, 3, 1.0, &a, &x, 0.0, &mut y);
+//! assert_eq!(y, [14.0, 32.0]);
+//! # }
+//! ```
+//!
+//! Because the vectorized kernels sum in a different order than a naive loop,
+//! their results may differ from a scalar implementation in the last few
+//! bits.
+
+use crate::vecs::{Packed, Packable};
+use crate::intrin::cmp::Cmp;
+use crate::intrin::mul_add::MulAdd;
+use crate::intrin::sqrt::Sqrt;
+use crate::intrin::sum::Sum;
+use crate::arch::current::vecs::{f32s, f64s};
+use crate::core::ops::{Add, Sub, Mul, Div};
+
+/// A floating-point scalar which the kernels in this module operate on.
+pub trait Real : Packable + PartialOrd + Add<Output = Self> + Sub<Output = Self>
+    + Mul<Output = Self> + Div<Output = Self> {
+    /// The native vector of this scalar.
+    type Lanes : Packed<Scalar = Self> + MulAdd + Sum + Sqrt + Cmp
+        + Add<Output = Self::Lanes> + Sub<Output = Self::Lanes> + Mul<Output = Self::Lanes>;
+
+    /// The additive identity.
+    const ZERO: Self;
+
+    /// The multiplicative identity.
+    const ONE: Self;
+}
+
+impl Real for f32 {
+    type Lanes = f32s;
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+}
+
+impl Real for f64 {
+    type Lanes = f64s;
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+}
+
+/// The order in which the elements of a matrix are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Each row is contiguous in memory.
+    RowMajor,
+    /// Each column is contiguous in memory.
+    ColMajor,
+}
+
+// The number of rows of A which the gemm micro-kernel updates at once
+const GEMM_MR: usize = 4;
+// The number of rows of B which are streamed through the cache per block
+const GEMM_KC: usize = 256;
+// The number of columns of B and C which are kept in cache per block
+const GEMM_NC: usize = 512;
+
+/// Return the dot product of `x` and `y`.
+///
+/// # Panics
+///
+/// Panics if `x` and `y` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::*;
+///
+/// # fn main() {
+/// assert_eq!(dot(&[1.0f64, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0);
+/// # }
+/// ```
+#[inline]
+pub fn dot<S : Real>(x: &[S], y: &[S]) -> S {
+    assert_eq!(x.len(), y.len(), "You can only take the dot product of slices of the same length.");
+    let width = S::Lanes::WIDTH;
+    let len = x.len();
+    let zero = S::Lanes::splat(S::ZERO);
+    let (mut acc0, mut acc1, mut acc2, mut acc3) = (zero, zero, zero, zero);
+    let mut i = 0;
+
+    unsafe {
+        while i + width * 4 <= len {
+            acc0 = S::Lanes::load_unchecked(x, i).mul_add(S::Lanes::load_unchecked(y, i), acc0);
+            acc1 = S::Lanes::load_unchecked(x, i + width).mul_add(S::Lanes::load_unchecked(y, i + width), acc1);
+            acc2 = S::Lanes::load_unchecked(x, i + width * 2).mul_add(S::Lanes::load_unchecked(y, i + width * 2), acc2);
+            acc3 = S::Lanes::load_unchecked(x, i + width * 3).mul_add(S::Lanes::load_unchecked(y, i + width * 3), acc3);
+            i += width * 4;
+        }
+        while i + width <= len {
+            acc0 = S::Lanes::load_unchecked(x, i).mul_add(S::Lanes::load_unchecked(y, i), acc0);
+            i += width;
+        }
+    }
+
+    let mut ret = ((acc0 + acc1) + (acc2 + acc3)).sum();
+    while i < len {
+        ret = ret + x[i] * y[i];
+        i += 1;
+    }
+    ret
+}
+
+/// Add `alpha * x` to `y`, in place.
+///
+/// # Panics
+///
+/// Panics if `x` and `y` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::*;
+///
+/// # fn main() {
+/// let mut y = [1.0f32, 1.0, 1.0];
+/// axpy(2.0, &[1.0, 2.0, 3.0], &mut y);
+/// assert_eq!(y, [3.0, 5.0, 7.0]);
+/// # }
+/// ```
+#[inline]
+pub fn axpy<S : Real>(alpha: S, x: &[S], y: &mut [S]) {
+    assert_eq!(x.len(), y.len(), "You can only axpy slices of the same length.");
+    let width = S::Lanes::WIDTH;
+    let len = x.len();
+    let a = S::Lanes::splat(alpha);
+    let mut i = 0;
+
+    unsafe {
+        while i + width * 2 <= len {
+            let y0 = a.mul_add(S::Lanes::load_unchecked(x, i), S::Lanes::load_unchecked(y, i));
+            let y1 = a.mul_add(S::Lanes::load_unchecked(x, i + width), S::Lanes::load_unchecked(y, i + width));
+            y0.store_unchecked(y, i);
+            y1.store_unchecked(y, i + width);
+            i += width * 2;
+        }
+        while i + width <= len {
+            a.mul_add(S::Lanes::load_unchecked(x, i), S::Lanes::load_unchecked(y, i)).store_unchecked(y, i);
+            i += width;
+        }
+    }
+
+    while i < len {
+        y[i] = alpha * x[i] + y[i];
+        i += 1;
+    }
+}
+
+/// Multiply every element of `x` by `alpha`, in place.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::*;
+///
+/// # fn main() {
+/// let mut x = [1.0f64, -2.0, 3.0];
+/// scal(-0.5, &mut x);
+/// assert_eq!(x, [-0.5, 1.0, -1.5]);
+/// # }
+/// ```
+#[inline]
+pub fn scal<S : Real>(alpha: S, x: &mut [S]) {
+    let width = S::Lanes::WIDTH;
+    let len = x.len();
+    let a = S::Lanes::splat(alpha);
+    let mut i = 0;
+
+    unsafe {
+        while i + width <= len {
+            (a * S::Lanes::load_unchecked(x, i)).store_unchecked(x, i);
+            i += width;
+        }
+    }
+
+    while i < len {
+        x[i] = alpha * x[i];
+        i += 1;
+    }
+}
+
+/// Return the euclidean norm of `x`.
+///
+/// The elements are scaled by the largest magnitude in `x` before they are
+/// squared, so this does not overflow or underflow unless the result does.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::*;
+///
+/// # fn main() {
+/// assert_eq!(nrm2(&[3.0f32, -4.0]), 5.0);
+/// // Squaring 2^100 would overflow an f32
+/// let big = 1267650600228229401496703205376.0f32;
+/// assert_eq!(nrm2(&[3.0 * big, -4.0 * big]), 5.0 * big);
+/// # }
+/// ```
+#[inline]
+pub fn nrm2<S : Real>(x: &[S]) -> S {
+    let width = S::Lanes::WIDTH;
+    let len = x.len();
+    let zero = S::Lanes::splat(S::ZERO);
+    let mut i = 0;
+
+    // Find the largest magnitude, which we scale everything by
+    let mut vmax = zero;
+    unsafe {
+        while i + width <= len {
+            let v = S::Lanes::load_unchecked(x, i);
+            vmax = vmax.max(v.max(zero - v));
+            i += width;
+        }
+    }
+    let mut scale = vmax.scalar_reduce(S::ZERO, |acc, s| if s > acc { s } else { acc });
+    for &s in &x[i..] {
+        let s = if s < S::ZERO { S::ZERO - s } else { s };
+        if s > scale {
+            scale = s;
+        }
+    }
+
+    // Zero needs no scaling, and infinities and NaNs can't be scaled
+    if scale == S::ZERO || scale * S::ZERO != S::ZERO {
+        return scale;
+    }
+
+    let inv = S::Lanes::splat(S::ONE / scale);
+    let (mut acc0, mut acc1) = (zero, zero);
+    i = 0;
+    unsafe {
+        while i + width * 2 <= len {
+            let v0 = S::Lanes::load_unchecked(x, i) * inv;
+            let v1 = S::Lanes::load_unchecked(x, i + width) * inv;
+            acc0 = v0.mul_add(v0, acc0);
+            acc1 = v1.mul_add(v1, acc1);
+            i += width * 2;
+        }
+        while i + width <= len {
+            let v = S::Lanes::load_unchecked(x, i) * inv;
+            acc0 = v.mul_add(v, acc0);
+            i += width;
+        }
+    }
+
+    let mut ssq = (acc0 + acc1).sum();
+    let inv = S::ONE / scale;
+    while i < len {
+        let s = x[i] * inv;
+        ssq = ssq + s * s;
+        i += 1;
+    }
+
+    // The scalar square root isn't available without std
+    scale * S::Lanes::splat(ssq).sqrt().extract(0)
+}
+
+/// Compute `alpha * A * x + beta * y` and store it in `y`, where `A` is an
+/// `m` by `n` matrix stored in `a` with the given layout.
+///
+/// If `beta` is zero, `y` does not need to be initialized, and any NaNs in it
+/// are overwritten.
+///
+/// # Panics
+///
+/// Panics if `m * n` overflows, `a` does not contain exactly `m * n`
+/// elements, `x` does not contain `n` elements, or `y` does not contain `m`
+/// elements.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn gemv<S : Real>(layout: Layout, m: usize, n: usize, alpha: S, a: &[S],
+                      x: &[S], beta: S, y: &mut [S]) {
+    let len = m.checked_mul(n).expect("dimension overflow");
+    assert_eq!(a.len(), len, "The matrix must contain m * n elements.");
+    assert_eq!(x.len(), n, "The input vector must contain n elements.");
+    assert_eq!(y.len(), m, "The output vector must contain m elements.");
+
+    match layout {
+        Layout::RowMajor => {
+            // Each output is the dot product of a row of A and x
+            for (i, yi) in y.iter_mut().enumerate() {
+                let prod = alpha * dot(&a[i * n..(i + 1) * n], x);
+                *yi = if beta == S::ZERO { prod } else { prod + beta * *yi };
+            }
+        },
+        Layout::ColMajor => {
+            // The output is a linear combination of the columns of A
+            scale_or_zero(beta, y);
+            for (j, &xj) in x.iter().enumerate() {
+                axpy(alpha * xj, &a[j * m..(j + 1) * m], y);
+            }
+        }
+    }
+}
+
+/// Compute `alpha * A * B + beta * C` and store it in `C`, where `A` is an
+/// `m` by `k` matrix, `B` is a `k` by `n` matrix, and `C` is an `m` by `n`
+/// matrix. All three are stored in row-major order.
+///
+/// The multiplication is blocked such that a panel of `B` stays in cache
+/// while it is multiplied against every row of `A`, and several rows of `C`
+/// are updated at once to keep multiple accumulators in flight.
+///
+/// If `beta` is zero, `c` does not need to be initialized, and any NaNs in it
+/// are overwritten.
+///
+/// # Panics
+///
+/// Panics if `m * k`, `k * n`, or `m * n` overflows, or if `a`, `b`, or `c`
+/// do not contain that many elements, respectively.
+///
+/// ```
+/// extern crate faster;
+/// use faster::linalg::*;
+///
+/// # fn main() {
+/// let a = [1.0f64, 2.0, 3.0, 4.0];
+/// let b = [5.0f64, 6.0, 7.0, 8.0];
+/// let mut c = [0.0f64; 4];
+/// gemm(2, 2, 2, 1.0, &a, &b, 0.0, &mut c);
+/// assert_eq!(c, [19.0, 22.0, 43.0, 50.0]);
+/// # }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn gemm<S : Real>(m: usize, n: usize, k: usize, alpha: S, a: &[S], b: &[S],
+                      beta: S, c: &mut [S]) {
+    let a_len = m.checked_mul(k).expect("dimension overflow");
+    let b_len = k.checked_mul(n).expect("dimension overflow");
+    let c_len = m.checked_mul(n).expect("dimension overflow");
+    assert_eq!(a.len(), a_len, "The left matrix must contain m * k elements.");
+    assert_eq!(b.len(), b_len, "The right matrix must contain k * n elements.");
+    assert_eq!(c.len(), c_len, "The output matrix must contain m * n elements.");
+
+    scale_or_zero(beta, c);
+    if alpha == S::ZERO {
+        return;
+    }
+
+    for jb in (0..n).step_by(GEMM_NC) {
+        let je = (jb + GEMM_NC).min(n);
+        for pb in (0..k).step_by(GEMM_KC) {
+            let pe = (pb + GEMM_KC).min(k);
+            let mut i = 0;
+            while i + GEMM_MR <= m {
+                gemm_kernel_4(i, jb, je, pb, pe, n, k, alpha, a, b, c);
+                i += GEMM_MR;
+            }
+            while i < m {
+                gemm_kernel_1(i, jb, je, pb, pe, n, k, alpha, a, b, c);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Multiply `x` by `beta`, or zero it if `beta` is zero.
+#[inline(always)]
+fn scale_or_zero<S : Real>(beta: S, x: &mut [S]) {
+    if beta == S::ZERO {
+        for s in x.iter_mut() {
+            *s = S::ZERO;
+        }
+    } else if beta != S::ONE {
+        scal(beta, x);
+    }
+}
+
+/// Add `alpha * A[i..i + 4, pb..pe] * B[pb..pe, jb..je]` to
+/// `C[i..i + 4, jb..je]`.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+fn gemm_kernel_4<S : Real>(i: usize, jb: usize, je: usize, pb: usize, pe: usize,
+                           n: usize, k: usize, alpha: S, a: &[S], b: &[S], c: &mut [S]) {
+    let width = S::Lanes::WIDTH;
+    let (r0, r1, r2, r3) = (i * n, (i + 1) * n, (i + 2) * n, (i + 3) * n);
+    let (a0, a1, a2, a3) = (i * k, (i + 1) * k, (i + 2) * k, (i + 3) * k);
+    let mut j = jb;
+
+    unsafe {
+        while j + width <= je {
+            let mut c0 = S::Lanes::load_unchecked(c, r0 + j);
+            let mut c1 = S::Lanes::load_unchecked(c, r1 + j);
+            let mut c2 = S::Lanes::load_unchecked(c, r2 + j);
+            let mut c3 = S::Lanes::load_unchecked(c, r3 + j);
+            for p in pb..pe {
+                let bv = S::Lanes::load_unchecked(b, p * n + j);
+                c0 = S::Lanes::splat(alpha * *a.get_unchecked(a0 + p)).mul_add(bv, c0);
+                c1 = S::Lanes::splat(alpha * *a.get_unchecked(a1 + p)).mul_add(bv, c1);
+                c2 = S::Lanes::splat(alpha * *a.get_unchecked(a2 + p)).mul_add(bv, c2);
+                c3 = S::Lanes::splat(alpha * *a.get_unchecked(a3 + p)).mul_add(bv, c3);
+            }
+            c0.store_unchecked(c, r0 + j);
+            c1.store_unchecked(c, r1 + j);
+            c2.store_unchecked(c, r2 + j);
+            c3.store_unchecked(c, r3 + j);
+            j += width;
+        }
+    }
+
+    for row in i..i + GEMM_MR {
+        gemm_scalar_tail(row, j, je, pb, pe, n, k, alpha, a, b, c);
+    }
+}
+
+/// Add `alpha * A[i, pb..pe] * B[pb..pe, jb..je]` to `C[i, jb..je]`.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+fn gemm_kernel_1<S : Real>(i: usize, jb: usize, je: usize, pb: usize, pe: usize,
+                           n: usize, k: usize, alpha: S, a: &[S], b: &[S], c: &mut [S]) {
+    let width = S::Lanes::WIDTH;
+    let mut j = jb;
+
+    unsafe {
+        while j + width <= je {
+            let mut cv = S::Lanes::load_unchecked(c, i * n + j);
+            for p in pb..pe {
+                let bv = S::Lanes::load_unchecked(b, p * n + j);
+                cv = S::Lanes::splat(alpha * *a.get_unchecked(i * k + p)).mul_add(bv, cv);
+            }
+            cv.store_unchecked(c, i * n + j);
+            j += width;
+        }
+    }
+
+    gemm_scalar_tail(i, j, je, pb, pe, n, k, alpha, a, b, c);
+}
+
+/// Add `alpha * A[i, pb..pe] * B[pb..pe, jb..je]` to `C[i, jb..je]`, one
+/// element at a time.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+fn gemm_scalar_tail<S : Real>(i: usize, jb: usize, je: usize, pb: usize, pe: usize,
+                              n: usize, k: usize, alpha: S, a: &[S], b: &[S], c: &mut [S]) {
+    for j in jb..je {
+        let mut acc = c[i * n + j];
+        for p in pb..pe {
+            acc = acc + alpha * a[i * k + p] * b[p * n + j];
+        }
+        c[i * n + j] = acc;
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing::Rng;
+
+    // A deterministic sequence of values in [-1, 1)
+    fn data(len: usize, seed: u64) -> Vec<f64> {
+        let mut rng = Rng::with_seed(seed);
+        (0..len).map(|_| (rng.next() >> 11) as f64 / (1u64 << 52) as f64 - 1.0).collect()
+    }
+
+    fn data_f32(len: usize, seed: u64) -> Vec<f32> {
+        data(len, seed).into_iter().map(|x| x as f32).collect()
+    }
+
+    fn naive_gemm(m: usize, n: usize, k: usize, alpha: f64, a: &[f64], b: &[f64], beta: f64, c: &mut [f64]) {
+        for i in 0..m {
+            for j in 0..n {
+                let mut acc = 0.0;
+                for p in 0..k {
+                    acc += a[i * k + p] * b[p * n + j];
+                }
+                c[i * n + j] = alpha * acc + beta * c[i * n + j];
+            }
+        }
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64], tol: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() <= tol * (1.0 + e.abs()), "{} != {}", a, e);
+        }
+    }
+
+    const SIZES: [usize; 8] = [0, 1, 3, 7, 16, 33, 100, 257];
+
+    #[test]
+    fn dot_matches_naive() {
+        for &len in SIZES.iter() {
+            let (x, y) = (data(len, 1), data(len, 2));
+            let expected: f64 = x.iter().zip(&y).map(|(a, b)| a * b).sum();
+            assert_close(&[dot(&x, &y)], &[expected], 1e-12);
+
+            let (x, y) = (data_f32(len, 1), data_f32(len, 2));
+            let expected: f64 = x.iter().zip(&y).map(|(a, b)| *a as f64 * *b as f64).sum();
+            assert_close(&[dot(&x, &y) as f64], &[expected], 1e-4);
+        }
+    }
+
+    #[test]
+    fn axpy_scal_match_naive() {
+        for &len in SIZES.iter() {
+            let x = data(len, 3);
+            let mut y = data(len, 4);
+            let expected: Vec<f64> = x.iter().zip(&y).map(|(a, b)| -1.5 * a + b).collect();
+            axpy(-1.5, &x, &mut y);
+            assert_close(&y, &expected, 1e-15);
+
+            let expected: Vec<f64> = y.iter().map(|a| a * 0.25).collect();
+            scal(0.25, &mut y);
+            assert_eq!(y, expected);
+        }
+    }
+
+    #[test]
+    fn nrm2_matches_naive() {
+        for &len in SIZES.iter() {
+            let x = data(len, 5);
+            let expected = x.iter().map(|a| a * a).sum::<f64>().sqrt();
+            assert_close(&[nrm2(&x)], &[expected], 1e-12);
+        }
+        assert_close(&[nrm2(&[1.0e300f64; 4]) / 1.0e300], &[2.0], 1e-15);
+        assert_close(&[nrm2(&[1.0e-300f64; 4]) / 1.0e-300], &[2.0], 1e-15);
+        assert_eq!(nrm2(&[1.0f64, -crate::core::f64::INFINITY, 2.0, 3.0, 4.0]), crate::core::f64::INFINITY);
+        assert!(nrm2(&[1.0f32, crate::core::f32::NAN, 2.0]).is_nan());
+    }
+
+    #[test]
+    fn gemv_matches_naive() {
+        for &(m, n) in [(0, 0), (1, 1), (3, 5), (17, 9), (64, 100), (5, 300)].iter() {
+            let a = data(m * n, 6);
+            let x = data(n, 7);
+            let y = data(m, 8);
+            let mut expected = y.clone();
+            naive_gemm(m, 1, n, 2.0, &a, &x, -0.5, &mut expected);
+
+            let mut row = y.clone();
+            gemv(Layout::RowMajor, m, n, 2.0, &a, &x, -0.5, &mut row);
+            assert_close(&row, &expected, 1e-12);
+
+            // Transpose A into column-major order
+            let at: Vec<f64> = (0..m * n).map(|idx| a[(idx % m) * n + idx / m]).collect();
+            let mut col = y.clone();
+            gemv(Layout::ColMajor, m, n, 2.0, &at, &x, -0.5, &mut col);
+            assert_close(&col, &expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn gemv_ignores_output_with_zero_beta() {
+        let mut y = [crate::core::f64::NAN; 2];
+        gemv(Layout::ColMajor, 2, 2, 1.0, &[1.0, 2.0, 3.0, 4.0], &[1.0, 1.0], 0.0, &mut y);
+        assert_eq!(y, [4.0, 6.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension overflow")]
+    fn gemv_rejects_overflowing_dimensions() {
+        // m * n wraps around to zero
+        let mut y = [0.0f64; 0];
+        gemv(Layout::RowMajor, 2, usize::max_value() / 2 + 1, 1.0, &[], &[], 0.0, &mut y);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension overflow")]
+    fn gemm_rejects_overflowing_dimensions() {
+        // m * k and m * n wrap around to zero
+        let mut c = [0.0f64; 0];
+        gemm(usize::max_value() / 2 + 1, 2, 2, 1.0, &[], &[0.0; 4], 0.0, &mut c);
+    }
+
+    #[test]
+    fn gemm_matches_naive() {
+        for &(m, n, k) in [(0, 0, 0), (1, 1, 1), (4, 4, 4), (5, 7, 3), (13, 600, 9),
+                           (33, 17, 300), (64, 64, 64)].iter() {
+            let a = data(m * k, 9);
+            let b = data(k * n, 10);
+            let c = data(m * n, 11);
+            let mut expected = c.clone();
+            naive_gemm(m, n, k, 0.75, &a, &b, 1.25, &mut expected);
+            let mut actual = c.clone();
+            gemm(m, n, k, 0.75, &a, &b, 1.25, &mut actual);
+            assert_close(&actual, &expected, 1e-12);
+
+            let (af, bf) = (data_f32(m * k, 9), data_f32(k * n, 10));
+            let mut actual = vec![crate::core::f32::NAN; m * n];
+            gemm(m, n, k, 1.0, &af, &bf, 0.0, &mut actual);
+            let (ad, bd): (Vec<f64>, Vec<f64>) = (af.iter().map(|&x| x as f64).collect(),
+                                                  bf.iter().map(|&x| x as f64).collect());
+            let mut expected = vec![0.0; m * n];
+            naive_gemm(m, n, k, 1.0, &ad, &bd, 0.0, &mut expected);
+            let actual: Vec<f64> = actual.iter().map(|&x| x as f64).collect();
+            assert_close(&actual, &expected, 1e-4);
+        }
+    }
+}
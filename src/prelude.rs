@@ -9,8 +9,12 @@ pub use crate::iters::*;
 pub use crate::into_iters::*;
 pub use crate::vecs::{Packed, Pattern};
 pub use crate::arch::current::vecs::{u8s, i8s, u16s, i16s, u32s, i32s, f32s, u64s, i64s, f64s};
+pub use crate::arch::current::widths::*;
 pub use crate::arch::current::intrin::prelude::*;
 pub use crate::intrin::prelude::*;
 pub use crate::zip::*;
 pub use crate::stride_zip::*;
 pub use crate::stride::*;
+pub use crate::restride::*;
+pub use crate::complex::{Complex, PackedComplex, c32s, c64s, as_complex, as_complex_mut};
+pub use crate::half::{PackedF16, PackedBF16, f16s, bf16s};
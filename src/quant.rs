@@ -0,0 +1,473 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Affine quantization of floats to 8-bit integers, and dot products of
+//! 8-bit integers.
+//!
+//! A float `x` is quantized to `round(x / scale) + zero_point`, rounding
+//! halfway cases to even and saturating to the range of the integer type,
+//! and an integer `q` is dequantized to `(q - zero_point) * scale`. Both
+//! give the same results on every target, and the same results as doing the
+//! arithmetic above one element at a time in `f32`. NaNs quantize to an
+//! unspecified value.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::quant::*;
+//!
+//! # fn main() {
+//! let activations = [0.0f32, 0.25, -0.5, 1.0, 100.0];
+//! let mut quantized = [0u8; 5];
+//! quantize(&activations, &mut quantized, 0.25, 128);
+//! assert_eq!(quantized, [128, 129, 126, 132, 255]);
+//!
+//! let mut restored = [0f32; 5];
+//! dequantize(&quantized, &mut restored, 0.25, 128);
+//! assert_eq!(restored, [0.0, 0.25, -0.5, 1.0, 31.75]);
+//!
+//! assert_eq!(dot_u8i8(&[1, 2, 255], &[3, -4, -128]), 1 * 3 + 2 * -4 + 255 * -128);
+//! # }
+//! ```
+
+use crate::arch::current::vecs::{u8s, i8s, u16s, i16s, u32s, i32s, f32s};
+use crate::intrin::cast::{Asf32s, Asi32s};
+use crate::intrin::cmp::Cmp;
+use crate::intrin::downcast::Downcast;
+use crate::intrin::transmute::Transmute;
+use crate::intrin::upcast::Upcast;
+use crate::vecs::Packed;
+
+/// Adding and subtracting this rounds any float of magnitude up to 2^22 to
+/// the nearest integer, with ties to even, in `f32` arithmetic.
+const ROUNDING: f32 = 12_582_912.0;
+
+/// Quotients are clamped to this magnitude before rounding. Anything beyond
+/// it saturates whatever the zero point is.
+const CLAMP: f32 = 512.0;
+
+/// The number of vectors of products which are accumulated in `i32` lanes
+/// before being added to the total, so that no lane can overflow.
+const FLUSH: usize = 8192;
+
+/// An integer which floats can be quantized to.
+pub trait Quantized : Copy {
+    #[doc(hidden)]
+    fn quantize(input: &[f32], output: &mut [Self], scale: f32, zero_point: Self);
+
+    #[doc(hidden)]
+    fn dequantize(input: &[Self], output: &mut [f32], scale: f32, zero_point: Self);
+}
+
+/// Quantize each element of `input` into the same index of `output`.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::quant::quantize;
+///
+/// # fn main() {
+/// let mut quantized = [0i8; 4];
+/// quantize(&[0.5, 1.5, -2.5, 1000.0], &mut quantized, 1.0, 0);
+/// assert_eq!(quantized, [0, 2, -2, 127]);
+/// # }
+/// ```
+#[inline]
+pub fn quantize<Q : Quantized>(input: &[f32], output: &mut [Q], scale: f32, zero_point: Q) {
+    assert_eq!(input.len(), output.len());
+    Q::quantize(input, output, scale, zero_point)
+}
+
+/// Dequantize each element of `input` into the same index of `output`.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::quant::dequantize;
+///
+/// # fn main() {
+/// let mut restored = [0f32; 3];
+/// dequantize(&[-128i8, 0, 127], &mut restored, 0.5, -1);
+/// assert_eq!(restored, [-63.5, 0.5, 64.0]);
+/// # }
+/// ```
+#[inline]
+pub fn dequantize<Q : Quantized>(input: &[Q], output: &mut [f32], scale: f32, zero_point: Q) {
+    assert_eq!(input.len(), output.len());
+    Q::dequantize(input, output, scale, zero_point)
+}
+
+#[inline(always)]
+fn quantize_scalar(x: f32, scale: f32, zero_point: f32, min: f32, max: f32) -> i32 {
+    let x = (x / scale).max(-CLAMP).min(CLAMP);
+    ((x + ROUNDING) - ROUNDING + zero_point).max(min).min(max) as i32
+}
+
+macro_rules! impl_quantized {
+    ($el:ty, $vec:ty, $min:expr, $max:expr,
+     |$qs:ident| $narrow:expr, |$q:ident| $widen:expr) => {
+        impl Quantized for $el {
+            fn quantize(input: &[f32], output: &mut [Self], scale: f32, zero_point: Self) {
+                let (width, lanes) = (<$vec>::WIDTH, f32s::WIDTH);
+                let (scales, zero_points) = (f32s::splat(scale), f32s::splat(zero_point as f32));
+                let (clamp_min, clamp_max) = (f32s::splat(-CLAMP), f32s::splat(CLAMP));
+                let (min, max) = (f32s::splat($min), f32s::splat($max));
+                let rounding = f32s::splat(ROUNDING);
+                let quantize = |offset: usize| -> i32s {
+                    let x = f32s::load(input, offset) / scales;
+                    let x = Cmp::min(&Cmp::max(&x, clamp_min), clamp_max);
+                    let x = (x + rounding) - rounding + zero_points;
+                    Cmp::min(&Cmp::max(&x, min), max).as_i32s()
+                };
+
+                let mut i = 0;
+                while i + width <= input.len() {
+                    let $qs = [quantize(i), quantize(i + lanes),
+                               quantize(i + lanes * 2), quantize(i + lanes * 3)];
+                    let narrow: $vec = $narrow;
+                    narrow.store(output, i);
+                    i += width;
+                }
+                for j in i..input.len() {
+                    output[j] = quantize_scalar(input[j], scale, zero_point as f32, $min, $max) as $el;
+                }
+            }
+
+            fn dequantize(input: &[Self], output: &mut [f32], scale: f32, zero_point: Self) {
+                let (width, lanes) = (<$vec>::WIDTH, f32s::WIDTH);
+                let (scales, zero_points) = (f32s::splat(scale), f32s::splat(zero_point as f32));
+                let mut i = 0;
+                while i + width <= input.len() {
+                    let $q = <$vec>::load(input, i);
+                    let wide: [i32s; 4] = $widen;
+                    for (k, w) in wide.iter().enumerate() {
+                        ((w.as_f32s() - zero_points) * scales).store(output, i + k * lanes);
+                    }
+                    i += width;
+                }
+                for j in i..input.len() {
+                    output[j] = (input[j] as f32 - zero_point as f32) * scale;
+                }
+            }
+        }
+    }
+}
+
+impl_quantized!(i8, i8s, -128.0, 127.0,
+                |q| {
+                    let lo: i16s = q[0].saturating_downcast(q[1]);
+                    let hi: i16s = q[2].saturating_downcast(q[3]);
+                    lo.saturating_downcast(hi)
+                },
+                |q| {
+                    let (lo, hi): (i16s, i16s) = q.upcast();
+                    let (a, b): (i32s, i32s) = lo.upcast();
+                    let (c, d): (i32s, i32s) = hi.upcast();
+                    [a, b, c, d]
+                });
+
+impl_quantized!(u8, u8s, 0.0, 255.0,
+                |q| {
+                    // Everything is already in range of a u8, so the signed
+                    // saturation doesn't clip anything
+                    let lo: i16s = q[0].saturating_downcast(q[1]);
+                    let hi: i16s = q[2].saturating_downcast(q[3]);
+                    lo.be_u16s().saturating_downcast(hi.be_u16s())
+                },
+                |q| {
+                    let (lo, hi): (u16s, u16s) = q.upcast();
+                    let (a, b): (u32s, u32s) = lo.upcast();
+                    let (c, d): (u32s, u32s) = hi.upcast();
+                    [a.be_i32s(), b.be_i32s(), c.be_i32s(), d.be_i32s()]
+                });
+
+/// Return the dot product of a vector of unsigned bytes and a vector of
+/// signed bytes, as used with activations quantized to `u8` and weights
+/// quantized to `i8`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::quant::dot_u8i8;
+///
+/// # fn main() {
+/// assert_eq!(dot_u8i8(&[255; 100], &[-128; 100]), 100 * 255 * -128);
+/// # }
+/// ```
+pub fn dot_u8i8(a: &[u8], b: &[i8]) -> i64 {
+    assert_eq!(a.len(), b.len());
+    let (sum, done) = dot_u8i8_vectors(a, b);
+    sum + a[done..].iter().zip(&b[done..]).map(|(&x, &y)| x as i64 * y as i64).sum::<i64>()
+}
+
+/// Return the dot product of two vectors of signed bytes.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+/// ```
+/// extern crate faster;
+/// use faster::quant::dot_i8i8;
+///
+/// # fn main() {
+/// assert_eq!(dot_i8i8(&[-128, 127, 3], &[-128, -128, 3]), 16384 - 16256 + 9);
+/// # }
+/// ```
+pub fn dot_i8i8(a: &[i8], b: &[i8]) -> i64 {
+    assert_eq!(a.len(), b.len());
+    let (sum, done) = dot_i8i8_vectors(a, b);
+    sum + a[done..].iter().zip(&b[done..]).map(|(&x, &y)| x as i64 * y as i64).sum::<i64>()
+}
+
+/// Return the dot product of the whole vectors at the start of `$a` and
+/// `$b`, and how many elements that covers, by multiplying them with
+/// `pmaddubsw` and adding pairs of the results with `pmaddwd`.
+#[cfg(all(any(target_feature = "ssse3", target_feature = "avx2"),
+          not(all(target_feature = "avx512vnni", target_feature = "avx512vl")),
+          not(feature = "force-polyfill")))]
+macro_rules! dot_maddubs {
+    ($a:expr, $b:expr, $u8v:ident, $i8v:ident, $i16v:ident, $i32v:ident, $maddubs:ident, $madd:ident) => {{
+        use crate::core::mem::transmute;
+        use crate::intrin::sum::UpcastSum;
+        optimized!();
+        let (a, b) = ($a, $b);
+        let width = $u8v::WIDTH;
+        let (low, high, ones) = ($u8v::splat(0x7F), $u8v::splat(0x80), $i16v::splat(1));
+        let mut total = 0i64;
+        let mut sums = $i32v::splat(0);
+        let mut i = 0;
+        let mut pending = 0;
+        while i + width <= a.len() {
+            let (x, y) = ($u8v::load(a, i), $i8v::load(b, i));
+            // pmaddubsw saturates the sum of each pair of products, which
+            // can only overflow when both unsigned bytes have their top bit
+            // set. Multiplying the top bits separately keeps both in range.
+            let (lo, hi): ($i32v, $i32v) = unsafe {
+                (transmute($madd($maddubs(transmute(x & low), transmute(y)), transmute(ones))),
+                 transmute($madd($maddubs(transmute(x & high), transmute(y)), transmute(ones))))
+            };
+            sums = sums + lo + hi;
+            pending += 1;
+            if pending == FLUSH {
+                total += sums.sum_upcast();
+                sums = $i32v::splat(0);
+                pending = 0;
+            }
+            i += width;
+        }
+        (total + sums.sum_upcast(), i)
+    }}
+}
+
+/// Return the dot product of the whole vectors at the start of `$a` and
+/// `$b`, and how many elements that covers, by upcasting them to words and
+/// multiplying them with `pmaddwd`. `pmaddubsw` can't be used here, as
+/// moving the sign of one byte onto the other overflows at -128.
+#[cfg(all(any(target_feature = "sse2", target_feature = "avx2"), not(feature = "force-polyfill")))]
+macro_rules! dot_madd {
+    ($a:expr, $b:expr, $i8v:ident, $i16v:ident, $i32v:ident, $madd:ident) => {{
+        use crate::core::mem::transmute;
+        use crate::intrin::sum::UpcastSum;
+        optimized!();
+        let (a, b) = ($a, $b);
+        let width = $i8v::WIDTH;
+        let mut total = 0i64;
+        let mut sums = $i32v::splat(0);
+        let mut i = 0;
+        let mut pending = 0;
+        while i + width <= a.len() {
+            let (x0, x1): ($i16v, $i16v) = $i8v::load(a, i).upcast();
+            let (y0, y1): ($i16v, $i16v) = $i8v::load(b, i).upcast();
+            let (lo, hi): ($i32v, $i32v) = unsafe {
+                (transmute($madd(transmute(x0), transmute(y0))),
+                 transmute($madd(transmute(x1), transmute(y1))))
+            };
+            sums = sums + lo + hi;
+            pending += 1;
+            if pending == FLUSH {
+                total += sums.sum_upcast();
+                sums = $i32v::splat(0);
+                pending = 0;
+            }
+            i += width;
+        }
+        (total + sums.sum_upcast(), i)
+    }}
+}
+
+#[inline(always)]
+#[cfg(all(target_feature = "avx512vnni", target_feature = "avx512vl", not(feature = "force-polyfill")))]
+fn dot_u8i8_vectors(a: &[u8], b: &[i8]) -> (i64, usize) {
+    use crate::arch::current::vecs::{u8x32, i8x32, i32x8};
+    use crate::core::arch::x86_64::_mm256_dpbusd_epi32;
+    use crate::core::mem::transmute;
+    use crate::intrin::sum::UpcastSum;
+    optimized!();
+    // vpdpbusd adds each group of four products to its lane without
+    // saturating, so the lanes only need flushing to stay in range.
+    let mut total = 0i64;
+    let mut sums = i32x8::splat(0);
+    let mut i = 0;
+    let mut pending = 0;
+    while i + u8x32::WIDTH <= a.len() {
+        let (x, y) = (u8x32::load(a, i), i8x32::load(b, i));
+        sums = unsafe { transmute(_mm256_dpbusd_epi32(transmute(sums), transmute(x), transmute(y))) };
+        pending += 1;
+        if pending == FLUSH {
+            total += sums.sum_upcast();
+            sums = i32x8::splat(0);
+            pending = 0;
+        }
+        i += u8x32::WIDTH;
+    }
+    (total + sums.sum_upcast(), i)
+}
+
+#[inline(always)]
+#[cfg(all(target_feature = "avx2",
+          not(all(target_feature = "avx512vnni", target_feature = "avx512vl")),
+          not(feature = "force-polyfill")))]
+fn dot_u8i8_vectors(a: &[u8], b: &[i8]) -> (i64, usize) {
+    use crate::arch::current::vecs::{u8x32, i8x32, i16x16, i32x8};
+    use crate::vektor::x86::{_mm256_maddubs_epi16, _mm256_madd_epi16};
+    dot_maddubs!(a, b, u8x32, i8x32, i16x16, i32x8, _mm256_maddubs_epi16, _mm256_madd_epi16)
+}
+
+#[inline(always)]
+#[cfg(all(target_feature = "ssse3", not(target_feature = "avx2"), not(feature = "force-polyfill")))]
+fn dot_u8i8_vectors(a: &[u8], b: &[i8]) -> (i64, usize) {
+    use crate::arch::current::vecs::{u8x16, i8x16, i16x8, i32x4};
+    use crate::vektor::x86::{_mm_maddubs_epi16, _mm_madd_epi16};
+    dot_maddubs!(a, b, u8x16, i8x16, i16x8, i32x4, _mm_maddubs_epi16, _mm_madd_epi16)
+}
+
+#[inline(always)]
+#[cfg(any(not(any(target_feature = "ssse3", target_feature = "avx2")), feature = "force-polyfill"))]
+fn dot_u8i8_vectors(_: &[u8], _: &[i8]) -> (i64, usize) {
+    fallback!();
+    (0, 0)
+}
+
+#[inline(always)]
+#[cfg(all(target_feature = "avx2", not(feature = "force-polyfill")))]
+fn dot_i8i8_vectors(a: &[i8], b: &[i8]) -> (i64, usize) {
+    use crate::arch::current::vecs::{i8x32, i16x16, i32x8};
+    use crate::vektor::x86::_mm256_madd_epi16;
+    dot_madd!(a, b, i8x32, i16x16, i32x8, _mm256_madd_epi16)
+}
+
+#[inline(always)]
+#[cfg(all(target_feature = "sse2", not(target_feature = "avx2"), not(feature = "force-polyfill")))]
+fn dot_i8i8_vectors(a: &[i8], b: &[i8]) -> (i64, usize) {
+    use crate::arch::current::vecs::{i8x16, i16x8, i32x4};
+    use crate::vektor::x86::_mm_madd_epi16;
+    dot_madd!(a, b, i8x16, i16x8, i32x4, _mm_madd_epi16)
+}
+
+#[inline(always)]
+#[cfg(any(not(any(target_feature = "sse2", target_feature = "avx2")), feature = "force-polyfill"))]
+fn dot_i8i8_vectors(_: &[i8], _: &[i8]) -> (i64, usize) {
+    fallback!();
+    (0, 0)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn data(len: usize) -> Vec<u32> {
+        testing::data(len).into_iter().map(|x| (x >> 32) as u32).collect()
+    }
+
+    fn reference(x: f32, scale: f32, zero_point: f32, min: f32, max: f32) -> f32 {
+        ((x / scale).round_ties_even() + zero_point).max(min).min(max)
+    }
+
+    fn inputs(len: usize, scale: f32) -> Vec<f32> {
+        data(len).iter().enumerate().map(|(i, &x)| match i % 4 {
+            // Exactly halfway between two steps
+            0 => ((x % 600) as f32 - 300.0 + 0.5) * scale,
+            1 => (x % 100_000) as f32 / 1000.0 - 50.0,
+            2 => -((x % 100_000) as f32 / 1000.0),
+            _ => (x as i32) as f32,
+        }).collect()
+    }
+
+    #[test]
+    fn quantize_i8() {
+        for &(scale, zero_point) in [(1.0f32, 0i8), (0.1, -3), (0.37, 100), (2.0, -128)].iter() {
+            for &len in [0, 1, 15, 16, 17, 63, 64, 65, 1000].iter() {
+                let input = inputs(len, scale);
+                let mut output = vec![0i8; len];
+                quantize(&input, &mut output, scale, zero_point);
+                for (&x, &q) in input.iter().zip(output.iter()) {
+                    assert_eq!(q as f32, reference(x, scale, zero_point as f32, -128.0, 127.0), "{} {}", x, scale);
+                }
+
+                let mut restored = vec![0f32; len];
+                dequantize(&output, &mut restored, scale, zero_point);
+                for (&q, &x) in output.iter().zip(restored.iter()) {
+                    assert_eq!(x, (q as f32 - zero_point as f32) * scale);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quantize_u8() {
+        for &(scale, zero_point) in [(1.0f32, 0u8), (0.1, 128), (0.37, 7), (2.0, 255)].iter() {
+            for &len in [0, 1, 15, 16, 17, 63, 64, 65, 1000].iter() {
+                let input = inputs(len, scale);
+                let mut output = vec![0u8; len];
+                quantize(&input, &mut output, scale, zero_point);
+                for (&x, &q) in input.iter().zip(output.iter()) {
+                    assert_eq!(q as f32, reference(x, scale, zero_point as f32, 0.0, 255.0), "{} {}", x, scale);
+                }
+
+                let mut restored = vec![0f32; len];
+                dequantize(&output, &mut restored, scale, zero_point);
+                for (&q, &x) in output.iter().zip(restored.iter()) {
+                    assert_eq!(x, (q as f32 - zero_point as f32) * scale);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dot_products() {
+        // Long enough to flush the lanes several times
+        for &len in [0, 1, 31, 32, 33, 1000, FLUSH * 64 * 2 + 7].iter() {
+            let random = data(len);
+            let a = random.iter().map(|&x| x as u8).collect::<Vec<u8>>();
+            let b = random.iter().map(|&x| (x >> 8) as i8).collect::<Vec<i8>>();
+            let c = random.iter().map(|&x| (x >> 16) as i8).collect::<Vec<i8>>();
+            let wide_a = a.iter().map(|&x| x as i64).collect::<Vec<i64>>();
+            let wide_b = b.iter().map(|&x| x as i64).collect::<Vec<i64>>();
+            let wide_c = c.iter().map(|&x| x as i64).collect::<Vec<i64>>();
+            let expected = |x: &[i64], y: &[i64]| x.iter().zip(y).map(|(x, y)| x * y).sum::<i64>();
+            assert_eq!(dot_u8i8(&a, &b), expected(&wide_a[..], &wide_b[..]));
+            assert_eq!(dot_i8i8(&b, &c), expected(&wide_b[..], &wide_c[..]));
+        }
+
+        // The largest products, which saturate a naive pmaddubsw
+        for &len in [64, FLUSH * 64 + 1].iter() {
+            assert_eq!(dot_u8i8(&vec![255; len], &vec![-128; len]), len as i64 * 255 * -128);
+            assert_eq!(dot_u8i8(&vec![255; len], &vec![127; len]), len as i64 * 255 * 127);
+            assert_eq!(dot_i8i8(&vec![-128; len], &vec![-128; len]), len as i64 * 128 * 128);
+        }
+    }
+}
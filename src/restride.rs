@@ -0,0 +1,162 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::vecs::{Packed, Packable};
+use crate::intrin::restride::*;
+use crate::zip::SIMDZippedIterator;
+
+/// A tuple of vectors which may be stored as a single interleaved stream of
+/// scalars.
+pub trait Interleaved : Sized + Copy {
+    type Scalar : Packable;
+    type Scalars;
+    type Vector : Packed<Scalar = Self::Scalar>;
+
+    /// The number of vectors in this tuple.
+    const COUNT: usize;
+
+    /// Return a tuple of default vectors.
+    fn default() -> Self;
+
+    /// Write the elements of every vector in this tuple to `data`, interleaved
+    /// and beginning at `offset`. `Self::COUNT * Self::Vector::WIDTH` elements
+    /// are written.
+    fn store_interleaved(self, data: &mut [Self::Scalar], offset: usize);
+
+    /// Write the elements of every vector in this tuple to `data`, interleaved
+    /// and beginning at `offset`, skipping the first `skip` elements of each
+    /// vector.
+    fn store_interleaved_partial(self, data: &mut [Self::Scalar], offset: usize, skip: usize);
+}
+
+macro_rules! impl_interleaved {
+    ($count:expr, $interleave:ident, ($($v:ident),*), ($($n:tt),*)) => {
+        impl<V> Interleaved for ($($v),*) where V : Restride {
+            type Scalar = V::Scalar;
+            type Scalars = ($(<$v as Packed>::Scalar),*);
+            type Vector = V;
+
+            const COUNT: usize = $count;
+
+            #[inline(always)]
+            fn default() -> Self {
+                ($(<$v as Packed>::default()),*)
+            }
+
+            #[inline(always)]
+            fn store_interleaved(self, data: &mut [Self::Scalar], offset: usize) {
+                let out = self.0.$interleave($(self.$n),*);
+                let out = [out.0, $(out.$n),*];
+                for (i, v) in out.iter().enumerate() {
+                    v.store(data, offset + i * V::WIDTH);
+                }
+            }
+
+            #[inline(always)]
+            fn store_interleaved_partial(self, data: &mut [Self::Scalar], offset: usize, skip: usize) {
+                let vecs = [self.0, $(self.$n),*];
+                for i in skip..V::WIDTH {
+                    for (j, v) in vecs.iter().enumerate() {
+                        data[offset + (i - skip) * $count + j] = v.extract(i);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl_interleaved!(2, interleave_two, (V, V), (1));
+impl_interleaved!(3, interleave_three, (V, V, V), (1, 2));
+impl_interleaved!(4, interleave_four, (V, V, V, V), (1, 2, 3));
+
+/// A trait which can transform a stream of tuples of vectors into a
+/// contiguous, interleaved collection of scalars. This is the inverse of
+/// striding a collection.
+///
+/// ```
+/// extern crate faster;
+/// use faster::*;
+///
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() { }
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// let (r, g, b) = ([1u8; 100], [2u8; 100], [3u8; 100]);
+/// let mut rgb = [0u8; 300];
+/// (r.simd_iter(u8s(0)), g.simd_iter(u8s(0)), b.simd_iter(u8s(0))).zip()
+///     .simd_map(|(r, g, b)| (r * u8s(2), g * u8s(2), b * u8s(2)))
+///     .scalar_fill_interleaved(&mut rgb);
+/// assert_eq!(&rgb[..6], &[2, 4, 6, 2, 4, 6]);
+/// # }
+/// ```
+pub trait IntoScalarInterleaved<T> where T : Packable {
+    /// Take an iterator of tuples of SIMD vectors, and store them in-order
+    /// and interleaved in a Vec.
+    #[cfg(feature = "std")]
+    fn scalar_collect_interleaved(&mut self) -> Vec<T>;
+
+    /// Take an iterator of tuples of SIMD vectors, and store them in-order
+    /// and interleaved in `fill`.
+    fn scalar_fill_interleaved<'a>(&mut self, fill: &'a mut [T]) -> &'a mut [T];
+}
+
+impl<I, T> IntoScalarInterleaved<T> for I
+    where I : SIMDZippedIterator, I::Vectors : Interleaved<Scalar = T>, T : Packable {
+
+    #[inline(always)]
+    #[cfg(feature = "std")]
+    fn scalar_collect_interleaved(&mut self) -> Vec<T> {
+        let count = <I::Vectors as Interleaved>::COUNT;
+        let width = <<I::Vectors as Interleaved>::Vector as Packed>::WIDTH;
+        let mut ret = Vec::with_capacity((self.len() + 1) * width * count);
+
+        unsafe {
+            ret.set_len((self.len() + 1) * width * count);
+            let len = fill_interleaved(self, ret.as_mut_slice());
+            ret.set_len(len);
+        }
+        ret
+    }
+
+    #[inline(always)]
+    fn scalar_fill_interleaved<'a>(&mut self, fill: &'a mut [T]) -> &'a mut [T] {
+        fill_interleaved(self, fill);
+        fill
+    }
+}
+
+/// Store the contents of `iter` in `fill`, returning the number of scalars
+/// written.
+#[inline(always)]
+fn fill_interleaved<I, T>(iter: &mut I, fill: &mut [T]) -> usize
+    where I : SIMDZippedIterator, I::Vectors : Interleaved<Scalar = T>, T : Packable {
+    let count = <I::Vectors as Interleaved>::COUNT;
+    let step = <<I::Vectors as Interleaved>::Vector as Packed>::WIDTH * count;
+    let mut offset = 0;
+    let mut lastvec = <I::Vectors as Interleaved>::default();
+
+    while let Some(vecs) = iter.next() {
+        vecs.store_interleaved(fill, offset);
+        offset += step;
+        lastvec = vecs;
+    }
+
+    if let Some((p, n)) = iter.end() {
+        if offset > 0 {
+            // We stored a vector in this buffer; overwrite the unused elements
+            p.store_interleaved(fill, offset - n * count);
+            lastvec.store_interleaved(fill, offset - step);
+        } else {
+            // The buffer won't fit one vector; store elementwise
+            p.store_interleaved_partial(fill, offset, n);
+        }
+        offset + step - n * count
+    } else {
+        offset
+    }
+}
@@ -0,0 +1,397 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Classification of structural characters in delimited text, such as CSV
+//! and newline-delimited JSON.
+//!
+//! Input is processed in blocks of 64 bytes. Each block is compared against
+//! the characters of interest a vector at a time, and the comparison masks
+//! are packed into one 64-bit bitmap per character class, where the `n`th bit
+//! corresponds to the `n`th byte of the block. Quoted strings are found with a
+//! prefix XOR over the bitmap of unescaped quotes, which lets delimiters and
+//! newlines inside strings be masked away without a branch per byte.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::scan::*;
+//!
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() { }
+//!
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! let csv = b"id,name\n1,\"Smith, J\"\n";
+//! let fields: Vec<usize> = structural_indices(csv, CSV).collect();
+//! assert_eq!(fields, vec![2, 7, 9, 20]);
+//! # }
+//! ```
+
+use crate::vecs::Packed;
+use crate::arch::current::vecs::u8s;
+use crate::intrin::eq::Eq as PackedEq;
+use crate::intrin::movemask::Movemask;
+
+/// The number of bytes classified at once.
+pub const BLOCK_SIZE: usize = 64;
+
+/// The characters which separate fields and escape quotes in a format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The character which separates fields.
+    pub delimiter: u8,
+    /// Whether a backslash escapes the character after it. Formats which
+    /// escape quotes by doubling them, such as CSV, don't need this, as a
+    /// doubled quote closes and reopens a string.
+    pub backslash_escapes: bool,
+}
+
+/// Comma-separated values, as specified by RFC 4180.
+pub const CSV: Config = Config { delimiter: b',', backslash_escapes: false };
+
+/// Tab-separated values.
+pub const TSV: Config = Config { delimiter: b'\t', backslash_escapes: false };
+
+/// Newline-delimited JSON, where commas separate values within a record.
+pub const NDJSON: Config = Config { delimiter: b',', backslash_escapes: true };
+
+/// The positions of each class of character in a block of up to 64 bytes.
+/// Bits past the end of a short block are zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bitmaps {
+    /// Positions of `"`.
+    pub quotes: u64,
+    /// Positions of the delimiter.
+    pub delimiters: u64,
+    /// Positions of `\n`.
+    pub newlines: u64,
+    /// Positions of `\`.
+    pub backslashes: u64,
+    /// Positions of ` `, `\t`, `\r` and `\n`.
+    pub whitespace: u64,
+}
+
+/// Return the bitmap of the bytes in `block` which are equal to any of
+/// `chars`.
+#[inline(always)]
+fn bitmap(block: &[u8; BLOCK_SIZE], chars: &[u8]) -> u64 {
+    let mut bits = 0;
+    for offset in (0..BLOCK_SIZE).step_by(u8s::WIDTH) {
+        let v = u8s::load(block, offset);
+        let eq = chars.iter().fold(u8s(0), |acc, &c| acc | v.eq_mask(u8s(c)));
+        bits |= eq.movemask() << offset;
+    }
+    bits
+}
+
+/// Classify each byte of `block`, which may be no longer than 64 bytes.
+///
+/// ```
+/// extern crate faster;
+/// use faster::scan::*;
+///
+/// # fn main() {
+/// let bitmaps = classify(b"a,\"b\"\n", CSV);
+/// assert_eq!(bitmaps.delimiters, 0b000010);
+/// assert_eq!(bitmaps.quotes, 0b010100);
+/// assert_eq!(bitmaps.newlines, 0b100000);
+/// # }
+/// ```
+pub fn classify(block: &[u8], config: Config) -> Bitmaps {
+    assert!(block.len() <= BLOCK_SIZE, "A block may be no longer than 64 bytes.");
+
+    // Zero isn't a character of interest, so padding a short block with it
+    // leaves the bits past its end clear
+    let mut buf = [0u8; BLOCK_SIZE];
+    buf[..block.len()].copy_from_slice(block);
+    Bitmaps {
+        quotes: bitmap(&buf, &[b'"']),
+        delimiters: if config.delimiter == 0 { 0 } else { bitmap(&buf, &[config.delimiter]) },
+        newlines: bitmap(&buf, &[b'\n']),
+        backslashes: bitmap(&buf, &[b'\\']),
+        whitespace: bitmap(&buf, &[b' ', b'\t', b'\r', b'\n']),
+    }
+}
+
+/// Return a bitmap whose `n`th bit is the XOR of the first `n + 1` bits of
+/// `bits`. Applied to a bitmap of quotes, this sets every bit from an opening
+/// quote up to, but not including, its closing quote.
+///
+/// ```
+/// extern crate faster;
+/// use faster::scan::*;
+///
+/// # fn main() {
+/// assert_eq!(prefix_xor(0b0100_0100), 0b0011_1100);
+/// # }
+/// ```
+#[inline]
+pub fn prefix_xor(bits: u64) -> u64 {
+    let mut bits = bits;
+    bits ^= bits << 1;
+    bits ^= bits << 2;
+    bits ^= bits << 4;
+    bits ^= bits << 8;
+    bits ^= bits << 16;
+    bits ^= bits << 32;
+    bits
+}
+
+/// Return the bitmap of characters which are escaped by an odd-length run of
+/// the backslashes in `backslashes`. `carry` holds whether the first
+/// character of the block is escaped by the previous block, and is updated
+/// for the next block.
+///
+/// ```
+/// extern crate faster;
+/// use faster::scan::*;
+///
+/// # fn main() {
+/// let mut carry = 0;
+/// // `\\\"\a` escapes the second backslash, the quote, and the `a`
+/// assert_eq!(escaped(0b10111, &mut carry), 0b101010);
+/// assert_eq!(carry, 0);
+/// // A backslash at the end of a block escapes the start of the next
+/// assert_eq!(escaped(1 << 63, &mut carry), 0);
+/// assert_eq!(escaped(0, &mut carry), 1);
+/// # }
+/// ```
+#[inline]
+pub fn escaped(backslashes: u64, carry: &mut u64) -> u64 {
+    const EVEN_BITS: u64 = 0x5555_5555_5555_5555;
+
+    // An escaped backslash doesn't escape the character after it
+    let backslashes = backslashes & !*carry;
+    let follows_escape = backslashes << 1 | *carry;
+
+    // Adding the start of each run which begins on an odd bit to the run
+    // carries out of its end, which flips the parity of the bit after it
+    let odd_starts = backslashes & !EVEN_BITS & !follows_escape;
+    let (even_starts, overflow) = odd_starts.overflowing_add(backslashes);
+    *carry = overflow as u64;
+    (EVEN_BITS ^ (even_starts << 1)) & follows_escape
+}
+
+/// The bitmaps of one block of input, along with the strings and structural
+/// characters found in it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block {
+    /// The offset of this block in the input.
+    pub offset: usize,
+    /// The number of bytes in this block, which is 64 for all but the last.
+    pub len: usize,
+    /// The classification of each byte in this block.
+    pub bitmaps: Bitmaps,
+    /// Positions of quotes which aren't escaped.
+    pub quotes: u64,
+    /// Positions of bytes inside strings, including opening quotes but not
+    /// closing quotes.
+    pub in_string: u64,
+    /// Positions of delimiters and newlines outside of strings.
+    pub structurals: u64,
+}
+
+/// An iterator over the blocks of an input, which carries string and escape
+/// state from one block to the next.
+#[derive(Clone, Debug)]
+pub struct Blocks<'a> {
+    data: &'a [u8],
+    config: Config,
+    offset: usize,
+    escape_carry: u64,
+    string_carry: u64,
+}
+
+/// Return an iterator over each 64-byte block of `data`.
+pub fn blocks(data: &[u8], config: Config) -> Blocks {
+    Blocks { data, config, offset: 0, escape_carry: 0, string_carry: 0 }
+}
+
+impl<'a> Blocks<'a> {
+    /// Return whether the input so far ends inside a string.
+    pub fn in_string(&self) -> bool {
+        self.string_carry != 0
+    }
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block;
+
+    #[inline]
+    fn next(&mut self) -> Option<Block> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        let end = (self.offset + BLOCK_SIZE).min(self.data.len());
+        let bitmaps = classify(&self.data[self.offset..end], self.config);
+        let quotes = if self.config.backslash_escapes {
+            bitmaps.quotes & !escaped(bitmaps.backslashes, &mut self.escape_carry)
+        } else {
+            bitmaps.quotes
+        };
+
+        // Every bit of the carry is set if the previous block ended inside a
+        // string, which inverts the whole mask
+        let in_string = prefix_xor(quotes) ^ self.string_carry;
+        self.string_carry = ((in_string as i64) >> 63) as u64;
+
+        let block = Block {
+            offset: self.offset,
+            len: end - self.offset,
+            bitmaps,
+            quotes,
+            in_string,
+            structurals: (bitmaps.delimiters | bitmaps.newlines) & !in_string,
+        };
+        self.offset = end;
+        Some(block)
+    }
+}
+
+/// An iterator over the positions of delimiters and newlines which aren't
+/// inside a string.
+#[derive(Clone, Debug)]
+pub struct StructuralIndices<'a> {
+    blocks: Blocks<'a>,
+    offset: usize,
+    bits: u64,
+}
+
+/// Return an iterator over the positions of delimiters and newlines in
+/// `data` which aren't inside a string, in ascending order.
+pub fn structural_indices(data: &[u8], config: Config) -> StructuralIndices {
+    StructuralIndices { blocks: blocks(data, config), offset: 0, bits: 0 }
+}
+
+impl<'a> StructuralIndices<'a> {
+    /// Return whether the input so far ends inside a string, which means it
+    /// has an unterminated quote once the iterator is exhausted.
+    pub fn in_string(&self) -> bool {
+        self.blocks.in_string()
+    }
+}
+
+impl<'a> Iterator for StructuralIndices<'a> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while self.bits == 0 {
+            let block = self.blocks.next()?;
+            self.offset = block.offset;
+            self.bits = block.structurals;
+        }
+        let index = self.offset + self.bits.trailing_zeros() as usize;
+        // Clear the lowest set bit
+        self.bits &= self.bits - 1;
+        Some(index)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn data(len: usize, alphabet: &[u8]) -> Vec<u8> {
+        testing::data(len).into_iter().map(|x| alphabet[(x >> 8) as usize % alphabet.len()]).collect()
+    }
+
+    fn bit(block: &[u8], pred: impl Fn(u8) -> bool) -> u64 {
+        block.iter().enumerate().fold(0, |acc, (i, &b)| acc | (pred(b) as u64) << i)
+    }
+
+    /// Return the structural indices of `data` and whether it ends in a
+    /// string, one byte at a time.
+    fn structural_reference(data: &[u8], config: Config) -> (Vec<usize>, bool) {
+        let (mut in_string, mut escaping) = (false, false);
+        let mut indices = vec![];
+        for (i, &b) in data.iter().enumerate() {
+            if escaping {
+                escaping = false;
+            } else if b == b'\\' && config.backslash_escapes {
+                escaping = true;
+            } else if b == b'"' {
+                in_string = !in_string;
+            } else if !in_string && (b == config.delimiter || b == b'\n') {
+                indices.push(i);
+            }
+        }
+        (indices, in_string)
+    }
+
+    #[test]
+    fn classify_matches_scalar() {
+        let input = data(1000, b"ab,\t\"\\\n \r\0\xFF");
+        for len in 0..=BLOCK_SIZE {
+            for block in input.chunks(len.max(1)).filter(|c| c.len() == len) {
+                let bitmaps = classify(block, TSV);
+                assert_eq!(bitmaps.quotes, bit(block, |b| b == b'"'));
+                assert_eq!(bitmaps.delimiters, bit(block, |b| b == b'\t'));
+                assert_eq!(bitmaps.newlines, bit(block, |b| b == b'\n'));
+                assert_eq!(bitmaps.backslashes, bit(block, |b| b == b'\\'));
+                assert_eq!(bitmaps.whitespace, bit(block, |b| b" \t\r\n".contains(&b)));
+            }
+        }
+        assert_eq!(classify(&[0; 64], Config { delimiter: 0, backslash_escapes: false }).delimiters, 0);
+    }
+
+    #[test]
+    fn prefix_xor_matches_scalar() {
+        for &bits in [0, 1, 1 << 63, !0, 0x8000_0001, 0xDEAD_BEEF_CAFE_F00D].iter() {
+            let expected = (0..64).fold((0u64, 0u64), |(acc, parity), i| {
+                let parity = parity ^ (bits >> i & 1);
+                (acc | parity << i, parity)
+            }).0;
+            assert_eq!(prefix_xor(bits), expected);
+        }
+    }
+
+    #[test]
+    fn escaped_matches_scalar() {
+        let input = data(4096, b"\\\\\\a\"");
+        let (mut carry, mut escaping) = (0, false);
+        for block in input.chunks(BLOCK_SIZE) {
+            let mut expected = 0;
+            for (i, &b) in block.iter().enumerate() {
+                if escaping {
+                    expected |= 1 << i;
+                    escaping = false;
+                } else {
+                    escaping = b == b'\\';
+                }
+            }
+            assert_eq!(escaped(bit(block, |b| b == b'\\'), &mut carry), expected);
+            assert_eq!(carry != 0, escaping);
+        }
+    }
+
+    #[test]
+    fn structural_indices_match_scalar() {
+        for &config in [CSV, TSV, NDJSON].iter() {
+            for len in (0..300).chain(1000..1010) {
+                let input = data(len, b"ab\",\t\\\n");
+                let mut iter = structural_indices(&input, config);
+                let indices: Vec<usize> = iter.by_ref().collect();
+                let (expected, in_string) = structural_reference(&input, config);
+                assert_eq!(indices, expected, "{:?} {}", config, len);
+                assert_eq!(iter.in_string(), in_string);
+            }
+        }
+    }
+
+    #[test]
+    fn strings_span_blocks() {
+        let mut input = vec![b'a'; 200];
+        input[10] = b'"';
+        input[100] = b',';
+        input[150] = b'"';
+        input[160] = b',';
+        assert_eq!(structural_indices(&input, CSV).collect::<Vec<_>>(), vec![160]);
+        let in_string: Vec<u64> = blocks(&input, CSV).map(|b| b.in_string).collect();
+        assert_eq!(in_string, vec![!0 << 10, !0, !0 >> (64 - 22), 0]);
+    }
+}
@@ -0,0 +1,322 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Sorting slices of primitives.
+//!
+//! [`sort_unstable`] is a quicksort whose partitioning compares a whole
+//! vector of elements against the pivot at once, and which sorts the pieces
+//! small enough to fit in two vectors with the bitonic networks behind
+//! [`SortLanes`]. Floats are sorted by the total order of `f32::total_cmp`,
+//! so NaNs and signed zeroes have a place in the output, rather than being
+//! compared as equal to everything or each other.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::sort::sort_unstable;
+//!
+//! # fn main() {
+//! let mut data = [5u32, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+//! sort_unstable(&mut data);
+//! assert_eq!(data, [1, 1, 2, 3, 4, 5, 5, 5, 6, 9]);
+//!
+//! let mut data = [0.5f32, -0.0, 3.0, 0.0, -7.5];
+//! sort_unstable(&mut data);
+//! assert_eq!(data, [-7.5, -0.0, 0.0, 0.5, 3.0]);
+//! # }
+//! ```
+//!
+//! [`sort_unstable`]: fn.sort_unstable.html
+//! [`SortLanes`]: ../intrin/sort/trait.SortLanes.html
+
+use crate::arch::current::vecs::{u8s, i8s, u16s, i16s, u32s, i32s, u64s, i64s};
+use crate::intrin::cmp::Cmp;
+use crate::intrin::eq::Eq as PackedEq;
+use crate::intrin::movemask::Movemask;
+use crate::intrin::sort::SortLanes;
+use crate::intrin::transmute::Transmute;
+use crate::vecs::Packed;
+use crate::core::slice;
+
+/// Slices of this many elements or fewer which don't fit in two vectors are
+/// insertion sorted.
+const INSERTION_THRESHOLD: usize = 24;
+
+/// A primitive which can be sorted by [`sort_unstable`].
+///
+/// [`sort_unstable`]: fn.sort_unstable.html
+pub trait Sortable : Copy {
+    #[doc(hidden)]
+    fn sort_slice(data: &mut [Self]);
+}
+
+/// Sort `data` in ascending order. Equal elements may be reordered, and
+/// floats are ordered as by `total_cmp`.
+///
+/// ```
+/// extern crate faster;
+/// use faster::sort::sort_unstable;
+///
+/// # fn main() {
+/// let mut data = [3i16, -1, 2, -8];
+/// sort_unstable(&mut data);
+/// assert_eq!(data, [-8, -1, 2, 3]);
+/// # }
+/// ```
+#[inline]
+pub fn sort_unstable<T : Sortable>(data: &mut [T]) {
+    T::sort_slice(data)
+}
+
+/// An integer whose slices are sorted with vectors of `Vector`.
+trait SortKey : Copy + Ord {
+    type Vector : Packed<Scalar = Self> + SortLanes;
+
+    /// The element to fill the unused elements of a vector with, so they're
+    /// sorted to the end.
+    const MAX: Self;
+
+    /// Return a mask whose `n`th bit is set if the `n`th element of the vector
+    /// at `offset` in `data` is less than `pivot`, or equal to it if
+    /// `or_equal` is set.
+    fn below(data: &[Self], offset: usize, pivot: Self::Vector, or_equal: bool) -> u64;
+}
+
+macro_rules! impl_sort_key {
+    ($($el:ty, $vec:ty);*) => {
+        $(
+            impl SortKey for $el {
+                type Vector = $vec;
+                const MAX: Self = <$el>::MAX;
+
+                #[inline(always)]
+                fn below(data: &[Self], offset: usize, pivot: $vec, or_equal: bool) -> u64 {
+                    let v = <$vec>::load(data, offset);
+                    // Every byte of an element of an equality mask is the
+                    // same, so only the first bit of each element is kept.
+                    let (bits, ge) = if or_equal {
+                        (Cmp::min(&v, pivot).eq_mask(v).be_u8s().movemask(), false)
+                    } else {
+                        (Cmp::max(&v, pivot).eq_mask(v).be_u8s().movemask(), true)
+                    };
+                    let size = crate::core::mem::size_of::<$el>();
+                    let mut mask = 0;
+                    for i in 0..<$vec>::WIDTH {
+                        mask |= ((bits >> (i * size)) & 1) << i;
+                    }
+                    if ge { !mask & (!0u64 >> (64 - <$vec>::WIDTH)) } else { mask }
+                }
+            }
+
+            impl Sortable for $el {
+                #[inline]
+                fn sort_slice(data: &mut [Self]) {
+                    quicksort(data, 2 * (usize::BITS - data.len().leading_zeros()));
+                }
+            }
+        )*
+    }
+}
+
+impl_sort_key!(u8, u8s; i8, i8s; u16, u16s; i16, i16s;
+               u32, u32s; i32, i32s; u64, u64s; i64, i64s);
+
+macro_rules! impl_sortable_float {
+    ($($el:ty, $int:ty, $uint:ty);*) => {
+        $(
+            impl Sortable for $el {
+                #[inline]
+                fn sort_slice(data: &mut [Self]) {
+                    // Flipping every bit but the sign of a negative float
+                    // makes its bits order like `total_cmp` when read as a
+                    // signed integer. Doing it twice gives the float back.
+                    let keys = unsafe {
+                        slice::from_raw_parts_mut(data.as_mut_ptr() as *mut $int, data.len())
+                    };
+                    let flip = |k: &mut $int| *k ^= ((*k >> (<$int>::BITS - 1)) as $uint >> 1) as $int;
+                    keys.iter_mut().for_each(flip);
+                    <$int>::sort_slice(keys);
+                    keys.iter_mut().for_each(flip);
+                }
+            }
+        )*
+    }
+}
+
+impl_sortable_float!(f32, i32, u32; f64, i64, u64);
+
+/// Move the elements of `data` which are less than `pivot` (or equal to it,
+/// if `or_equal` is set) to its start, and return how many there are.
+#[inline(always)]
+fn partition<T : SortKey>(data: &mut [T], pivot: T, or_equal: bool) -> usize {
+    let width = T::Vector::WIDTH;
+    let pivots = T::Vector::splat(pivot);
+    let mut store = 0;
+    let mut i = 0;
+    while i + width <= data.len() {
+        // Elements past the current one haven't moved since `below` looked
+        // at them, so the mask stays valid while we swap.
+        let mut mask = T::below(data, i, pivots, or_equal);
+        while mask != 0 {
+            data.swap(store, i + mask.trailing_zeros() as usize);
+            store += 1;
+            mask &= mask - 1;
+        }
+        i += width;
+    }
+    for j in i..data.len() {
+        if data[j] < pivot || (or_equal && data[j] == pivot) {
+            data.swap(store, j);
+            store += 1;
+        }
+    }
+    store
+}
+
+#[inline(always)]
+fn median_of_three<T : SortKey>(a: T, b: T, c: T) -> T {
+    a.max(b).min(a.min(b).max(c))
+}
+
+/// Sort `data`, which fits in two vectors, with a sorting network.
+#[inline(always)]
+fn sort_network<T : SortKey>(data: &mut [T]) {
+    let width = T::Vector::WIDTH;
+    debug_assert!(data.len() <= width * 2);
+    let load = |offset: usize| {
+        (0..width).filter(|i| offset + i < data.len())
+            .fold(T::Vector::splat(T::MAX), |mut v, i| v.replace(i, data[offset + i]))
+    };
+    let (lo, hi) = load(0).sort_lanes().merge_sorted(load(width).sort_lanes());
+    for (i, x) in data.iter_mut().enumerate() {
+        *x = if i < width { lo.extract(i) } else { hi.extract(i - width) };
+    }
+}
+
+#[inline(always)]
+fn insertion_sort<T : SortKey>(data: &mut [T]) {
+    for i in 1..data.len() {
+        let x = data[i];
+        let mut j = i;
+        while j > 0 && data[j - 1] > x {
+            data[j] = data[j - 1];
+            j -= 1;
+        }
+        data[j] = x;
+    }
+}
+
+fn quicksort<T : SortKey>(data: &mut [T], limit: u32) {
+    let mut data = data;
+    let mut limit = limit;
+    loop {
+        let len = data.len();
+        if len <= T::Vector::WIDTH * 2 {
+            return sort_network(data);
+        }
+        if len <= INSERTION_THRESHOLD {
+            return insertion_sort(data);
+        }
+        if limit == 0 {
+            // Too many bad pivots; fall back to a sort which can't go
+            // quadratic
+            return data.sort_unstable();
+        }
+        limit -= 1;
+
+        let pivot = median_of_three(data[0], data[len / 2], data[len - 1]);
+        let mid = partition(data, pivot, false);
+        let rest = data;
+        if mid == 0 {
+            // The pivot is the smallest element, so everything equal to it
+            // is already where it belongs.
+            let equal = partition(rest, pivot, true);
+            data = &mut rest[equal..];
+            continue;
+        }
+
+        // Recurse into the smaller side to bound the stack depth
+        let (left, right) = rest.split_at_mut(mid);
+        if left.len() < right.len() {
+            quicksort(left, limit);
+            data = right;
+        } else {
+            quicksort(right, limit);
+            data = left;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing::data;
+
+    const LENGTHS: &[usize] = &[0, 1, 2, 3, 7, 8, 15, 16, 17, 31, 32, 33, 63, 64, 65,
+                                100, 127, 128, 129, 255, 256, 1000, 4099];
+
+    macro_rules! test_sort_ints {
+        ($($el:ty, $fn:ident);*) => {
+            $(
+                #[test]
+                fn $fn() {
+                    for &len in LENGTHS {
+                        let random = data(len);
+                        let inputs: Vec<Vec<$el>> = vec![
+                            random.iter().map(|&x| x as $el).collect(),
+                            // Mostly duplicates
+                            random.iter().map(|&x| (x % 5) as $el).collect(),
+                            (0..len).map(|x| x as $el).collect(),
+                            (0..len).rev().map(|x| x as $el).collect(),
+                            vec![<$el>::MAX; len],
+                            random.iter().map(|&x| if x % 2 == 0 { <$el>::MIN } else { <$el>::MAX }).collect(),
+                        ];
+                        for input in inputs {
+                            let mut expected = input.clone();
+                            expected.sort_unstable();
+                            let mut actual = input;
+                            sort_unstable(&mut actual);
+                            assert_eq!(actual, expected, "{}", len);
+                        }
+                    }
+                }
+            )*
+        }
+    }
+
+    test_sort_ints!(u8, sort_u8; i8, sort_i8; u16, sort_u16; i16, sort_i16;
+                    u32, sort_u32; i32, sort_i32; u64, sort_u64; i64, sort_i64);
+
+    macro_rules! test_sort_floats {
+        ($($el:tt, $uint:ty, $fn:ident);*) => {
+            $(
+                #[test]
+                fn $fn() {
+                    let specials = [0.0, -0.0, $el::INFINITY, $el::NEG_INFINITY, $el::NAN,
+                                    -$el::NAN, $el::MIN_POSITIVE, $el::MAX, $el::MIN];
+                    for &len in LENGTHS {
+                        let random = data(len);
+                        let inputs: Vec<Vec<$el>> = vec![
+                            random.iter().map(|&x| (x as i64 as $el) / 3.0).collect(),
+                            random.iter().map(|&x| $el::from_bits(x as $uint)).collect(),
+                            random.iter().map(|&x| specials[x as usize % specials.len()]).collect(),
+                        ];
+                        for input in inputs {
+                            let mut expected = input.clone();
+                            expected.sort_unstable_by(|a, b| a.total_cmp(b));
+                            let mut actual = input;
+                            sort_unstable(&mut actual);
+                            let bits = |v: &[$el]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+                            assert_eq!(bits(&actual), bits(&expected), "{}", len);
+                        }
+                    }
+                }
+            )*
+        }
+    }
+
+    test_sort_floats!(f32, u32, sort_f32; f64, u64, sort_f64);
+}
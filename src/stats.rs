@@ -0,0 +1,401 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Summary statistics of slices of floats, in one pass.
+//!
+//! Each lane of a vector keeps its own count, mean and sum of squared
+//! deviations (Welford's method), and its own minimum and maximum along with
+//! the vector they came from. Every few thousand vectors, the lanes are
+//! merged horizontally into a running total kept in `f64`.
+//!
+//! # Portability
+//!
+//! The count, minimum, maximum and their indices are the same on every
+//! target; ties go to the lowest index. The mean and variance are computed by
+//! adding the same numbers in a different order depending on the width of
+//! the vector, so they may differ in their last few bits between targets, as
+//! they would between any two orders of summation.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::stats::*;
+//!
+//! # fn main() {
+//! let data = [2.0f32, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+//! let summary = stats(&data);
+//! assert_eq!(summary.count, 8);
+//! assert_eq!(summary.mean, 5.0);
+//! assert_eq!(summary.variance, 4.0);
+//! assert_eq!((summary.min, summary.min_index), (2.0, Some(0)));
+//! assert_eq!((summary.max, summary.max_index), (9.0, Some(7)));
+//! # }
+//! ```
+
+use crate::arch::current::vecs::{f32s, f64s, u32s, u64s};
+use crate::intrin::cmp::Cmp;
+use crate::intrin::eq::Eq as PackedEq;
+use crate::intrin::transmute::Transmute;
+use crate::into_iters::IntoSIMDRefIterator;
+use crate::iters::SIMDIterable;
+use crate::vecs::Packed;
+
+/// The number of vectors each lane accumulates before the lanes are merged.
+/// This keeps each lane's count exact in its own float type.
+const BLOCK: usize = 4096;
+
+/// Summary statistics of a slice of floats.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats<T> {
+    /// The number of elements summarized. NaNs are only counted if they
+    /// aren't ignored.
+    pub count: usize,
+    /// The number of NaNs in the slice, whether or not they were ignored.
+    pub nans: usize,
+    /// The arithmetic mean, or NaN if nothing was summarized.
+    pub mean: T,
+    /// The population variance, or NaN if nothing was summarized.
+    pub variance: T,
+    /// The smallest element.
+    pub min: T,
+    /// The index of the first occurrence of the smallest element.
+    pub min_index: Option<usize>,
+    /// The largest element.
+    pub max: T,
+    /// The index of the first occurrence of the largest element.
+    pub max_index: Option<usize>,
+}
+
+/// A float which can be summarized by [`stats`].
+///
+/// [`stats`]: fn.stats.html
+pub trait Statistical : Copy {
+    #[doc(hidden)]
+    fn summarize(data: &[Self], ignore_nans: bool) -> Stats<Self>;
+}
+
+/// Return the summary statistics of `data`. If `data` contains a NaN, the
+/// mean, variance, minimum and maximum are NaN, and both indices are those of
+/// the first NaN.
+///
+/// ```
+/// extern crate faster;
+/// use faster::stats::stats;
+///
+/// # fn main() {
+/// let summary = stats(&[1.0f64, ::std::f64::NAN, 3.0]);
+/// assert_eq!(summary.count, 3);
+/// assert_eq!(summary.nans, 1);
+/// assert!(summary.mean.is_nan());
+/// assert_eq!(summary.min_index, Some(1));
+/// # }
+/// ```
+#[inline]
+pub fn stats<T : Statistical>(data: &[T]) -> Stats<T> {
+    T::summarize(data, false)
+}
+
+/// Return the summary statistics of the elements of `data` which aren't NaN.
+///
+/// ```
+/// extern crate faster;
+/// use faster::stats::stats_ignoring_nans;
+///
+/// # fn main() {
+/// let summary = stats_ignoring_nans(&[1.0f64, ::std::f64::NAN, 3.0]);
+/// assert_eq!(summary.count, 2);
+/// assert_eq!(summary.nans, 1);
+/// assert_eq!(summary.mean, 2.0);
+/// assert_eq!(summary.max_index, Some(2));
+/// # }
+/// ```
+#[inline]
+pub fn stats_ignoring_nans<T : Statistical>(data: &[T]) -> Stats<T> {
+    T::summarize(data, true)
+}
+
+/// The statistics of everything merged so far.
+struct Running<T> {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: Option<(T, usize)>,
+    max: Option<(T, usize)>,
+}
+
+impl<T> Running<T> where T : Copy + PartialOrd + Into<f64> {
+    fn new() -> Self {
+        Self { count: 0, mean: 0.0, m2: 0.0, min: None, max: None }
+    }
+
+    /// Merge in the moments of `count` other elements (Chan et al.).
+    #[inline(always)]
+    fn merge_moments(&mut self, count: usize, mean: f64, m2: f64) {
+        let total = self.count + count;
+        let delta = mean - self.mean;
+        self.mean += delta * count as f64 / total as f64;
+        self.m2 += m2 + delta * delta * self.count as f64 * count as f64 / total as f64;
+        self.count = total;
+    }
+
+    #[inline(always)]
+    fn merge_extremes(&mut self, min: (T, usize), max: (T, usize)) {
+        match self.min {
+            Some((v, i)) if v < min.0 || (v == min.0 && i < min.1) => {},
+            _ => self.min = Some(min),
+        }
+        match self.max {
+            Some((v, i)) if v > max.0 || (v == max.0 && i < max.1) => {},
+            _ => self.max = Some(max),
+        }
+    }
+
+    #[inline(always)]
+    fn push(&mut self, value: T, index: usize) {
+        self.merge_moments(1, value.into(), 0.0);
+        self.merge_extremes((value, index), (value, index));
+    }
+}
+
+/// Return `a` where `mask` is set and `b` elsewhere.
+macro_rules! select {
+    ($mask:expr, $a:expr, $b:expr) => {{
+        let b = $b;
+        b ^ (($a ^ b) & $mask)
+    }};
+    ($bits:ident, $float:ident; $mask:expr, $a:expr, $b:expr) => {{
+        let b = $b.$bits();
+        unsafe { (b ^ (($a.$bits() ^ b) & $mask)).$float() }
+    }};
+}
+
+macro_rules! impl_statistical {
+    ($($el:tt, $vec:ty, $mask:ty, $bits:ident, $float:ident);*) => {
+        $(
+            impl Statistical for $el {
+                fn summarize(data: &[Self], ignore_nans: bool) -> Stats<Self> {
+                    let width = <$vec>::WIDTH;
+                    let (zero, one) = (<$vec>::splat(0.0), <$vec>::splat(1.0));
+                    let all = <$mask>::splat(!0);
+                    let mut running = Running::new();
+
+                    for (block, chunk) in data.chunks(BLOCK * width).enumerate() {
+                        let base = block * BLOCK * width;
+                        let (mut n, mut mean, mut m2) = (zero, zero, zero);
+                        let (mut min, mut max) = (zero, zero);
+                        let (mut min_at, mut max_at) = (<$mask>::splat(0), <$mask>::splat(0));
+                        let mut iter = chunk.simd_iter(zero);
+                        let mut vector = 0;
+
+                        while let Some(v) = iter.next() {
+                            let valid = v.eq_mask(v);
+                            let first = n.eq_mask(zero);
+                            let at = <$mask>::splat(vector);
+
+                            // Compute every lane's update, but only keep it
+                            // in the lanes which aren't NaN
+                            let n_next = n + one;
+                            let delta = v - mean;
+                            let mean_next = mean + delta / n_next;
+                            m2 = select!($bits, $float; valid, m2 + delta * (v - mean_next), m2);
+                            mean = select!($bits, $float; valid, mean_next, mean);
+                            n = select!($bits, $float; valid, n_next, n);
+
+                            // Only strictly better elements replace the
+                            // extremes, so each lane keeps its first one
+                            let lt = Cmp::max(&v, min).eq_mask(v) ^ all;
+                            let gt = Cmp::min(&v, max).eq_mask(v) ^ all;
+                            let take_min = (lt | first) & valid;
+                            let take_max = (gt | first) & valid;
+                            min = select!($bits, $float; take_min, v, min);
+                            max = select!($bits, $float; take_max, v, max);
+                            min_at = select!(take_min, at, min_at);
+                            max_at = select!(take_max, at, max_at);
+                            vector += 1;
+                        }
+
+                        for lane in 0..width {
+                            let count = n.extract(lane) as usize;
+                            if count > 0 {
+                                let index = |at: $mask| base + at.extract(lane) as usize * width + lane;
+                                running.merge_moments(count, mean.extract(lane).into(), m2.extract(lane).into());
+                                running.merge_extremes((min.extract(lane), index(min_at)),
+                                                       (max.extract(lane), index(max_at)));
+                            }
+                        }
+
+                        let tail = iter.scalar_pos();
+                        for (i, &x) in chunk[tail..].iter().enumerate() {
+                            if !x.is_nan() {
+                                running.push(x, base + tail + i);
+                            }
+                        }
+                    }
+
+                    let nans = data.len() - running.count;
+                    if nans > 0 && !ignore_nans {
+                        let first = data.iter().position(|x| x.is_nan());
+                        return Stats {
+                            count: data.len(),
+                            nans,
+                            mean: $el::NAN,
+                            variance: $el::NAN,
+                            min: $el::NAN,
+                            min_index: first,
+                            max: $el::NAN,
+                            max_index: first,
+                        };
+                    }
+
+                    let summarized = running.count > 0;
+                    let (min, min_index) = running.min.map_or(($el::NAN, None), |(v, i)| (v, Some(i)));
+                    let (max, max_index) = running.max.map_or(($el::NAN, None), |(v, i)| (v, Some(i)));
+                    Stats {
+                        count: running.count,
+                        nans,
+                        mean: if summarized { running.mean as $el } else { $el::NAN },
+                        variance: if summarized { (running.m2 / running.count as f64) as $el } else { $el::NAN },
+                        min,
+                        min_index,
+                        max,
+                        max_index,
+                    }
+                }
+            }
+
+            impl Stats<$el> {
+                /// Return the sample variance, which divides by one less than
+                /// the count, or NaN if fewer than two elements were
+                /// summarized.
+                #[inline]
+                pub fn sample_variance(&self) -> $el {
+                    if self.count < 2 {
+                        $el::NAN
+                    } else {
+                        self.variance * self.count as $el / (self.count - 1) as $el
+                    }
+                }
+
+                /// Return the population standard deviation.
+                #[inline]
+                pub fn std_dev(&self) -> $el {
+                    self.variance.sqrt()
+                }
+            }
+        )*
+    }
+}
+
+impl_statistical!(f32, f32s, u32s, be_u32s, be_f32s_unchecked;
+                  f64, f64s, u64s, be_u64s, be_f64s_unchecked);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn data(len: usize) -> Vec<f64> {
+        testing::data(len).into_iter().map(|x| (x >> 40) as f64 / 1024.0 - 8000.0).collect()
+    }
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= tolerance * b.abs().max(1.0)
+    }
+
+    macro_rules! test_stats {
+        ($($el:tt, $tolerance:expr, $fn:ident);*) => {
+            $(
+                #[test]
+                fn $fn() {
+                    for &len in [1, 2, 3, 7, 16, 17, 100, 1001, BLOCK * 16 + 5].iter() {
+                        let input = data(len).iter().map(|&x| x as $el).collect::<Vec<$el>>();
+                        let wide = input.iter().map(|&x| x as f64).collect::<Vec<f64>>();
+                        let mean = wide.iter().sum::<f64>() / len as f64;
+                        let variance = wide.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / len as f64;
+                        let summary = stats(&input);
+                        assert_eq!(summary.count, len);
+                        assert_eq!(summary.nans, 0);
+                        assert!(close(summary.mean as f64, mean, $tolerance), "{} {} {}", len, summary.mean, mean);
+                        assert!(close(summary.variance as f64, variance, $tolerance), "{} {} {}", len, summary.variance, variance);
+
+                        let min = input.iter().cloned().fold($el::INFINITY, $el::min);
+                        let max = input.iter().cloned().fold($el::NEG_INFINITY, $el::max);
+                        assert_eq!(summary.min, min);
+                        assert_eq!(summary.max, max);
+                        assert_eq!(summary.min_index, input.iter().position(|&x| x == min));
+                        assert_eq!(summary.max_index, input.iter().position(|&x| x == max));
+                    }
+                }
+            )*
+        }
+    }
+
+    test_stats!(f32, 1e-4, stats_f32; f64, 1e-10, stats_f64);
+
+    #[test]
+    fn ties_go_to_first() {
+        let mut input = vec![1.0f32; 100];
+        input[37] = -2.0;
+        input[38] = -2.0;
+        input[90] = -2.0;
+        input[5] = 6.0;
+        input[64] = 6.0;
+        let summary = stats(&input);
+        assert_eq!(summary.min_index, Some(37));
+        assert_eq!(summary.max_index, Some(5));
+
+        let summary = stats(&[3.0f64; 50]);
+        assert_eq!((summary.min_index, summary.max_index), (Some(0), Some(0)));
+        assert_eq!(summary.variance, 0.0);
+    }
+
+    #[test]
+    fn nans() {
+        let nan = ::std::f32::NAN;
+        let mut input = data(100).iter().map(|&x| x as f32).collect::<Vec<f32>>();
+        input[3] = nan;
+        input[40] = nan;
+        input[99] = nan;
+
+        let summary = stats(&input);
+        assert_eq!((summary.count, summary.nans), (100, 3));
+        assert!(summary.mean.is_nan() && summary.variance.is_nan());
+        assert!(summary.min.is_nan() && summary.max.is_nan());
+        assert_eq!((summary.min_index, summary.max_index), (Some(3), Some(3)));
+
+        let summary = stats_ignoring_nans(&input);
+        let clean = input.iter().cloned().filter(|x| !x.is_nan()).collect::<Vec<f32>>();
+        let expected = stats(&clean);
+        assert_eq!((summary.count, summary.nans), (97, 3));
+        assert!(close(summary.mean as f64, expected.mean as f64, 1e-5));
+        assert!(close(summary.variance as f64, expected.variance as f64, 1e-5));
+        assert_eq!(summary.min, expected.min);
+        assert_eq!(summary.max, expected.max);
+        assert_eq!(summary.min_index, input.iter().position(|&x| x == expected.min));
+        assert_eq!(summary.max_index, input.iter().position(|&x| x == expected.max));
+
+        let summary = stats_ignoring_nans(&[nan; 20]);
+        assert_eq!((summary.count, summary.nans), (0, 20));
+        assert!(summary.mean.is_nan());
+        assert_eq!((summary.min_index, summary.max_index), (None, None));
+    }
+
+    #[test]
+    fn empty() {
+        let summary = stats::<f64>(&[]);
+        assert_eq!((summary.count, summary.nans), (0, 0));
+        assert!(summary.mean.is_nan() && summary.variance.is_nan());
+        assert_eq!((summary.min_index, summary.max_index), (None, None));
+        assert!(summary.sample_variance().is_nan());
+    }
+
+    #[test]
+    fn sample_variance() {
+        let summary = stats(&[2.0f64, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert!(close(summary.std_dev(), 2.0, 1e-12));
+        assert!(close(summary.sample_variance(), 32.0 / 7.0, 1e-12));
+    }
+}
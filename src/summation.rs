@@ -0,0 +1,382 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Accurate sums of floats.
+//!
+//! Adding a long series of floats to a single accumulator rounds away more
+//! of each addend as the accumulator grows, so the error of the sum grows
+//! with the length of the series. These sums keep their error small
+//! regardless of the length:
+//!
+//! - [`sum_kahan`] carries the rounding error of each addition into the
+//!   next, which makes the error independent of the length, unless the
+//!   series cancels itself out.
+//! - [`sum_neumaier`] also carries the rounding error, but computes it
+//!   exactly even when an addend is larger than the accumulator, so it
+//!   stays accurate when large elements cancel each other out.
+//! - [`sum_pairwise`] adds blocks of the series in a balanced tree, so the
+//!   error grows with the logarithm of the length. It is the fastest of
+//!   the three, and the least accurate.
+//!
+//! Each lane of a vector is summed separately, and the lanes are merged at
+//! the end with the same compensation. Each function is also available on
+//! iterators through [`CompensatedSum`].
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//! use faster::summation::*;
+//!
+//! # fn main() {
+//! let data = vec![0.1f32; 1_000_000];
+//! assert!((sum_kahan(&data) - 100_000.0).abs() < 0.01);
+//! assert!((data.simd_iter(f32s(0.0)).sum_neumaier() - 100_000.0).abs() < 0.01);
+//! # }
+//! ```
+//!
+//! [`sum_kahan`]: fn.sum_kahan.html
+//! [`sum_neumaier`]: fn.sum_neumaier.html
+//! [`sum_pairwise`]: fn.sum_pairwise.html
+//! [`CompensatedSum`]: trait.CompensatedSum.html
+
+use crate::arch::current::vecs::{f32s, f64s};
+use crate::into_iters::IntoSIMDRefIterator;
+use crate::intrin::merge::Merge;
+use crate::intrin::sum::Sum;
+use crate::iters::SIMDIterator;
+use crate::vecs::{Packable, Packed};
+
+/// The number of vectors added to a single accumulator before it becomes a
+/// leaf of the tree of a pairwise sum.
+const PAIRWISE_BLOCK: usize = 16;
+
+/// A float which can be summed accurately.
+pub trait Summable : Packable {
+    #[doc(hidden)]
+    fn sum_kahan<I>(iter: &mut I) -> Self
+        where I : SIMDIterator<Scalar = Self, Vector = <Self as Packable>::Vector>;
+
+    #[doc(hidden)]
+    fn sum_neumaier<I>(iter: &mut I) -> Self
+        where I : SIMDIterator<Scalar = Self, Vector = <Self as Packable>::Vector>;
+
+    #[doc(hidden)]
+    fn sum_pairwise<I>(iter: &mut I) -> Self
+        where I : SIMDIterator<Scalar = Self, Vector = <Self as Packable>::Vector>;
+}
+
+/// An iterator of float vectors which can be summed accurately.
+pub trait CompensatedSum : SIMDIterator {
+    /// Return the sum of the remaining elements of this iterator, using
+    /// Kahan summation in each lane.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    /// use faster::summation::CompensatedSum;
+    ///
+    /// # fn main() {
+    /// assert_eq!([0.1f64; 1000].simd_iter(f64s(0.0)).sum_kahan(), 100.0);
+    /// # }
+    /// ```
+    fn sum_kahan(&mut self) -> Self::Scalar;
+
+    /// Return the sum of the remaining elements of this iterator, using
+    /// Neumaier summation in each lane.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    /// use faster::summation::CompensatedSum;
+    ///
+    /// # fn main() {
+    /// assert_eq!([0.1f64; 1000].simd_iter(f64s(0.0)).sum_neumaier(), 100.0);
+    /// # }
+    /// ```
+    fn sum_neumaier(&mut self) -> Self::Scalar;
+
+    /// Return the sum of the remaining elements of this iterator, using
+    /// pairwise summation in each lane.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    /// use faster::summation::CompensatedSum;
+    ///
+    /// # fn main() {
+    /// assert_eq!([0.5f32; 1000].simd_iter(f32s(0.0)).sum_pairwise(), 500.0);
+    /// # }
+    /// ```
+    fn sum_pairwise(&mut self) -> Self::Scalar;
+}
+
+impl<T, S> CompensatedSum for T
+    where T : SIMDIterator<Scalar = S, Vector = <S as Packable>::Vector>, S : Summable {
+    #[inline]
+    fn sum_kahan(&mut self) -> S {
+        S::sum_kahan(self)
+    }
+
+    #[inline]
+    fn sum_neumaier(&mut self) -> S {
+        S::sum_neumaier(self)
+    }
+
+    #[inline]
+    fn sum_pairwise(&mut self) -> S {
+        S::sum_pairwise(self)
+    }
+}
+
+/// Return the sum of `data`, using Kahan summation.
+///
+/// ```
+/// extern crate faster;
+/// use faster::summation::sum_kahan;
+///
+/// # fn main() {
+/// assert!((sum_kahan(&[0.1f32; 1_000_000][..]) - 100_000.0).abs() < 0.01);
+/// # }
+/// ```
+#[inline]
+pub fn sum_kahan<T : Summable>(data: &[T]) -> T {
+    T::sum_kahan(&mut data.simd_iter(<T as Packable>::Vector::default()))
+}
+
+/// Return the sum of `data`, using Neumaier summation. Unlike Kahan
+/// summation, this keeps the low-order bits of a running sum when an addend
+/// is larger in magnitude than it.
+///
+/// ```
+/// extern crate faster;
+/// use faster::summation::sum_neumaier;
+///
+/// # fn main() {
+/// assert_eq!(sum_neumaier(&[1e8f32, 1.0, 1.0, 1.0, 1.0, -1e8][..]), 4.0);
+/// # }
+/// ```
+#[inline]
+pub fn sum_neumaier<T : Summable>(data: &[T]) -> T {
+    T::sum_neumaier(&mut data.simd_iter(<T as Packable>::Vector::default()))
+}
+
+/// Return the sum of `data`, using pairwise summation.
+///
+/// ```
+/// extern crate faster;
+/// use faster::summation::sum_pairwise;
+///
+/// # fn main() {
+/// assert_eq!(sum_pairwise(&[0.25f64; 4096][..]), 1024.0);
+/// # }
+/// ```
+#[inline]
+pub fn sum_pairwise<T : Summable>(data: &[T]) -> T {
+    T::sum_pairwise(&mut data.simd_iter(<T as Packable>::Vector::default()))
+}
+
+macro_rules! impl_summable {
+    ($($el:tt, $vec:tt);*) => {
+        $(
+            impl Summable for $el {
+                fn sum_kahan<I>(iter: &mut I) -> Self
+                    where I : SIMDIterator<Scalar = Self, Vector = $vec> {
+                    let zero = $vec::splat(0.0);
+                    let (mut sum, mut comp) = (zero, zero);
+                    let mut add = |v: $vec| {
+                        let y = v - comp;
+                        let t = sum + y;
+                        comp = (t - sum) - y;
+                        sum = t;
+                    };
+                    for v in &mut *iter {
+                        add(v);
+                    }
+                    if let Some((v, n)) = iter.end() {
+                        // The unfilled elements hold the default vector
+                        add(zero.merge_partitioned(v, n));
+                    }
+                    // Kahan's compensation is the negated error
+                    merge_lanes!($el, sum, zero - comp)
+                }
+
+                fn sum_neumaier<I>(iter: &mut I) -> Self
+                    where I : SIMDIterator<Scalar = Self, Vector = $vec> {
+                    let zero = $vec::splat(0.0);
+                    let (mut sum, mut comp) = (zero, zero);
+                    // The exact error of each addition, whichever addend is
+                    // larger. This is Neumaier's correction without the
+                    // comparison to pick the larger addend.
+                    let mut add = |v: $vec| {
+                        let t = sum + v;
+                        let bp = t - sum;
+                        comp = comp + ((sum - (t - bp)) + (v - bp));
+                        sum = t;
+                    };
+                    for v in &mut *iter {
+                        add(v);
+                    }
+                    if let Some((v, n)) = iter.end() {
+                        add(zero.merge_partitioned(v, n));
+                    }
+                    merge_lanes!($el, sum, comp)
+                }
+
+                fn sum_pairwise<I>(iter: &mut I) -> Self
+                    where I : SIMDIterator<Scalar = Self, Vector = $vec> {
+                    let zero = $vec::splat(0.0);
+                    // The sums of 2^n blocks, for each set bit n of `count`
+                    let mut levels = [zero; 64];
+                    let mut count = 0u64;
+                    let mut block = zero;
+                    let mut in_block = 0;
+                    for v in &mut *iter {
+                        block = block + v;
+                        in_block += 1;
+                        if in_block == PAIRWISE_BLOCK {
+                            let mut level = 0;
+                            while count & (1 << level) != 0 {
+                                block = levels[level] + block;
+                                level += 1;
+                            }
+                            levels[level] = block;
+                            count += 1;
+                            block = zero;
+                            in_block = 0;
+                        }
+                    }
+                    if let Some((v, n)) = iter.end() {
+                        block = block + zero.merge_partitioned(v, n);
+                    }
+                    // Add the smallest sums together first
+                    for (level, partial) in levels.iter().enumerate() {
+                        if count & (1 << level) != 0 {
+                            block = *partial + block;
+                        }
+                    }
+                    block.sum()
+                }
+            }
+        )*
+    }
+}
+
+/// Return the sum of the elements of `$sums` and `$comps`, carrying the
+/// exact error of each addition.
+macro_rules! merge_lanes {
+    ($el:tt, $sums:expr, $comps:expr) => {{
+        let (sums, comps) = ($sums, $comps);
+        let (mut sum, mut comp) = (0 as $el, 0 as $el);
+        for i in 0..sums.width() {
+            for &x in [sums.extract(i), comps.extract(i)].iter() {
+                let t = sum + x;
+                let bp = t - sum;
+                comp += (sum - (t - bp)) + (x - bp);
+                sum = t;
+            }
+        }
+        sum + comp
+    }}
+}
+
+impl_summable!(f32, f32s; f64, f64s);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::testing::data;
+
+    /// Return the sum of `data` to within an ulp. The running sum is kept
+    /// exactly, as a list of partial sums which don't overlap, and rounded
+    /// only at the end, as in Shewchuk's algorithm.
+    fn exact(data: &[f64]) -> f64 {
+        let mut partials: Vec<f64> = Vec::new();
+        for &x in data {
+            let mut x = x;
+            let mut kept = 0;
+            for i in 0..partials.len() {
+                let y = partials[i];
+                let (hi, lo) = if x.abs() < y.abs() { (y, x) } else { (x, y) };
+                let sum = hi + lo;
+                let err = lo - (sum - hi);
+                if err != 0.0 {
+                    partials[kept] = err;
+                    kept += 1;
+                }
+                x = sum;
+            }
+            partials.truncate(kept);
+            partials.push(x);
+        }
+        // The partials increase in magnitude
+        partials.iter().rev().fold(0.0, |sum, &x| sum + x)
+    }
+
+    const LENGTHS: &[usize] = &[0, 1, 3, 15, 16, 17, 1000, 65537, 1_000_003];
+
+    macro_rules! test_summation {
+        ($($el:tt, $fn:ident);*) => {
+            $(
+                #[test]
+                fn $fn() {
+                    let eps = $el::EPSILON as f64;
+                    for &len in LENGTHS {
+                        let random = data(len);
+                        let inputs: Vec<Vec<$el>> = vec![
+                            random.iter().map(|&x| 0.1 + (x % 1000) as $el / 7.0).collect(),
+                            // Many magnitudes, cancelling each other out
+                            random.iter().map(|&x| {
+                                ((x >> 8) as u32 as $el / u32::MAX as $el - 0.5) * (10.0 as $el).powi((x % 9) as i32)
+                            }).collect(),
+                        ];
+                        for input in inputs {
+                            let wide = input.iter().map(|&x| x as f64).collect::<Vec<f64>>();
+                            let expected = exact(&wide);
+                            let magnitude = exact(&wide.iter().map(|x| x.abs()).collect::<Vec<f64>>());
+                            let compensated = 4.0 * eps * magnitude + eps * expected.abs();
+                            let depth = PAIRWISE_BLOCK as f64 + (len as f64).log2().max(0.0) + <$el as Packable>::Vector::WIDTH as f64;
+                            let pairwise = (depth + 2.0) * eps * magnitude;
+
+                            let kahan = sum_kahan(&input) as f64;
+                            assert!((kahan - expected).abs() <= compensated, "{} {} {}", len, kahan, expected);
+                            let neumaier = sum_neumaier(&input) as f64;
+                            assert!((neumaier - expected).abs() <= compensated, "{} {} {}", len, neumaier, expected);
+                            let pair = sum_pairwise(&input) as f64;
+                            assert!((pair - expected).abs() <= pairwise, "{} {} {}", len, pair, expected);
+
+                            let zero = <$el as Packable>::Vector::splat(0 as $el);
+                            assert_eq!(input.simd_iter(zero).sum_kahan() as f64, kahan);
+                            assert_eq!(input.simd_iter(zero).sum_neumaier() as f64, neumaier);
+                            assert_eq!(input.simd_iter(zero).sum_pairwise() as f64, pair);
+                        }
+                    }
+                }
+            )*
+        }
+    }
+
+    test_summation!(f32, summation_f32; f64, summation_f64);
+
+    #[test]
+    fn neumaier_cancellation() {
+        // Every lane sees a large element cancel the next one out
+        let width = f64s::WIDTH;
+        let pattern = [1.0, 1e100, 1.0, -1e100];
+        let input = (0..width * 4).map(|i| pattern[i / width]).collect::<Vec<f64>>();
+        assert_eq!(sum_neumaier(&input), 2.0 * width as f64);
+    }
+
+    #[test]
+    fn ignores_default() {
+        // The unfilled elements of the last vector mustn't count
+        let input = [1.0f32; 5];
+        assert_eq!(input.simd_iter(f32s(100.0)).sum_kahan(), 5.0);
+        assert_eq!(input.simd_iter(f32s(100.0)).sum_neumaier(), 5.0);
+        assert_eq!(input.simd_iter(f32s(100.0)).sum_pairwise(), 5.0);
+    }
+}
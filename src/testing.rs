@@ -0,0 +1,39 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Fixtures shared by the unit tests.
+
+#![allow(dead_code)]
+
+/// An xorshift generator. Its seed is fixed, so a failing test reproduces on
+/// every run.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new() -> Self {
+        Rng(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Return a generator whose sequence is distinct for each `seed`.
+    pub(crate) fn with_seed(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Return the first `len` values of a new `Rng`.
+#[cfg(feature = "std")]
+pub(crate) fn data(len: usize) -> Vec<u64> {
+    let mut rng = Rng::new();
+    (0..len).map(|_| rng.next()).collect()
+}
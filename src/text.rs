@@ -0,0 +1,384 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Vectorized UTF-8 validation and ASCII scanning over byte slices.
+//!
+//! Validation classifies each byte by the high and low nibbles of the byte
+//! before it and the high nibble of itself, using [`Lookup`] tables, as
+//! described by John Keiser and Daniel Lemire in "Validating UTF-8 In Less
+//! Than One Instruction Per Byte". Invalid input is rescanned with a scalar
+//! validator, so errors are reported exactly as by `core::str::from_utf8`.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::text::*;
+//!
+//! # fn main() {
+//! assert!(validate_utf8("Grüße, Jürgen ❤".as_bytes()).is_ok());
+//! assert_eq!(validate_utf8(b"abc\xFFdef").unwrap_err().valid_up_to(), 3);
+//! assert_eq!(count_utf8_chars("Grüße, Jürgen ❤".as_bytes()), 15);
+//! assert!(is_ascii(b"Hello, world!"));
+//! # }
+//! ```
+//!
+//! [`Lookup`]: ../intrin/lookup/trait.Lookup.html
+
+use crate::iters::{SIMDIterator, SIMDIterable, SIMDArray};
+use crate::into_iters::IntoSIMDRefIterator;
+use crate::vecs::Packed;
+use crate::intrin::eq::Eq as PackedEq;
+use crate::intrin::lookup::Lookup;
+use crate::intrin::popcnt::Popcnt;
+use crate::intrin::saturating_sub::SaturatingSub;
+use crate::arch::current::vecs::u8s;
+use crate::core::fmt;
+
+/// An error returned when a byte slice is not valid UTF-8. This carries the
+/// same information as `core::str::Utf8Error`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Utf8Error {
+    valid_up_to: usize,
+    error_len: Option<u8>,
+}
+
+impl Utf8Error {
+    /// Return the index of the first byte which is not part of a valid UTF-8
+    /// sequence. The input up to this index is valid UTF-8.
+    #[inline(always)]
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Return the length of the invalid sequence at `valid_up_to`, or `None`
+    /// if the input ended in the middle of a sequence.
+    #[inline(always)]
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len.map(|len| len as usize)
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.error_len {
+            Some(len) => write!(f, "invalid utf-8 sequence of {} bytes from index {}",
+                                len, self.valid_up_to),
+            None => write!(f, "incomplete utf-8 byte sequence from index {}",
+                           self.valid_up_to),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Utf8Error { }
+
+// Error classes of a pair of bytes. Each table maps a nibble of the pair to
+// the classes which it may belong to, so a pair is erroneous if all three of
+// its lookups share a class.
+const TOO_SHORT: u8 = 1 << 0; // 11______ 0_______ or 11______ 11______
+const TOO_LONG: u8 = 1 << 1; // 0_______ 10______
+const OVERLONG_3: u8 = 1 << 2; // 11100000 100_____
+const TOO_LARGE: u8 = 1 << 3; // 11110100 1001____ or 11110101 and above
+const SURROGATE: u8 = 1 << 4; // 11101101 101_____
+const OVERLONG_2: u8 = 1 << 5; // 1100000_ 10______
+const TOO_LARGE_1000: u8 = 1 << 6; // 11110101 1000____ and above
+const OVERLONG_4: u8 = 1 << 6; // 11110000 1000____
+const TWO_CONTS: u8 = 1 << 7; // 10______ 10______
+const CARRY: u8 = TOO_SHORT | TOO_LONG | TWO_CONTS;
+
+// Indexed by the high nibble of the first byte of a pair
+const BYTE_1_HIGH: [u8; 16] = [
+    TOO_LONG, TOO_LONG, TOO_LONG, TOO_LONG,
+    TOO_LONG, TOO_LONG, TOO_LONG, TOO_LONG,
+    TWO_CONTS, TWO_CONTS, TWO_CONTS, TWO_CONTS,
+    TOO_SHORT | OVERLONG_2,
+    TOO_SHORT,
+    TOO_SHORT | OVERLONG_3 | SURROGATE,
+    TOO_SHORT | TOO_LARGE | TOO_LARGE_1000 | OVERLONG_4,
+];
+
+// Indexed by the low nibble of the first byte of a pair
+const BYTE_1_LOW: [u8; 16] = [
+    CARRY | OVERLONG_3 | OVERLONG_2 | OVERLONG_4,
+    CARRY | OVERLONG_2,
+    CARRY,
+    CARRY,
+    CARRY | TOO_LARGE,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000 | SURROGATE,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+];
+
+// Indexed by the high nibble of the second byte of a pair
+const BYTE_2_HIGH: [u8; 16] = [
+    TOO_SHORT, TOO_SHORT, TOO_SHORT, TOO_SHORT,
+    TOO_SHORT, TOO_SHORT, TOO_SHORT, TOO_SHORT,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE_1000 | OVERLONG_4,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    TOO_SHORT, TOO_SHORT, TOO_SHORT, TOO_SHORT,
+];
+
+/// Return a vector containing `table` in every group of 16 elements.
+#[inline(always)]
+fn splat_table(table: &[u8; 16]) -> u8s {
+    (0..u8s::WIDTH).fold(u8s(0), |v, i| v.replace(i, table[i % 16]))
+}
+
+/// Return the vector of `data` beginning `back` bytes before `offset`, where
+/// bytes before the start of `data` are zero.
+#[inline(always)]
+fn load_behind<A : SIMDArray<Vector = u8s, Scalar = u8>>(data: &A, offset: usize, back: usize) -> u8s {
+    if offset >= back {
+        unsafe { data.load_unchecked(offset - back) }
+    } else {
+        (back - offset..u8s::WIDTH).fold(u8s(0), |v, i| {
+            v.replace(i, data.load_scalar(i + offset - back))
+        })
+    }
+}
+
+/// Return the width of the UTF-8 sequence starting with `lead`, or zero if
+/// `lead` can't start a sequence.
+#[inline(always)]
+fn utf8_char_width(lead: u8) -> usize {
+    match lead {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => 0,
+    }
+}
+
+/// Return the first index at or before `offset` at which a scalar validator
+/// may resume, given that every sequence starting before `offset - 3` is
+/// valid.
+#[inline(always)]
+fn resume_point(data: &[u8], offset: usize) -> usize {
+    for i in (offset.saturating_sub(3)..offset).rev() {
+        if data[i] >= 0xC0 {
+            let width = utf8_char_width(data[i]);
+            return if width == 0 || i + width > offset { i } else { offset };
+        } else if data[i] < 0x80 {
+            return offset;
+        }
+    }
+    offset
+}
+
+/// Validate `data` one sequence at a time, starting from `offset`.
+#[inline(always)]
+fn validate_utf8_scalar(data: &[u8], offset: usize) -> Result<(), Utf8Error> {
+    let mut i = offset;
+    while i < data.len() {
+        let start = i;
+        let lead = data[i];
+        if lead >= 0x80 {
+            let err = |error_len| Err(Utf8Error { valid_up_to: start, error_len: error_len });
+            let width = utf8_char_width(lead);
+            if width == 0 {
+                return err(Some(1));
+            }
+            for n in 1..width {
+                i += 1;
+                let byte = match data.get(i) {
+                    Some(&byte) => byte,
+                    None => return err(None),
+                };
+                let valid = match (n, lead) {
+                    (1, 0xE0) => (0xA0..=0xBF).contains(&byte),
+                    (1, 0xED) => (0x80..=0x9F).contains(&byte),
+                    (1, 0xF0) => (0x90..=0xBF).contains(&byte),
+                    (1, 0xF4) => (0x80..=0x8F).contains(&byte),
+                    _ => byte & 0xC0 == 0x80,
+                };
+                if !valid {
+                    return err(Some(n as u8));
+                }
+            }
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Return `Ok(())` if `data` is valid UTF-8, or the position of the first
+/// invalid sequence otherwise.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::*;
+///
+/// # fn main() {
+/// let err = validate_utf8(b"caf\xC3").unwrap_err();
+/// assert_eq!(err.valid_up_to(), 3);
+/// assert_eq!(err.error_len(), None);
+/// # }
+/// ```
+pub fn validate_utf8(data: &[u8]) -> Result<(), Utf8Error> {
+    let byte_1_high = splat_table(&BYTE_1_HIGH);
+    let byte_1_low = splat_table(&BYTE_1_LOW);
+    let byte_2_high = splat_table(&BYTE_2_HIGH);
+    // Only bytes which begin a sequence of three or four bytes will have
+    // their high bit set after these subtractions
+    let third_lead = u8s(0xE0 - 0x80);
+    let fourth_lead = u8s(0xF0 - 0x80);
+
+    let mut iter = data.simd_iter(u8s(0));
+    let mut offset = iter.scalar_pos();
+    while let Some(input) = iter.next() {
+        let prev1 = load_behind(&iter, offset, 1);
+        let special = (prev1 >> u8s(4)).lookup(byte_1_high)
+            & (prev1 & u8s(0x0F)).lookup(byte_1_low)
+            & (input >> u8s(4)).lookup(byte_2_high);
+        let must_continue = load_behind(&iter, offset, 2).saturating_sub(third_lead)
+            | load_behind(&iter, offset, 3).saturating_sub(fourth_lead);
+        let error = (must_continue & u8s(0x80)) ^ special;
+        if error != u8s(0) {
+            return validate_utf8_scalar(data, resume_point(data, offset));
+        }
+        offset = iter.scalar_pos();
+    }
+
+    // Validate the remainder, including any sequence left incomplete by the
+    // last vector
+    validate_utf8_scalar(data, resume_point(data, offset))
+}
+
+/// Return whether every byte of `data` is ASCII.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::*;
+///
+/// # fn main() {
+/// assert!(is_ascii(b"plain text"));
+/// assert!(!is_ascii("naïve".as_bytes()));
+/// # }
+/// ```
+pub fn is_ascii(data: &[u8]) -> bool {
+    let high = data.simd_iter(u8s(0))
+        .simd_reduce(u8s(0), |acc, v| acc | v) & u8s(0x80);
+    high == u8s(0)
+}
+
+/// Return the number of characters in `data`, which is assumed to be valid
+/// UTF-8. For invalid input, this is the number of bytes which are not UTF-8
+/// continuation bytes.
+///
+/// ```
+/// extern crate faster;
+/// use faster::text::*;
+///
+/// # fn main() {
+/// let text = "¿Dónde está?";
+/// assert_eq!(count_utf8_chars(text.as_bytes()), text.chars().count());
+/// # }
+/// ```
+pub fn count_utf8_chars(data: &[u8]) -> usize {
+    let continuations = data.simd_iter(u8s(0))
+        .simd_reduce(0, |acc, v| {
+            acc + (v & u8s(0xC0)).eq_mask(u8s(0x80)).count_ones() / 8
+        });
+    data.len() - continuations
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn check(data: &[u8]) {
+        let expected = crate::core::str::from_utf8(data);
+        let actual = validate_utf8(data);
+        match (expected, actual) {
+            (Ok(s), Ok(())) => {
+                assert_eq!(count_utf8_chars(data), s.chars().count());
+            },
+            (Err(e), Err(a)) => {
+                assert_eq!((a.valid_up_to(), a.error_len()), (e.valid_up_to(), e.error_len()),
+                           "{:?}", data);
+            },
+            (e, a) => panic!("{:?}: expected {:?}, got {:?}", data, e, a),
+        }
+        assert_eq!(is_ascii(data), data.is_ascii());
+    }
+
+    #[test]
+    fn utf8_valid() {
+        let text = "Pchnąć w tę łódź jeża lub ośm skrzyń fig. ÄÖÜ ẞ 日本語 🦀 ";
+        for len in 0..200 {
+            let s: String = text.chars().cycle().take(len).collect();
+            check(s.as_bytes());
+        }
+        check(&[0; 1000]);
+        check("\u{7F}\u{80}\u{7FF}\u{800}\u{FFFF}\u{10000}\u{10FFFF}".as_bytes());
+    }
+
+    #[test]
+    fn utf8_invalid_at_every_position() {
+        // Overlong, surrogate, out of range, stray and truncated sequences
+        let bad: &[&[u8]] = &[
+            b"\x80", b"\xBF", b"\xC0\x80", b"\xC1\xBF", b"\xC2", b"\xC2\x41",
+            b"\xE0\x80\x80", b"\xE0\x9F\xBF", b"\xE0\xA0", b"\xED\xA0\x80",
+            b"\xED\xBF\xBF", b"\xEF\xBF", b"\xF0\x80\x80\x80", b"\xF0\x8F\xBF\xBF",
+            b"\xF0\x90\x80", b"\xF4\x90\x80\x80", b"\xF5\x80\x80\x80", b"\xFF",
+            b"\xE1\x80\xC0", b"\xF1\x80\x80\xC0", b"\xC2\x80\x80", b"\xF4\x8F\xBF\xBF\x80",
+        ];
+        for seq in bad {
+            for prefix in 0..70 {
+                for suffix in &[0, 1, 3, 40] {
+                    let mut data = vec![];
+                    data.extend("é".repeat(prefix / 2).bytes());
+                    data.extend(b"a".iter().cycle().take(prefix % 2));
+                    data.extend(*seq);
+                    data.extend(b"z".iter().cycle().take(*suffix));
+                    check(&data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_exhaustive_pairs_and_triples() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                check(&[a, b]);
+                check(&[b'x', a, b, b'y', b'z']);
+            }
+        }
+        for &a in &[0xE0u8, 0xE1, 0xED, 0xEE, 0xF0, 0xF1, 0xF4, 0xF5] {
+            for b in 0x70..=0xC8u8 {
+                for c in 0x70..=0xC8u8 {
+                    check(&[a, b, c, 0x80]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ascii_and_counting() {
+        for len in 0..100 {
+            let mut data = vec![b'q'; len];
+            check(&data);
+            if len > 0 {
+                data[len - 1] = 0x80;
+                assert!(!is_ascii(&data));
+                data[len / 2] = 0xC3;
+                assert_eq!(count_utf8_chars(&data), len - 1 + (len / 2 == len - 1) as usize);
+            }
+        }
+    }
+}
@@ -0,0 +1,387 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Telemetry on which operations run on SIMD hardware and which are emulated
+//! in software. This module is only compiled with the `trace` feature.
+//!
+//! Every call site of a hardware or software implementation counts how often
+//! it runs, and [`report`] returns a snapshot of those counters. The first
+//! time a call site runs, it also sends its [`Record`] to the [`Sink`] set
+//! by [`set_sink`]. Until a sink is set, records are printed to stdout, or
+//! appended to the file named by the `FASTER_DEBUG_FILE` environment
+//! variable if it is set at run time or at compile time.
+//!
+//! ```
+//! extern crate faster;
+//! use faster::*;
+//!
+//! # fn main() {
+//! trace::set_sink(trace::Sink::Silent);
+//! let _ = (&[1u8; 64][..]).simd_iter(u8s(0)).simd_map(|v| v.hadd(v)).scalar_collect();
+//! let emulated = trace::report().into_iter()
+//!     .filter(|r| r.backend == trace::Backend::Software)
+//!     .map(|r| r.count)
+//!     .sum::<usize>();
+//! println!("{} calls were emulated in software", emulated);
+//! # }
+//! ```
+//!
+//! [`report`]: fn.report.html
+//! [`Record`]: struct.Record.html
+//! [`Sink`]: enum.Sink.html
+//! [`set_sink`]: fn.set_sink.html
+
+use std::any::type_name;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Whether a call site uses SIMD instructions or emulates them in software.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Backend {
+    Hardware,
+    Software,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Backend::Hardware => write!(f, "hardware"),
+            Backend::Software => write!(f, "software"),
+        }
+    }
+}
+
+/// A snapshot of the counter of a call site.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// The trait implemented at the call site, such as `Abs` or
+    /// `Upcast<i16x8>`. Call sites outside of a trait implementation are
+    /// named after their module, such as `crc`.
+    pub trait_name: String,
+    /// The vector type the trait is implemented for, such as `f32x4`, or an
+    /// empty string outside of a trait implementation.
+    pub vector: String,
+    /// The function containing the call site.
+    pub function: String,
+    pub backend: Backend,
+    pub file: &'static str,
+    pub line: u32,
+    /// The number of times the call site has run since it was last reset.
+    pub count: usize,
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let what = match self.backend {
+            Backend::Hardware => "🚄 faster is using HARDWARE acceleration",
+            Backend::Software => "⛔ faster is using SOFTWARE emulation",
+        };
+        if self.vector.is_empty() {
+            write!(f, "{} in {}::{} ({}:{}).", what, self.trait_name, self.function, self.file, self.line)
+        } else {
+            write!(f, "{} in {}::{} for {} ({}:{}).", what, self.trait_name, self.function, self.vector,
+                   self.file, self.line)
+        }
+    }
+}
+
+/// A destination for the record of each call site, which is sent the first
+/// time the call site runs.
+#[derive(Clone)]
+pub enum Sink {
+    /// Discard the records. The counters are still kept for `report`.
+    Silent,
+    Stdout,
+    Stderr,
+    /// Append the records to the file at the given path.
+    File(PathBuf),
+    /// Pass the records to the given function. The function must not set the
+    /// sink.
+    Callback(fn(&Record)),
+    /// Log the records to the `log` crate with the target `faster::trace`,
+    /// at the warn level for software emulation and at the debug level for
+    /// hardware acceleration.
+    #[cfg(feature = "trace-log")]
+    Log,
+    /// Emit the records as `tracing` events with the target `faster::trace`,
+    /// at the same levels as `Sink::Log`.
+    #[cfg(feature = "trace-tracing")]
+    Tracing,
+}
+
+impl fmt::Debug for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Sink::Silent => write!(f, "Silent"),
+            Sink::Stdout => write!(f, "Stdout"),
+            Sink::Stderr => write!(f, "Stderr"),
+            Sink::File(ref path) => f.debug_tuple("File").field(path).finish(),
+            Sink::Callback(callback) => f.debug_tuple("Callback").field(&(callback as *const ())).finish(),
+            #[cfg(feature = "trace-log")]
+            Sink::Log => write!(f, "Log"),
+            #[cfg(feature = "trace-tracing")]
+            Sink::Tracing => write!(f, "Tracing"),
+        }
+    }
+}
+
+/// A call site of `fallback!` or `optimized!`.
+#[doc(hidden)]
+pub struct Site {
+    backend: Backend,
+    file: &'static str,
+    line: u32,
+    count: AtomicUsize,
+    registered: AtomicBool,
+}
+
+impl Site {
+    pub const fn new(backend: Backend, file: &'static str, line: u32) -> Self {
+        Site {
+            backend,
+            file,
+            line,
+            count: AtomicUsize::new(0),
+            registered: AtomicBool::new(false),
+        }
+    }
+}
+
+struct Entry {
+    site: &'static Site,
+    trait_name: String,
+    vector: String,
+    function: String,
+}
+
+impl Entry {
+    fn record(&self) -> Record {
+        Record {
+            trait_name: self.trait_name.clone(),
+            vector: self.vector.clone(),
+            function: self.function.clone(),
+            backend: self.site.backend,
+            file: self.site.file,
+            line: self.site.line,
+            count: self.site.count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static SITES: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+// A panic while holding a lock leaves nothing half-written, so ignore poison.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Return a snapshot of the counter of every call site which has run, ordered
+/// by file and line.
+pub fn report() -> Vec<Record> {
+    let mut records = lock(&SITES).iter().map(Entry::record).collect::<Vec<_>>();
+    records.sort_by(|a, b| (a.file, a.line).cmp(&(b.file, b.line)));
+    records
+}
+
+/// Set the counter of every call site to zero. Call sites which have run are
+/// still reported, and don't send their records to the sink again.
+pub fn reset() {
+    for entry in lock(&SITES).iter() {
+        entry.site.count.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Send the records of call sites which run for the first time from now on to
+/// `sink`.
+pub fn set_sink(sink: Sink) {
+    *lock(&SINK) = Some(sink);
+}
+
+fn default_sink() -> Sink {
+    std::env::var_os("FASTER_DEBUG_FILE").map(PathBuf::from)
+        .or_else(|| option_env!("FASTER_DEBUG_FILE").map(PathBuf::from))
+        .map_or(Sink::Stdout, Sink::File)
+}
+
+fn send(record: &Record) {
+    // Clone the sink, so a callback which runs a traced operation doesn't
+    // deadlock
+    let sink = lock(&SINK).get_or_insert_with(default_sink).clone();
+    match sink {
+        Sink::Silent => { },
+        Sink::Stdout => println!("{}", record),
+        Sink::Stderr => eprintln!("{}", record),
+        Sink::File(path) => {
+            // Telemetry must not make the traced operation fail
+            OpenOptions::new().create(true).append(true).open(path)
+                .and_then(|mut file| writeln!(file, "{}", record)).ok();
+        },
+        Sink::Callback(callback) => callback(record),
+        #[cfg(feature = "trace-log")]
+        Sink::Log => {
+            let level = match record.backend {
+                Backend::Hardware => log::Level::Debug,
+                Backend::Software => log::Level::Warn,
+            };
+            log::log!(target: "faster::trace", level, "{}", record);
+        },
+        #[cfg(feature = "trace-tracing")]
+        Sink::Tracing => match record.backend {
+            Backend::Hardware => tracing::debug!(target: "faster::trace", trait_name = %record.trait_name,
+                                                 vector = %record.vector, function = %record.function,
+                                                 file = record.file, line = record.line,
+                                                 "faster is using hardware acceleration"),
+            Backend::Software => tracing::warn!(target: "faster::trace", trait_name = %record.trait_name,
+                                                vector = %record.vector, function = %record.function,
+                                                file = record.file, line = record.line,
+                                                "faster is using software emulation"),
+        },
+    }
+}
+
+/// Count a run of `site`, whose enclosing function has the type name `path`.
+#[doc(hidden)]
+#[inline(always)]
+pub fn hit(site: &'static Site, path: &'static str) {
+    site.count.fetch_add(1, Ordering::Relaxed);
+    if !site.registered.swap(true, Ordering::Relaxed) {
+        register(site, path);
+    }
+}
+
+#[cold]
+fn register(site: &'static Site, path: &'static str) {
+    let (trait_name, vector, function) = describe(path);
+    let entry = Entry { site, trait_name, vector, function };
+    let record = entry.record();
+    lock(&SITES).push(entry);
+    send(&record);
+}
+
+#[doc(hidden)]
+pub fn type_name_of<T>(_: T) -> &'static str {
+    type_name::<T>()
+}
+
+/// Split the type name of a function nested in a call site's function, such
+/// as `faster::arch::x86::intrin::abs::<impl faster::intrin::abs::Abs for
+/// packed_simd_2::Simd<[f32; 4]>>::abs::__site`, into its trait, vector and
+/// function. Type names aren't guaranteed to be stable, so this is only
+/// best-effort.
+fn describe(path: &str) -> (String, String, String) {
+    let segments = split_top_level(path, "::");
+    // Drop the nested function
+    let segments = &segments[..segments.len().saturating_sub(1)];
+    let function = segments.last().map_or("", |s| *s).to_string();
+    let parent = segments.len().saturating_sub(2);
+    match segments.get(parent) {
+        Some(segment) if segment.starts_with("<impl ") && segment.ends_with('>') => {
+            let inner = &segment["<impl ".len()..segment.len() - 1];
+            match split_top_level(inner, " for ").as_slice() {
+                [tr, ty] => (shorten(tr), shorten(ty), function),
+                _ => (String::new(), shorten(inner), function),
+            }
+        },
+        Some(module) => (module.to_string(), String::new(), function),
+        None => (String::new(), String::new(), function),
+    }
+}
+
+/// Split `s` at each `sep` which isn't enclosed in brackets.
+fn split_top_level<'a>(s: &'a str, sep: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start, mut i) = (0i32, 0, 0);
+    while i < s.len() {
+        match s.as_bytes()[i] {
+            b'<' | b'[' | b'(' => depth += 1,
+            b'>' | b']' | b')' => depth -= 1,
+            _ if depth == 0 && s.as_bytes()[i..].starts_with(sep.as_bytes()) => {
+                parts.push(&s[start..i]);
+                i += sep.len();
+                start = i;
+                continue;
+            },
+            _ => { },
+        }
+        i += 1;
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Shorten a type name, such as `faster::half::PackedF16<packed_simd_2::Simd<[u16; 8]>>`,
+/// to its name in faster, such as `PackedF16<u16x8>`.
+fn shorten(ty: &str) -> String {
+    // Spell packed_simd's vectors as their aliases
+    let mut ty = ty.to_string();
+    while let Some(start) = ty.find("Simd<[") {
+        let end = match ty[start..].find("]>") {
+            Some(end) => start + end,
+            None => break,
+        };
+        let alias = match split_top_level(&ty[start + "Simd<[".len()..end], "; ").as_slice() {
+            [el, n] => format!("{}x{}", el, n),
+            _ => break,
+        };
+        ty.replace_range(start..end + "]>".len(), &alias);
+    }
+
+    // Drop the paths of the types
+    let mut short = String::new();
+    let mut chars = ty.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            while short.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                short.pop();
+            }
+        } else {
+            short.push(c);
+        }
+    }
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_trait_impl() {
+        assert_eq!(describe("faster::arch::x86::intrin::abs::<impl faster::intrin::abs::Abs for packed_simd_2::Simd<[f32; 4]>>::abs::__site"),
+                   ("Abs".to_string(), "f32x4".to_string(), "abs".to_string()));
+        assert_eq!(describe("faster::arch::x86::intrin::upcast::<impl faster::intrin::upcast::Upcast<packed_simd_2::Simd<[i16; 8]>> for packed_simd_2::Simd<[i8; 16]>>::upcast::__site"),
+                   ("Upcast<i16x8>".to_string(), "i8x16".to_string(), "upcast".to_string()));
+        assert_eq!(describe("faster::arch::unknown::intrin::downcast::<impl faster::intrin::downcast::Downcast<faster::half::PackedF16<faster::arch::unknown::vecs::u16x8>> for faster::arch::unknown::vecs::f32x4>::saturating_downcast::__site"),
+                   ("Downcast<PackedF16<u16x8>>".to_string(), "f32x4".to_string(), "saturating_downcast".to_string()));
+    }
+
+    #[test]
+    fn describe_free_fn() {
+        assert_eq!(describe("faster::checksum::crc::update_crc32::__site"),
+                   ("crc".to_string(), String::new(), "update_crc32".to_string()));
+    }
+
+    #[test]
+    fn counts_hits() {
+        static SITE: Site = Site::new(Backend::Software, file!(), line!());
+        fn __site() { }
+        set_sink(Sink::Silent);
+        for _ in 0..3 {
+            hit(&SITE, type_name_of(__site));
+        }
+        let record = report().into_iter().find(|r| r.line == SITE.line && r.file == file!()).unwrap();
+        assert_eq!(record.backend, Backend::Software);
+        assert_eq!(record.count, 3);
+        assert_eq!(record.function, "counts_hits");
+    }
+}
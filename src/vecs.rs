@@ -8,6 +8,7 @@
 
 pub use crate::vec_patterns::Pattern;
 use crate::core::fmt::Debug;
+use crate::error::{Error, check_bounds};
 use crate::intrin::merge::*;
 
 /// A SIMD vector of some type.
@@ -26,20 +27,108 @@ pub trait Packed : Sized + Copy + Debug + Merge {
 
     /// Create a new vector with `Self::WIDTH` elements from `data`, beginning
     /// at `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` doesn't have `Self::WIDTH` elements after `offset`.
     fn load(data: &[Self::Scalar], offset: usize) -> Self;
 
+    /// Create a new vector with `Self::WIDTH` elements from `data`, beginning
+    /// at `offset`, or return an error if `data` doesn't have `Self::WIDTH`
+    /// elements after `offset`.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let data = [1u8; 3];
+    /// assert!(u8s::try_load(&data, 0).is_err());
+    /// # }
+    /// ```
+    #[inline(always)]
+    fn try_load(data: &[Self::Scalar], offset: usize) -> Result<Self, Error> {
+        check_bounds(data.len(), offset, Self::WIDTH)?;
+        Ok(unsafe { Self::load_unchecked(data, offset) })
+    }
+
     /// Create a new vector with `Self::WIDTH` elements from `data`, beginning
     /// at `offset`, without asserting length of data.
     unsafe fn load_unchecked(data: &[Self::Scalar], offset: usize) -> Self;
 
     /// Write `Self::WIDTH` elements from this vector to `data`, beginning at
     /// `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` doesn't have `Self::WIDTH` elements after `offset`.
     fn store(self, data: &mut [Self::Scalar], offset: usize);
 
+    /// Write `Self::WIDTH` elements from this vector to `data`, beginning at
+    /// `offset`, or return an error if `data` doesn't have `Self::WIDTH`
+    /// elements after `offset`. Nothing is written if an error is returned.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let mut data = [0u8; 3];
+    /// assert!(u8s(1).try_store(&mut data, 0).is_err());
+    /// assert_eq!(data, [0u8; 3]);
+    /// # }
+    /// ```
+    #[inline(always)]
+    fn try_store(self, data: &mut [Self::Scalar], offset: usize) -> Result<(), Error> {
+        check_bounds(data.len(), offset, Self::WIDTH)?;
+        unsafe { self.store_unchecked(data, offset) };
+        Ok(())
+    }
+
     /// Create a new vector with `Self::WIDTH` elements from `data`, beginning
     /// at `offset`, without asserting length of data.
     unsafe fn store_unchecked(self, data: &mut [Self::Scalar], offset: usize);
 
+    /// Create a new vector with `Self::WIDTH` elements from `data`, beginning
+    /// at `offset`, which should be aligned to the alignment of this vector.
+    /// Debug builds assert the alignment, and release builds fall back to an
+    /// unaligned load if it isn't aligned.
+    #[inline(always)]
+    fn load_aligned(data: &[Self::Scalar], offset: usize) -> Self {
+        let data = &data[offset..];
+        assert!(data.len() >= Self::WIDTH);
+        debug_assert!(data.as_ptr() as usize % crate::core::mem::align_of::<Self>() == 0,
+                      "load_aligned from a misaligned slice");
+        unsafe { Self::load_unchecked(data, 0) }
+    }
+
+    /// Create a new vector with `Self::WIDTH` elements from `data`, beginning
+    /// at `offset`, without asserting length or alignment of data.
+    #[inline(always)]
+    unsafe fn load_aligned_unchecked(data: &[Self::Scalar], offset: usize) -> Self {
+        Self::load_unchecked(data, offset)
+    }
+
+    /// Write `Self::WIDTH` elements from this vector to `data`, beginning at
+    /// `offset`, which should be aligned to the alignment of this vector.
+    /// Debug builds assert the alignment, and release builds fall back to an
+    /// unaligned store if it isn't aligned.
+    #[inline(always)]
+    fn store_aligned(self, data: &mut [Self::Scalar], offset: usize) {
+        let data = &mut data[offset..];
+        assert!(data.len() >= Self::WIDTH);
+        debug_assert!(data.as_ptr() as usize % crate::core::mem::align_of::<Self>() == 0,
+                      "store_aligned to a misaligned slice");
+        unsafe { self.store_unchecked(data, 0) }
+    }
+
+    /// Write `Self::WIDTH` elements from this vector to `data`, beginning at
+    /// `offset`, without asserting length or alignment of data.
+    #[inline(always)]
+    unsafe fn store_aligned_unchecked(self, data: &mut [Self::Scalar], offset: usize) {
+        self.store_unchecked(data, offset)
+    }
+
     /// Assert all elements of the vector are equal, then return the
     /// element. Opposite operation of `Self::splat`.
     fn coalesce(self) -> Self::Scalar;
@@ -84,6 +173,11 @@ pub trait Packable where Self : Sized + Copy + Debug {
 
 macro_rules! impl_packed {
     ($el:tt, $pvec:tt, $vec:tt, $sz:expr, $width:expr, [$($feat:expr),*], [$($nfeat:expr),*]) => (
+        impl_packed!($el, $pvec, $vec, $sz, $width, [$($feat),*], [$($nfeat),*], all());
+    );
+    // `$packable` further restricts when `$vec` is the vector of `$el`, as
+    // only one backend may implement `Packable` when several are compiled.
+    ($el:tt, $pvec:tt, $vec:tt, $sz:expr, $width:expr, [$($feat:expr),*], [$($nfeat:expr),*], $packable:meta) => (
 
         /// A SIMD vector of this primitive type.
         #[allow(non_camel_case_types)]
@@ -98,7 +192,7 @@ macro_rules! impl_packed {
             $vec::splat(data)
         }
 
-        #[cfg(all($(target_feature = $feat,)* not($(target_feature = $nfeat)*)))]
+        #[cfg(all($(target_feature = $feat,)* not($(target_feature = $nfeat)*), $packable))]
         impl Packable for $el {
             type Vector = $vec;
             const SIZE: usize = $sz;
@@ -111,7 +205,10 @@ macro_rules! impl_packed {
 
             #[inline(always)]
             fn load(data: &[$el], offset: usize) -> $vec {
-                $vec::from_slice_unaligned(&data[offset..])
+                match Self::try_load(data, offset) {
+                    Ok(v) => v,
+                    Err(e) => panic!("{}", e),
+                }
             }
 
             #[inline(always)]
@@ -122,7 +219,9 @@ macro_rules! impl_packed {
 
             #[inline(always)]
             fn store(self, data: &mut [$el], offset: usize) {
-                $vec::write_to_slice_unaligned(self, &mut data[offset..]);
+                if let Err(e) = self.try_store(data, offset) {
+                    panic!("{}", e);
+                }
             }
 
             #[inline(always)]
@@ -131,6 +230,50 @@ macro_rules! impl_packed {
                 $vec::write_to_slice_unaligned_unchecked(self, &mut data[offset..]);
             }
 
+            #[inline(always)]
+            fn load_aligned(data: &[$el], offset: usize) -> $vec {
+                let data = &data[offset..];
+                assert!(data.len() >= Self::WIDTH);
+                let aligned = data.as_ptr() as usize % crate::core::mem::align_of::<$vec>() == 0;
+                debug_assert!(aligned, "load_aligned from a misaligned slice");
+                unsafe {
+                    if aligned {
+                        $vec::from_slice_aligned_unchecked(data)
+                    } else {
+                        $vec::from_slice_unaligned_unchecked(data)
+                    }
+                }
+            }
+
+            #[inline(always)]
+            unsafe fn load_aligned_unchecked(data: &[$el], offset: usize) -> $vec {
+                debug_assert!(data[offset..].len() >= Self::WIDTH);
+                debug_assert!(data[offset..].as_ptr() as usize % crate::core::mem::align_of::<$vec>() == 0);
+                $vec::from_slice_aligned_unchecked(data.get_unchecked(offset..))
+            }
+
+            #[inline(always)]
+            fn store_aligned(self, data: &mut [$el], offset: usize) {
+                let data = &mut data[offset..];
+                assert!(data.len() >= Self::WIDTH);
+                let aligned = data.as_ptr() as usize % crate::core::mem::align_of::<$vec>() == 0;
+                debug_assert!(aligned, "store_aligned to a misaligned slice");
+                unsafe {
+                    if aligned {
+                        $vec::write_to_slice_aligned_unchecked(self, data)
+                    } else {
+                        $vec::write_to_slice_unaligned_unchecked(self, data)
+                    }
+                }
+            }
+
+            #[inline(always)]
+            unsafe fn store_aligned_unchecked(self, data: &mut [$el], offset: usize) {
+                debug_assert!(data[offset..].len() >= Self::WIDTH);
+                debug_assert!(data[offset..].as_ptr() as usize % crate::core::mem::align_of::<$vec>() == 0);
+                $vec::write_to_slice_aligned_unchecked(self, data.get_unchecked_mut(offset..))
+            }
+
             #[inline(always)]
             fn coalesce(self) -> Self::Scalar {
                 for i in 1..Self::WIDTH {
@@ -192,3 +335,26 @@ macro_rules! impl_packed {
     );
 }
 
+macro_rules! impl_width {
+    ($width:ident, $doc:expr, $($el:tt, $pvec:tt, $vec:tt),*) => (
+        #[doc = $doc]
+        pub mod $width {
+            use crate::vecs::Packed;
+            pub use super::super::vecs::{$($vec),*};
+
+            $(
+                /// An explicitly-sized SIMD vector of this primitive type.
+                #[allow(non_camel_case_types)]
+                pub type $pvec = $vec;
+
+                /// Return a vector of this type with all elements initialized
+                /// to `data`.
+                #[inline(always)]
+                pub fn $pvec(data: $el) -> $pvec {
+                    <$vec as Packed>::splat(data)
+                }
+            )*
+        }
+    );
+}
+
@@ -7,6 +7,8 @@
 
 use crate::iters::{SIMDIterator, SIMDIterable, SIMDObject, UnsafeIterator, SIMDSized};
 use crate::vecs::{Packed, Packable};
+use crate::restride::Interleaved;
+use crate::error::Error;
 
 /// A macro which takes a number n and an expression, and returns a tuple
 /// containing n copies of the expression. Only works for numbers less than or
@@ -53,7 +55,32 @@ pub struct SIMDZipMap<I, F> where I : SIMDZippedIterator {
 /// A trait which can transform a collection of iterators into a `Zip`
 pub trait IntoSIMDZip : Sized {
     /// Return an iterator which may iterate over `self` in lockstep.
-    fn zip(self) -> Zip<Self>;
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterators have different lengths.
+    #[inline(always)]
+    fn zip(self) -> Zip<Self> {
+        match self.try_zip() {
+            Ok(zip) => zip,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Return an iterator which may iterate over `self` in lockstep, or an
+    /// error if the iterators have different lengths.
+    ///
+    /// ```
+    /// extern crate faster;
+    /// use faster::*;
+    ///
+    /// # fn main() {
+    /// let a = [0u32; 64];
+    /// let b = [0u32; 32];
+    /// assert!((a.simd_iter(u32s(0)), b.simd_iter(u32s(0))).try_zip().is_err());
+    /// # }
+    /// ```
+    fn try_zip(self) -> Result<Zip<Self>, Error>;
 }
 
 pub trait SIMDZippedObject : Sized {
@@ -133,10 +160,12 @@ pub trait SIMDZippedIterator : SIMDZippedIterable {
     /// Elements which are not filled are instead initialized to default.
     fn end(&mut self) -> Option<(Self::Vectors, usize)>;
 
-    /// Return an iterator which calls `func` on vectors of elements.
+    /// Return an iterator which calls `func` on vectors of elements. `func`
+    /// may return a single vector, or a tuple of vectors which may be
+    /// interleaved with `IntoScalarInterleaved`.
     #[inline(always)]
-    fn simd_map<A, B, F>(self, func: F) -> SIMDZipMap<Self, F>
-        where F : FnMut(Self::Vectors) -> A, A : Packed<Scalar = B>, B : Packable {
+    fn simd_map<A, F>(self, func: F) -> SIMDZipMap<Self, F>
+        where F : FnMut(Self::Vectors) -> A {
         SIMDZipMap {
             iter: self,
             func: func,
@@ -224,11 +253,16 @@ macro_rules! impl_iter_zip {
     (($($a:tt),*), ($($b:tt),*), ($($n:tt),*)) => (
         impl<$($a),*> IntoSIMDZip for ($($a),*) where $($a : SIMDIterator + UnsafeIterator),* {
             #[inline(always)]
-            fn zip(self) -> Zip<Self> {
-                if $(self.0.len() != self.$n.len())||* {
-                    panic!("You can only zip iterators of the same length.");
-                }
-                Zip { iters: self }
+            fn try_zip(self) -> Result<Zip<Self>, Error> {
+                $(
+                    if self.0.len() != self.$n.len() {
+                        return Err(Error::LengthMismatch {
+                            expected: self.0.len(),
+                            found: self.$n.len(),
+                        });
+                    }
+                )*
+                Ok(Zip { iters: self })
             }
         }
 
@@ -303,7 +337,7 @@ macro_rules! impl_iter_zip {
 }
 
 impl<I, F, A> Iterator for SIMDZipMap<I, F>
-    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : Packed {
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A {
     type Item = A;
 
     #[inline(always)]
@@ -313,7 +347,7 @@ impl<I, F, A> Iterator for SIMDZipMap<I, F>
 }
 
 impl<I, F, A> ExactSizeIterator for SIMDZipMap<I, F>
-    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : Packed {
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A {
     #[inline(always)]
     fn len(&self) -> usize {
         self.iter.len()
@@ -370,6 +404,51 @@ where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : Packed {
     }
 }
 
+impl<I, F, A> SIMDZippedObject for SIMDZipMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : Interleaved {
+    type Vectors = A;
+    type Scalars = A::Scalars;
+
+    #[inline(always)]
+    fn width(&self) -> usize {
+        <A::Vector as Packed>::WIDTH
+    }
+
+    #[inline(always)]
+    fn size(&self) -> usize {
+        A::Scalar::SIZE
+    }
+}
+
+impl<I, F, A> SIMDZippedIterable for SIMDZipMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : Interleaved {
+    #[inline(always)]
+    fn scalar_pos(&self) -> usize {
+        self.iter.scalar_pos()
+    }
+
+    #[inline(always)]
+    fn advance(&mut self, amount: usize) {
+        self.iter.advance(amount)
+    }
+
+    #[inline(always)]
+    fn default(&self) -> Self::Vectors {
+        // TODO: Is there a more sane return value (without invoking the closure)?
+        <A as Interleaved>::default()
+    }
+}
+
+impl<I, F, A> SIMDZippedIterator for SIMDZipMap<I, F>
+    where I : SIMDZippedIterator, F : FnMut(I::Vectors) -> A, A : Interleaved {
+    #[inline(always)]
+    fn end(&mut self) -> Option<(Self::Vectors, usize)> {
+        let (v, n) = self.iter.end()?;
+        let nr = n * self.iter.size() / SIMDZippedObject::size(self);
+        Some(((self.func)(v), nr))
+    }
+}
+
 impl_iter_zip!((A, B),
                (AA, BB),
                (1));
@@ -36,4 +36,88 @@ mod tests {
         let sum = vec.simd_iter(u32s(0u32)).simd_reduce(u32s(0u32), |acc, x| acc + x).sum();
         assert_eq!(sum, 2 * 129);
     }
+
+    #[test]
+    fn prefetch_far_ahead() {
+        let vec = [2u32; 129];
+        let sum = vec.simd_iter(u32s(0u32)).prefetch(usize::max_value(), Locality::None)
+            .simd_reduce(u32s(0u32), |acc, x| acc + x).sum();
+        assert_eq!(sum, 2 * 129);
+
+        let data = (0..1024u32).collect::<Vec<u32>>();
+        let mut columns = (&data[..]).stride(32, &[u32s(0); 32]);
+        let sum = columns.remove(0).prefetch(usize::max_value(), Locality::High)
+            .simd_reduce(u32s(0), |acc, v| acc + v).sum();
+        assert_eq!(sum, (0..32).map(|x| x * 32).sum::<u32>());
+    }
+
+    #[test]
+    fn explicit_width_reduce() {
+        let vec = [2u32; 129];
+        let sum = vec.simd_iter(w128::u32s(0u32)).simd_reduce(w128::u32s(0u32), |acc, x| acc + x).sum();
+        assert_eq!(sum, 2 * 129);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"), not(feature = "force-polyfill")))]
+    fn explicit_width_mutation() {
+        let test = |len: usize| {
+            let mut narrow: Vec<i16> = (0..len).map(|x| x as i16 - 50).collect();
+            let mut wide = narrow.clone();
+            let mut widest = narrow.clone();
+
+            narrow.simd_iter_mut(w128::i16s(0)).simd_for_each(|x| *x = x.abs().be_i16s());
+            wide.simd_iter_mut(w256::i16s(0)).simd_for_each(|x| *x = x.abs().be_i16s());
+            widest.simd_iter_mut(w512::i16s(0)).simd_for_each(|x| *x = x.abs().be_i16s());
+
+            assert_eq!(narrow, (0..len).map(|x| (x as i16 - 50).abs()).collect::<Vec<i16>>());
+            assert_eq!(narrow, wide);
+            assert_eq!(narrow, widest);
+        };
+
+        for len in 0..100 {
+            test(len);
+        }
+    }
+
+    #[test]
+    fn try_load_store() {
+        let mut data = [1u32; 64];
+        for offset in 0..data.len() {
+            let fits = offset + u32s::WIDTH <= data.len();
+            assert_eq!(u32s::try_load(&data, offset).is_ok(), fits);
+            assert_eq!(u32s(2).try_store(&mut data, offset).is_ok(), fits);
+        }
+        assert_eq!(u32s::try_load(&data, usize::max_value()).err(),
+                   Some(Error::OutOfBounds { offset: usize::max_value(), width: u32s::WIDTH, len: 64 }));
+        assert_eq!(&data[..data.len() - u32s::WIDTH], &[2u32; 64][..data.len() - u32s::WIDTH]);
+    }
+
+    #[test]
+    fn try_scalar_fill() {
+        for len in 0..100 {
+            let data: Vec<u8> = (0..len).map(|x| x as u8).collect();
+            let mut fill = vec![0u8; len + 1];
+            for short in 0..len {
+                assert!(data.simd_iter(u8s(0)).simd_map(|v| v).try_scalar_fill(&mut fill[..short]).is_err());
+            }
+            assert_eq!(data.simd_iter(u8s(0)).simd_map(|v| v).try_scalar_fill(&mut fill[..len]).unwrap(), &data[..]);
+            assert!(data.simd_iter(u8s(0)).simd_map(|v| v).try_scalar_fill(&mut fill).is_ok());
+        }
+    }
+
+    #[test]
+    fn try_scalar_fill_streaming() {
+        for len in 0..100 {
+            let data: Vec<u32> = (0..len).map(|x| x as u32).collect();
+            let mut fill = vec![!0u32; len + 1];
+            for short in 0..len {
+                assert_eq!(data.simd_iter(u32s(0)).simd_map(|v| v).try_scalar_fill_streaming(&mut fill[..short]).err(),
+                           Some(Error::BufferTooShort { needed: len, len: short }));
+            }
+            // Nothing is stored into a buffer which is too short
+            assert!(fill.iter().all(|&x| x == !0));
+            assert_eq!(data.simd_iter(u32s(0)).simd_map(|v| v).try_scalar_fill_streaming(&mut fill[..len]).unwrap(), &data[..]);
+        }
+    }
 }
@@ -0,0 +1,82 @@
+// This file is part of faster, the SIMD library for humans.
+// Copyright 2017 Adam Niederer <adam.niederer@gmail.com>
+
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Compare the native backend against the portable one, which the `polyfill`
+// feature compiles beside it.
+
+#![cfg(all(feature = "polyfill", not(feature = "force-polyfill"),
+           any(target_arch = "x86", target_arch = "x86_64")))]
+#![feature(stdsimd)]
+
+extern crate faster;
+
+#[cfg(test)]
+mod tests {
+    use faster::*;
+    use faster::arch::polyfill::vecs as poly;
+
+    fn data(len: usize) -> Vec<u64> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        }).collect()
+    }
+
+    fn lanes<V : Packed>(v: V) -> Vec<V::Scalar> {
+        (0..V::WIDTH).map(|i| v.extract(i)).collect()
+    }
+
+    macro_rules! differential {
+        ($name:ident, |$a:ident, $b:ident| $op:expr, $($el:tt, $native:ty, $poly:ty);*) => {
+            #[test]
+            fn $name() {
+                $(
+                    let width = <$poly as Packed>::WIDTH;
+                    let edges = [$el::min_value(), $el::max_value(), 0, 1];
+                    let scalars = data(width * 2 * 256).iter().map(|&x| x as $el)
+                        .chain(edges.iter().cloned().cycle().take(width * 2))
+                        .collect::<Vec<$el>>();
+                    for chunk in scalars.chunks(width * 2) {
+                        let native = {
+                            let ($a, $b) = (<$native>::load(chunk, 0), <$native>::load(chunk, width));
+                            lanes($op)
+                        };
+                        let polyfill = {
+                            let ($a, $b) = (<$poly>::load(chunk, 0), <$poly>::load(chunk, width));
+                            lanes($op)
+                        };
+                        assert_eq!(native, polyfill, "{} of {:?}", stringify!($name), chunk);
+                    }
+                )*
+            }
+        }
+    }
+
+    differential!(abs, |a, _b| a.abs(),
+                  i8, w128::i8s, poly::i8x16; i16, w128::i16s, poly::i16x8; i32, w128::i32s, poly::i32x4);
+
+    differential!(saturating_add, |a, b| a.saturating_add(b),
+                  u8, w128::u8s, poly::u8x16; i8, w128::i8s, poly::i8x16;
+                  u16, w128::u16s, poly::u16x8; i16, w128::i16s, poly::i16x8);
+
+    differential!(saturating_sub, |a, b| a.saturating_sub(b),
+                  u8, w128::u8s, poly::u8x16; i8, w128::i8s, poly::i8x16;
+                  u16, w128::u16s, poly::u16x8; i16, w128::i16s, poly::i16x8);
+
+    differential!(min, |a, b| a.min(b),
+                  u8, w128::u8s, poly::u8x16; i8, w128::i8s, poly::i8x16;
+                  u16, w128::u16s, poly::u16x8; i16, w128::i16s, poly::i16x8;
+                  u32, w128::u32s, poly::u32x4; i32, w128::i32s, poly::i32x4);
+
+    differential!(max, |a, b| a.max(b),
+                  u8, w128::u8s, poly::u8x16; i8, w128::i8s, poly::i8x16;
+                  u16, w128::u16s, poly::u16x8; i16, w128::i16s, poly::i16x8;
+                  u32, w128::u32s, poly::u32x4; i32, w128::i32s, poly::i32x4);
+}
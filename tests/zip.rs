@@ -67,6 +67,39 @@ mod tests {
                 .fold(true, |acc, (a, b)| acc && a == b));
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn zip_fill_interleaved() {
+        for n in 0..(u8s::WIDTH * 3 + 2) {
+            let r = (0..n).map(|i| i as u8).collect::<Vec<_>>();
+            let g = (0..n).map(|i| (i as u8).wrapping_add(100)).collect::<Vec<_>>();
+            let b = (0..n).map(|i| (i as u8).wrapping_add(200)).collect::<Vec<_>>();
+            let rgb = (0..n).flat_map(|i| vec![r[i], g[i], b[i]]).collect::<Vec<_>>();
+
+            let mut out = vec![0u8; n * 3];
+            (r.simd_iter(u8s(0)), g.simd_iter(u8s(0)), b.simd_iter(u8s(0))).zip()
+                .scalar_fill_interleaved(&mut out);
+            assert_eq!(out, rgb);
+
+            let out = (r.simd_iter(u8s(0)), g.simd_iter(u8s(0)), b.simd_iter(u8s(0))).zip()
+                .simd_map(|(r, g, b)| (b, g, r))
+                .scalar_collect_interleaved();
+            assert_eq!(out, rgb.chunks(3).flat_map(|c| vec![c[2], c[1], c[0]]).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn stride_zip_restride() {
+        for n in 0..(f32s::WIDTH * 5) {
+            let matrices = (0..(n * 2)).map(|i| i as f32).collect::<Vec<_>>();
+            let flipped = (&matrices[..]).stride_two(tuplify!(2, f32s(0.0))).zip()
+                .simd_map(|(a, b)| (b, a))
+                .scalar_collect_interleaved();
+            assert_eq!(flipped, matrices.chunks(2).flat_map(|c| vec![c[1], c[0]]).collect::<Vec<_>>());
+        }
+    }
+
     #[test]
     fn zip_simd_reduce() {
         let vec1 = [2u32; 129];
@@ -75,4 +108,20 @@ mod tests {
         assert_eq!(result, 2 * 3 * 129);
     }
 
+    #[test]
+    fn try_zip_mismatched() {
+        let vec1 = [2u32; 129];
+        let vec2 = [3u32; 257];
+        let zip = (vec1.simd_iter(u32s(0u32)), vec2.simd_iter(u32s(0u32))).try_zip();
+        assert_eq!(zip.err(), Some(Error::LengthMismatch { expected: 129 / u32s::WIDTH, found: 257 / u32s::WIDTH }));
+        assert!((vec1.simd_iter(u32s(0u32)), vec1.simd_iter(u32s(0u32))).try_zip().is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zip_mismatched() {
+        let vec1 = [2u32; 129];
+        let vec2 = [3u32; 257];
+        (vec1.simd_iter(u32s(0u32)), vec2.simd_iter(u32s(0u32))).zip();
+    }
 }